@@ -1,19 +1,61 @@
-use crate::enums::{InputMode, Library, Menu, SearchMenu};
+use crate::enums::{
+    AuthFlow, FilterField, InputMode, Library, Menu, SearchCategory, SetupStep, Severity, Task,
+    View,
+};
+use crate::event_log::{EventLog, LogEntry};
 use crate::handlers::key_event::handle_key_event;
 use crate::handlers::key_event::search_input;
-use crate::spotify::player::player::process_currently_playing;
-use crate::structs::{Key, Settings, Themes};
+use crate::handlers::search::live_search_event;
+use crate::handlers::setup::start_profile_login;
+use crate::handlers::task::task_completed_event;
+use crate::models::{Album, Artist, Playlist, Show, Track};
+use crate::settings::creds::write_creds;
+use crate::settings::paths::DEFAULT_PROFILE;
+use crate::settings::profile::load_profile;
+use crate::spotify::account::Account;
+use crate::spotify::auth::{check_credentials, forget_token};
+use crate::spotify::backend::{
+    build_backend, build_backend_with, fake_backend_requested,
+    request::{describe, RequestStatus},
+    SpotifyBackend,
+};
+use crate::spotify::search::{
+    query::{SavedSearch, SearchFilters},
+    search::SearchPaging,
+};
+use crate::spotify::task::handle_tasks;
+use crate::structs::{Key, Navigation, Notifications, SearchHistory, Settings, Setup, Themes};
+use crate::ui::selectable::Selectable;
 use crate::ui::tui;
 use crate::ui::ui::render_frame;
+use crate::util::{startup, update_player_info};
 use crossterm::event::{self, Event};
-use ratatui::widgets::{ListState, TableState};
+use ratatui::widgets::ListState;
+use rspotify::model::Market;
+use rspotify::ClientError;
 use std::io;
-use std::sync::mpsc::Receiver;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio::task::JoinHandle;
 
 #[derive(Clone, Debug)]
 pub struct App {
-    pub file_name: String,
+    // Where the configuration files and the cached data are kept
+    pub config_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    // Keeps a copy of the fetched Spotify data on disk to fill the screens at startup
+    pub disk_cache: bool,
+    // Country the search results have to be playable in, the account's own by default
+    pub market: Market,
+    // Leaves the tracks that can't be played in `market` out of the search results instead of flagging them
+    pub hide_unplayable: bool,
+    // Searches while the query is typed, once no key has been pressed for `live_search_delay`
+    pub live_search: bool,
+    pub live_search_delay: Duration,
+
     // Controls whether the application should exit
     pub exit: bool,
 
@@ -23,186 +65,80 @@ pub struct App {
     // Controls the navigation inside Menu
     pub selected_menu: Menu,
     pub can_navigate_menu: bool,
+    pub navigation: Navigation,
 
     // Controls the navigation inside Library
-    pub selected_library: Library,
-    pub library_state: ListState,
+    pub library: Selectable<Library>,
 
     // Handles Search function
     pub search_query: String,
     pub input: String,
     pub cursor_position: usize,
     pub input_mode: InputMode,
-    pub search_results_rendered: bool,
-    pub search_menu: SearchMenu,
-
-    pub album_names_search_results: Vec<String>,
-    pub track_names_search_results: Vec<String>,
-    pub playlist_names_search_results: Vec<String>,
-    pub artist_names_search_results: Vec<String>,
 
-    pub album_links_search_results: Vec<String>,
-    pub track_links_search_results: Vec<String>,
-    pub playlist_links_search_results: Vec<String>,
-    pub artist_links_search_results: Vec<String>,
-
-    pub album_index: usize,
-    pub track_index: usize,
-    pub playlist_index: usize,
-    pub artist_index: usize,
+    pub searched_albums: Selectable<Album>,
+    pub searched_tracks: Selectable<Track>,
+    pub searched_playlists: Selectable<Playlist>,
+    pub searched_artists: Selectable<Artist>,
 
     pub selected_album_in_search_result: bool,
     pub selected_track_in_search_result: bool,
     pub selected_playlist_in_search_result: bool,
     pub selected_artist_in_search_result: bool,
-    pub selected_search: bool,
 
     pub search_state: ListState,
-    pub album_state_in_search_result: ListState,
-    pub track_state_in_search_result: ListState,
-    pub playlist_state_in_search_result: ListState,
-    pub artist_state_in_search_result: ListState,
-
-    pub selected_album_tracks_names: Vec<String>,
-    pub selected_album_tracks_artists: Vec<String>,
-    pub selected_album_tracks_duration: Vec<i64>,
-    pub selected_album_tracks_links: Vec<String>,
-    pub searched_album_selected: bool,
-    pub searched_album_state: TableState,
-    pub searched_album_index: usize,
-
-    pub selected_playlist_tracks_names: Vec<String>,
-    pub selected_playlist_tracks_artists: Vec<String>,
-    pub selected_playlist_tracks_duration: Vec<i64>,
-    pub selected_playlist_tracks_albums: Vec<String>,
-    pub selected_playlist_tracks_links: Vec<String>,
-    pub searched_playlist_selected: bool,
-    pub searched_playlist_state: TableState,
-    pub searched_playlist_index: usize,
-
-    pub selected_artist_tracks_names: Vec<String>,
-    pub selected_artist_tracks_duration: Vec<i64>,
-    pub selected_artist_tracks_links: Vec<String>,
-    pub selected_artist_track_album_names: Vec<String>,
-    pub searched_artist_selected: bool,
-    pub searched_artist_state: TableState,
-    pub searched_artist_index: usize,
+    // How far each type of results has been fetched, and the type whose next page is on its way
+    pub search_paging: SearchPaging,
+    pub search_loading: Option<SearchCategory>,
+    // Counts the searches started, a request made for an earlier one is stale even when the query is the same
+    pub search_generation: u64,
+    // The types whose first page hasn't come back yet, and when the query typed so far is searched for
+    pub search_pending: Vec<SearchCategory>,
+    pub live_search_due: Option<Instant>,
+    // Filters added to every search, and the form they are picked on
+    pub search_filters: SearchFilters,
+    pub search_filter_fields: Selectable<FilterField>,
+    // Queries searched lately, the searches kept under a name and the last one made, which can be saved
+    pub search_history: SearchHistory,
+    pub saved_searches: Selectable<SavedSearch>,
+    pub saved_search_name: String,
+    pub last_search: Option<SavedSearch>,
+
+    pub selected_album_tracks: Selectable<Track>,
+
+    pub selected_playlist_tracks: Selectable<Track>,
+
+    pub selected_artist_tracks: Selectable<Track>,
 
     // Handles User's playlists
-    pub user_playlist_names: Vec<String>,
-    pub user_playlist_artist_names: Vec<String>,
-    pub user_playlist_track_names: Vec<String>,
-    pub user_playlist_album_names: Vec<String>,
-
-    pub user_playlist_links: Vec<String>,
-    pub user_playlist_track_links: Vec<String>,
-
-    pub user_playlist_track_duration: Vec<i64>,
+    pub user_playlists: Selectable<Playlist>,
+    pub user_playlist_tracks: Selectable<Track>,
 
     pub current_user_playlist: String,
-    pub selected_playlist_uri: String,
-
-    pub user_playlist_display: bool,
-    pub user_playlist_tracks_selected: bool,
-
-    pub user_playlist_state: ListState,
-    pub user_playlist_tracks_state: TableState,
-    pub user_playlist_index: usize,
-
-    pub enter_for_playback_in_user_playlist: bool,
+    pub selected_playlist_id: String,
 
     // Handles User's Liked Songs
-    pub liked_song_names: Vec<String>,
-    pub liked_song_links: Vec<String>,
-    pub liked_song_duration: Vec<i64>,
-    pub liked_song_artist_names: Vec<String>,
-    pub liked_song_album_names: Vec<String>,
-    pub liked_songs_selected: bool,
-    pub liked_song_display: bool,
-    pub liked_songs_state: TableState,
-    pub liked_songs_index: usize,
-    pub enter_for_playback_in_liked_song: bool,
+    pub liked_songs: Selectable<Track>,
 
     // Handles User's Saved Albums
-    pub user_album_names: Vec<String>,
-    pub user_album_links: Vec<String>,
-    pub user_album_artist_names: Vec<String>,
-    pub user_album_tracks: Vec<usize>,
-    pub user_album_selected: bool,
-    pub user_album_display: bool,
-    pub user_album_state: TableState,
-    pub user_album_index: usize,
-
-    pub user_album_track_names: Vec<String>,
-    pub user_album_track_artist: Vec<String>,
-    pub user_album_track_duration: Vec<i64>,
-    pub user_album_track_index: usize,
-    pub user_album_track_state: TableState,
-    pub user_album_track_display: bool,
-    pub user_album_track_selected: bool, // for a track list that is selected
-    pub user_album_current_album_selected: bool, // for a album that is selected
-    pub user_album_track_links: Vec<String>,
-    pub enter_for_playback_in_user_album: bool,
+    pub user_albums: Selectable<Album>,
+
+    pub user_album_tracks: Selectable<Track>,
 
     // Handles User's Saved Podcasts
-    pub podcast_names: Vec<String>,
-    pub podcast_links: Vec<String>,
-    pub podcast_publisher: Vec<String>,
-    pub podcast_selected: bool,
-    pub podcast_display: bool,
-    pub podcast_state: TableState,
-    pub podcast_index: usize,
+    pub podcasts: Selectable<Show>,
 
     // Handles User's Recently Played Songs
-    pub recently_played_names: Vec<String>,
-    pub recently_played_links: Vec<String>,
-    pub recently_played_duration: Vec<i64>,
-    pub recently_played_artist_names: Vec<String>,
-    pub recently_played_album_names: Vec<String>,
-    pub recently_played_selected: bool,
-    pub recently_played_display: bool,
-    pub recently_played_state: TableState,
-    pub recently_played_index: usize,
-    pub enter_for_playback_in_recently_played: bool,
+    pub recently_played: Selectable<Track>,
 
     // Handles User's Saved Artists
-    pub user_artist_names: Vec<String>,
-    pub user_artist_links: Vec<String>,
-    pub user_artist_selected: bool,
-    pub user_artist_display: bool,
-    pub user_artist_state: TableState,
-    pub user_artist_index: usize,
-    pub enter_for_playback_in_saved_artist: bool,
-
-    pub user_artist_track_names: Vec<String>,
-    pub user_artist_track_album: Vec<String>,
-    pub user_artist_track_duration: Vec<i64>,
-    pub user_artist_track_index: usize,
-    pub user_artist_track_state: TableState,
-    pub user_artist_track_display: bool,
-    pub user_artist_track_selected: bool,
-    pub user_artist_current_artist_selected: bool,
-    pub user_artist_track_links: Vec<String>,
+    pub user_artists: Selectable<Artist>,
+
+    pub user_artist_tracks: Selectable<Track>,
 
     // Handles Made For You
-    pub made_fy_playlist_names: Vec<String>,
-    pub made_fy_playlist_links: Vec<String>,
-    pub made_fy_selected: bool,
-    pub made_fy_display: bool,
-    pub made_fy_state: TableState,
-    pub made_fy_index: usize,
-    pub made_fy_playlist_track_total: Vec<i64>,
-    pub made_fy_track_links: Vec<String>,
-    pub made_fy_track_names: Vec<String>,
-    pub made_fy_track_duration: Vec<i64>,
-    pub made_fy_artist_names: Vec<String>,
-    pub made_fy_album_names: Vec<String>,
-    pub made_fy_track_display: bool,
-    pub made_fy_track_state: TableState,
-    pub made_fy_track_index: usize,
-    pub made_fy_track_selected: bool, // for a track list that is selected
-    pub made_fy_current_playlist_selected: bool, // for a playlist that is selected
-    pub enter_for_playback_in_made_fy: bool,
+    pub made_fy_playlists: Selectable<Playlist>,
+    pub made_fy_tracks: Selectable<Track>,
 
     // Handles User's currently playing device
     pub current_device_name: String,
@@ -223,88 +159,231 @@ pub struct App {
     pub currently_playing_media_type: String,
 
     // Handle New Release section
-    pub new_release_artist: Vec<String>,
-    pub new_release_name: Vec<String>,
-    pub new_release_state: ListState,
+    pub new_releases: Selectable<Album>,
     pub current_new_release: String,
-    pub new_release_display: bool,
-    pub new_release_album_selected: bool,
-    pub new_release_album_state: TableState,
-    pub new_release_album_links: Vec<String>,
-    pub current_new_release_album: String,
-    pub current_new_release_album_link: String,
-    pub new_release_index: usize,
-
-    pub new_release_track_names: Vec<String>,
-    pub new_release_artist_names: Vec<String>,
-    pub new_release_durations_ms: Vec<i64>,
-    pub new_release_spotify_urls: Vec<String>,
-    pub enter_for_playback_in_new_release: bool,
+    pub current_new_release_album_id: String,
+
+    pub new_release_tracks: Selectable<Track>,
 
     // Creds
     pub client_id: String,
     pub client_secret: String,
-
-    // Error
+    pub auth_flow: AuthFlow,
+    // First run setup, shown while there are no creds, and the login again from the account screen
+    pub setup: Setup,
+    // Account screen, filled once its request finishes
+    pub account: Option<Account>,
+    // Set once Spotify stops accepting the token, until the user logs in again
+    pub login_expired: bool,
+
+    // Profiles, each one has its own folders inside the root configuration and cache directories
+    pub profile: String,
+    pub root_config_dir: PathBuf,
+    pub root_cache_dir: PathBuf,
+    pub profiles: Selectable<String>,
+    // Set by the profile screen, the main loop switches to it
+    pub switch_to_profile: Option<String>,
+
+    // Error, the error screen is only used for problems the app can't carry on from
     pub error_text: String,
+    pub notifications: Notifications,
+    pub log: EventLog,
+    // Entries shown on the log screen, only the ones of `log_filter` if it is set
+    pub log_entries: Selectable<LogEntry>,
+    pub log_filter: Option<Severity>,
 
     // Playback
-    pub selected_link_for_playback: String,
-    pub is_only_id: bool,
+    pub selected_track_id: String,
     pub is_in_track: bool,
 
     // Top Tracks
-    pub top_tracks_all_time_names: Vec<String>,
-    pub top_tracks_6_months_names: Vec<String>,
-    pub top_tracks_4_weeks_names: Vec<String>,
+    pub top_tracks_all_time: Vec<Track>,
+    pub top_tracks_6_months: Vec<Track>,
+    pub top_tracks_4_weeks: Vec<Track>,
 
     // Add track to playlist
-    pub add_track_to_playlist_state: ListState,
+    pub add_track_to_playlist: Selectable<Playlist>,
     pub track_added_to_playlist_name: String,
-    pub playlist_index_for_track_addition: usize,
-    pub track_added_to_playlist_link: String,
-    pub playlist_link_for_track_addition: String,
+    pub track_added_to_playlist_id: String,
+    pub playlist_id_for_track_addition: String,
 
     // Follow/Unfollow Playlist
-    pub playlist_link_to_follow: String,
+    pub playlist_id_to_follow: String,
     pub have_playlist: bool,
+
+    // Queue for the background Spotify requests
+    pub task_sender: Option<UnboundedSender<(Task, App)>>,
+
+    // Spotify backend shared by every request, the authenticated client or the in-memory one
+    pub backend: Option<Arc<dyn SpotifyBackend>>,
+    // Rate limits the backend has run into, shown in the player
+    pub requests: RequestStatus,
 }
 
 impl App {
     /// Runs the application's main loop until the user quits
     pub fn run(
         &mut self,
-        terminal: &mut tui::Tui,
-        rx1: Receiver<()>,
         keys: &mut Key,
         theme: &mut Themes,
         settings: &mut Settings,
     ) -> io::Result<()> {
+        // A single runtime serves every Spotify request for the lifetime of the app
+        let runtime: Runtime = Runtime::new()?;
+
+        // Without creds the setup screen asks for them and logs in, nothing is fetched until then
+        let needs_setup = self.client_id.is_empty() && !fake_backend_requested();
+        if !needs_setup {
+            // Authenticate before the terminal enters raw mode, the authorization flow needs stdin
+            match runtime.block_on(build_backend(self)) {
+                Ok(backend) => self.backend = Some(backend),
+                Err(e) => return Err(io::Error::other(describe(&e))),
+            }
+        }
+
+        let (task_tx, task_rx) = mpsc::unbounded_channel();
+        let (result_tx, mut result_rx) = mpsc::unbounded_channel();
+        self.task_sender = Some(task_tx);
+
+        runtime.spawn(handle_tasks(task_rx, result_tx.clone()));
+        let mut player_updates = None;
+        let mut login: Option<JoinHandle<Result<Arc<dyn SpotifyBackend>, ClientError>>> = None;
+        // The profile being switched to and its warnings, the app changes over once its login is done
+        let mut next_profile: Option<(App, Vec<String>)> = None;
+
+        if needs_setup {
+            self.selected_menu = Menu::Setup;
+        } else {
+            player_updates =
+                Some(runtime.spawn(update_player_info(result_tx.clone(), self.clone())));
+            // Set keybinds and themes and queue the data needed by the first screens
+            startup(self, keys, theme, settings);
+        }
+
+        let mut terminal = tui::init()?;
+
         let mut last_tick: Instant = Instant::now();
         // Set the duration for refreshing UI
         let timeout: Duration = Duration::from_millis(200);
 
         while !self.exit {
             // Handling user inputs
-            if event::poll(timeout)? {
+            // A search waiting for the typing to pause is started on time, not on the next tick
+            let wait = self.live_search_due.map_or(timeout, |due| {
+                due.saturating_duration_since(Instant::now()).min(timeout)
+            });
+            if event::poll(wait)? {
                 if let Event::Key(key_event) = event::read()? {
+                    // A key that closes the filter form and goes back to the search box isn't typed into it
+                    let editing = self.input_mode == InputMode::Editing;
+                    let typed = self.input.clone();
                     handle_key_event(self, key_event, keys, theme, settings);
 
                     // In editing mode, handle search input
-                    if self.input_mode == InputMode::Editing {
+                    if editing && self.input_mode == InputMode::Editing {
                         let _ = search_input(self, key_event);
                     }
+
+                    // Every change to the query puts the search off until the typing pauses
+                    if self.live_search
+                        && self.input_mode == InputMode::Editing
+                        && self.input != typed
+                    {
+                        self.live_search_due = Some(Instant::now() + self.live_search_delay);
+                    }
+                }
+            }
+
+            if self
+                .live_search_due
+                .is_some_and(|due| due <= Instant::now())
+            {
+                self.live_search_due = None;
+                live_search_event(self);
+            }
+
+            if let Some(profile) = self.switch_to_profile.take() {
+                next_profile = self.load_next_profile(&profile);
+                if next_profile.is_some() {
+                    start_profile_login(self, &profile);
+                }
+            }
+
+            if self.setup.start_login {
+                self.setup.start_login = false;
+                let prompt = self.setup.login.clone();
+                login = Some(match &next_profile {
+                    // The profile keeps its creds and token, the login only asks for the browser when it has no token
+                    Some((next, _)) if self.setup.profile.is_some() => {
+                        let next = next.clone();
+                        runtime.spawn(async move { build_backend_with(&next, &prompt).await })
+                    }
+                    _ => {
+                        let app = self.clone();
+                        runtime.spawn(async move {
+                            check_credentials(&app).await?;
+                            // A token left behind belongs to other creds, so the login always goes through the browser
+                            forget_token(&app).map_err(ClientError::Io)?;
+                            build_backend_with(&app, &prompt).await
+                        })
+                    }
+                });
+            }
+
+            // The login stops when it finishes or when the user leaves its step
+            if let Some(handle) = login
+                .take_if(|handle| handle.is_finished() || self.setup.step != SetupStep::LoggingIn)
+            {
+                if !handle.is_finished() {
+                    handle.abort();
+                } else {
+                    match runtime.block_on(handle) {
+                        Ok(Ok(backend)) => {
+                            match next_profile.take().filter(|_| self.setup.profile.is_some()) {
+                                Some((next, warnings)) => self
+                                    .switch_profile(next, warnings, backend, keys, theme, settings),
+                                None => self.finish_setup(backend, keys, theme, settings),
+                            }
+                            // The player updates carry the backend they were started with
+                            if let Some(player_updates) = &player_updates {
+                                player_updates.abort();
+                            }
+                            player_updates = Some(
+                                runtime.spawn(update_player_info(result_tx.clone(), self.clone())),
+                            );
+                        }
+                        Ok(Err(e)) => {
+                            self.setup.error = describe(&e);
+                            self.setup.step = SetupStep::Failed;
+                        }
+                        Err(e) => {
+                            self.setup.error = e.to_string();
+                            self.setup.step = SetupStep::Failed;
+                        }
+                    }
+                }
+            }
+
+            // Logging out drops the backend, the player stops asking for updates until the next login
+            if self.backend.is_none() {
+                if let Some(player_updates) = player_updates.take() {
+                    player_updates.abort();
                 }
             }
 
+            // Leaving the login of a profile keeps the current one
+            if self.setup.profile.is_none() {
+                next_profile = None;
+            }
+
             // Update UI
             let now: Instant = std::time::Instant::now();
             if now.duration_since(last_tick) >= timeout {
                 last_tick = now;
 
-                // Check if a message has been received from the player info update thread
-                if rx1.try_recv().is_ok() {
-                    process_currently_playing(self, settings);
+                // Apply the results of the requests that finished since the last tick
+                while let Ok(result) = result_rx.try_recv() {
+                    task_completed_event(self, settings, result);
                 }
 
                 // Draw the UI
@@ -313,9 +392,176 @@ impl App {
             }
         }
 
+        tui::restore()?;
+
+        // Don't wait on requests that are still in flight
+        runtime.shutdown_background();
+
         Ok(())
     }
 
+    // Saves the creds the setup screen logged in with and opens the app, a login again only swaps the backend
+    fn finish_setup(
+        &mut self,
+        backend: Arc<dyn SpotifyBackend>,
+        keys: &mut Key,
+        theme: &mut Themes,
+        settings: &mut Settings,
+    ) {
+        let relogin = self.setup.relogin;
+        self.backend = Some(backend);
+        self.login_expired = false;
+        self.setup = Setup::default();
+        self.show_current_view();
+        if relogin {
+            self.notify(Severity::Success, "Logged in to Spotify again");
+            return;
+        }
+        startup(self, keys, theme, settings);
+
+        match write_creds(self) {
+            Ok(()) => self.notify(Severity::Success, "Logged in to Spotify"),
+            // The session works, only the next start will ask for the creds again
+            Err(e) => self.notify(
+                Severity::Error,
+                format!("Logged in, but the credentials couldn't be saved: {}", e),
+            ),
+        }
+    }
+
+    // Loads `profile` into a fresh app, None when the profile can't be used and the current one is kept
+    fn load_next_profile(&self, profile: &str) -> Option<(App, Vec<String>)> {
+        let mut next = App {
+            root_config_dir: self.root_config_dir.clone(),
+            root_cache_dir: self.root_cache_dir.clone(),
+            log: self.log.clone(),
+            notifications: self.notifications.clone(),
+            task_sender: self.task_sender.clone(),
+            ..App::default()
+        };
+
+        let warnings = match load_profile(&mut next, profile) {
+            Ok(warnings) => warnings,
+            Err(e) => {
+                self.notify(
+                    Severity::Error,
+                    format!("Couldn't load the '{}' profile: {}", profile, e),
+                );
+                return None;
+            }
+        };
+        if next.client_id.is_empty() && !fake_backend_requested() {
+            self.notify(
+                Severity::Error,
+                format!(
+                    "The '{}' profile has no credentials yet, run 'spoify --profile {}' to set it up",
+                    profile, profile
+                ),
+            );
+            return None;
+        }
+        Some((next, warnings))
+    }
+
+    // Replaces the app with the one loaded for the next profile, once its login is done
+    fn switch_profile(
+        &mut self,
+        mut next: App,
+        warnings: Vec<String>,
+        backend: Arc<dyn SpotifyBackend>,
+        keys: &mut Key,
+        theme: &mut Themes,
+        settings: &mut Settings,
+    ) {
+        next.backend = Some(backend);
+        *self = next;
+        *keys = Key::default();
+        *theme = Themes::default();
+        *settings = Settings::default();
+        startup(self, keys, theme, settings);
+
+        self.notify(
+            Severity::Success,
+            format!("Switched to the '{}' profile", self.profile),
+        );
+        for warning in warnings {
+            self.notify(Severity::Warning, warning);
+        }
+    }
+
+    /// Shows a notification over the interface for a moment and keeps it in the log
+    pub fn notify(&self, severity: Severity, message: impl Into<String>) {
+        let message = message.into();
+        self.log.record(severity, message.clone());
+        self.notifications.push(severity, message);
+    }
+
+    /// Refills the log screen with the entries of the log that pass the severity filter
+    pub fn refresh_log_entries(&mut self) {
+        let filter = self.log_filter;
+        let entries = self
+            .log
+            .entries()
+            .into_iter()
+            .filter(|entry| filter.is_none_or(|severity| entry.severity == severity))
+            .collect();
+        self.log_entries.set_items(entries);
+    }
+
+    /// Queues a Spotify request, the worker gets a snapshot of the current app state
+    pub fn dispatch(&self, task: Task) {
+        if let Some(sender) = &self.task_sender {
+            let _ = sender.send((task, self.clone()));
+        }
+    }
+
+    /// Opens a view and shows its menu, it becomes the newest entry of the navigation history
+    pub fn navigate(&mut self, view: View) {
+        self.navigation.push(view);
+        self.enter_current_view();
+    }
+
+    /// Returns to the previous view, `false` if the history is already at its start
+    pub fn go_back(&mut self) -> bool {
+        let moved = self.navigation.back();
+        self.enter_current_view();
+        moved
+    }
+
+    /// Goes forward to the view left with `go_back`
+    pub fn go_forward(&mut self) {
+        self.navigation.forward();
+        self.enter_current_view();
+    }
+
+    // Views listing items put the focus on their table, the others on the menu list
+    fn enter_current_view(&mut self) {
+        self.can_navigate_menu = matches!(
+            self.navigation.current(),
+            View::Main | View::Library | View::Playlists | View::NewReleases | View::Search
+        );
+        self.show_current_view();
+    }
+
+    /// Shows the menu of the current view, closing the help, error and player screens
+    pub fn show_current_view(&mut self) {
+        let view = self.navigation.current();
+        self.selected_menu = view.menu();
+
+        // The library list follows the section that is on screen
+        if let View::LibrarySection(library) | View::LibraryTracks(library) = view {
+            self.library.select(usize::from(library));
+        }
+    }
+
+    /// Whether the results of the last search are on screen
+    pub fn search_results_shown(&self) -> bool {
+        self.navigation.current() == View::Search
+            // Search-as-you-type shows them while the query is typed
+            && (self.input_mode != InputMode::Editing || self.live_search)
+            && !self.search_query.is_empty()
+    }
+
     pub fn exit(&mut self) {
         self.exit = true;
     }
@@ -327,90 +573,51 @@ impl Default for App {
             exit: false,
 
             selected_menu: Menu::Default,
+            navigation: Navigation::default(),
 
-            selected_library: Library::MadeFY,
-            library_state: ListState::default(),
-
-            user_playlist_state: ListState::default(),
+            library: Selectable::from(Library::ALL.to_vec()),
 
             search_query: "".to_string(),
             input: String::new(),
             input_mode: InputMode::Normal,
             cursor_position: 0,
-            search_menu: SearchMenu::Default,
-
-            album_names_search_results: Vec::new(),
-            album_links_search_results: Vec::new(),
-            track_names_search_results: Vec::new(),
-            track_links_search_results: Vec::new(),
-            playlist_names_search_results: Vec::new(),
-            playlist_links_search_results: Vec::new(),
-            artist_names_search_results: Vec::new(),
-            artist_links_search_results: Vec::new(),
-            album_state_in_search_result: ListState::default(),
-            track_state_in_search_result: ListState::default(),
-            playlist_state_in_search_result: ListState::default(),
-            artist_state_in_search_result: ListState::default(),
+
+            searched_albums: Selectable::default(),
+            searched_tracks: Selectable::default(),
+            searched_playlists: Selectable::default(),
+            searched_artists: Selectable::default(),
             search_state: ListState::default(),
+            search_paging: SearchPaging::default(),
+            search_loading: None,
+            search_generation: 0,
+            search_pending: Vec::new(),
+            live_search_due: None,
+            search_filters: SearchFilters::default(),
+            search_filter_fields: Selectable::from(FilterField::ALL.to_vec()),
+            search_history: SearchHistory::default(),
+            saved_searches: Selectable::default(),
+            saved_search_name: String::new(),
+            last_search: None,
             selected_album_in_search_result: false,
             selected_track_in_search_result: false,
             selected_playlist_in_search_result: false,
             selected_artist_in_search_result: false,
-            selected_search: false,
-            search_results_rendered: false,
-
-            user_playlist_names: Vec::new(),
-            user_playlist_links: Vec::new(),
-            user_playlist_track_names: Vec::new(),
-            user_playlist_track_duration: Vec::new(),
-            user_playlist_artist_names: Vec::new(),
-            user_playlist_track_links: Vec::new(),
-            user_playlist_album_names: Vec::new(),
-            selected_playlist_uri: String::new(),
+
+            user_playlists: Selectable::default(),
+            user_playlist_tracks: Selectable::default(),
+            selected_playlist_id: String::new(),
             current_user_playlist: String::new(),
-            user_playlist_display: false,
-            user_playlist_tracks_selected: false,
-            user_playlist_tracks_state: TableState::default(),
-
-            liked_songs_state: TableState::default(),
-            liked_song_names: Vec::new(),
-            liked_song_links: Vec::new(),
-            liked_song_duration: Vec::new(),
-            liked_song_artist_names: Vec::new(),
-            liked_songs_selected: false,
-            liked_song_display: false,
-            liked_song_album_names: Vec::new(),
-
-            user_album_display: false,
-            user_album_selected: false,
-            user_album_state: TableState::default(),
-            user_album_names: Vec::new(),
-            user_album_links: Vec::new(),
-            user_album_artist_names: Vec::new(),
-            user_album_tracks: Vec::new(),
+
+            liked_songs: Selectable::default(),
+
+            user_albums: Selectable::default(),
             can_navigate_menu: true,
 
-            recently_played_names: Vec::new(),
-            recently_played_links: Vec::new(),
-            recently_played_duration: Vec::new(),
-            recently_played_artist_names: Vec::new(),
-            recently_played_album_names: Vec::new(),
-            recently_played_selected: false,
-            recently_played_display: false,
-            recently_played_state: TableState::default(),
-
-            podcast_names: Vec::new(),
-            podcast_links: Vec::new(),
-            podcast_publisher: Vec::new(),
-            podcast_selected: false,
-            podcast_display: false,
-            podcast_state: TableState::default(),
-
-            user_artist_names: Vec::new(),
-            user_artist_links: Vec::new(),
-            user_artist_selected: false,
-            user_artist_display: false,
-            user_artist_state: TableState::default(),
+            recently_played: Selectable::default(),
+
+            podcasts: Selectable::default(),
+
+            user_artists: Selectable::default(),
 
             current_device_name: String::new(),
             current_device_volume: String::new(),
@@ -431,127 +638,72 @@ impl Default for App {
             progress_bar_ratio: 0.0,
             currently_playing_media_type: String::new(),
 
-            new_release_artist: Vec::new(),
-            new_release_name: Vec::new(),
-            new_release_state: ListState::default(),
+            new_releases: Selectable::default(),
             current_new_release: String::new(),
-            new_release_display: false,
-            new_release_album_selected: false,
-            new_release_album_state: TableState::default(),
-            new_release_album_links: Vec::new(),
-            current_new_release_album: String::new(),
-            current_new_release_album_link: String::new(),
-            new_release_track_names: Vec::new(),
-            new_release_artist_names: Vec::new(),
-            new_release_durations_ms: Vec::new(),
-            new_release_spotify_urls: Vec::new(),
+            current_new_release_album_id: String::new(),
+            new_release_tracks: Selectable::default(),
 
             client_id: String::new(),
             client_secret: String::new(),
+            auth_flow: AuthFlow::Pkce,
+            setup: Setup::default(),
+            account: None,
+            login_expired: false,
 
-            album_index: 0,
-            track_index: 0,
-            playlist_index: 0,
-            artist_index: 0,
-            user_playlist_index: 0,
-            liked_songs_index: 0,
-            user_album_index: 0,
-            podcast_index: 0,
-            recently_played_index: 0,
-            user_artist_index: 0,
-            new_release_index: 0,
+            profile: DEFAULT_PROFILE.to_string(),
+            root_config_dir: PathBuf::new(),
+            root_cache_dir: PathBuf::new(),
+            profiles: Selectable::default(),
+            switch_to_profile: None,
 
             error_text: String::new(),
+            notifications: Notifications::default(),
+            log: EventLog::default(),
+            log_entries: Selectable::default(),
+            log_filter: None,
+
+            selected_album_tracks: Selectable::default(),
+
+            selected_artist_tracks: Selectable::default(),
+
+            selected_playlist_tracks: Selectable::default(),
+
+            made_fy_playlists: Selectable::default(),
+            made_fy_tracks: Selectable::default(),
+
+            user_album_tracks: Selectable::default(),
+
+            user_artist_tracks: Selectable::default(),
+
+            selected_track_id: String::new(),
 
-            selected_album_tracks_names: Vec::new(),
-            selected_album_tracks_artists: Vec::new(),
-            selected_album_tracks_duration: Vec::new(),
-            selected_album_tracks_links: Vec::new(),
-            searched_album_selected: false,
-            searched_album_state: TableState::default(),
-            searched_album_index: 0,
-
-            selected_artist_track_album_names: Vec::new(),
-            searched_artist_selected: false,
-            searched_artist_state: TableState::default(),
-            searched_artist_index: 0,
-            selected_artist_tracks_names: Vec::new(),
-            selected_artist_tracks_duration: Vec::new(),
-            selected_artist_tracks_links: Vec::new(),
-
-            searched_playlist_selected: false,
-            searched_playlist_state: TableState::default(),
-            searched_playlist_index: 0,
-            selected_playlist_tracks_names: Vec::new(),
-            selected_playlist_tracks_artists: Vec::new(),
-            selected_playlist_tracks_duration: Vec::new(),
-            selected_playlist_tracks_albums: Vec::new(),
-            selected_playlist_tracks_links: Vec::new(),
-
-            made_fy_playlist_names: Vec::new(),
-            made_fy_playlist_links: Vec::new(),
-            made_fy_selected: false,
-            made_fy_display: false,
-            made_fy_state: TableState::default(),
-            made_fy_index: 0,
-            made_fy_playlist_track_total: Vec::new(),
-            made_fy_track_links: Vec::new(),
-            made_fy_track_names: Vec::new(),
-            made_fy_track_duration: Vec::new(),
-            made_fy_artist_names: Vec::new(),
-            made_fy_album_names: Vec::new(),
-            made_fy_current_playlist_selected: false,
-            made_fy_track_display: false,
-            made_fy_track_state: TableState::default(),
-            made_fy_track_selected: false,
-            made_fy_track_index: 0,
-
-            user_album_track_names: Vec::new(),
-            user_album_track_artist: Vec::new(),
-            user_album_track_duration: Vec::new(),
-            user_album_track_index: 0,
-            user_album_track_state: TableState::default(),
-            user_album_track_display: false,
-            user_album_track_selected: false,
-            user_album_track_links: Vec::new(),
-            user_album_current_album_selected: false,
-
-            user_artist_track_names: Vec::new(),
-            user_artist_track_album: Vec::new(),
-            user_artist_track_duration: Vec::new(),
-            user_artist_track_index: 0,
-            user_artist_track_state: TableState::default(),
-            user_artist_track_display: false,
-            user_artist_track_selected: false,
-            user_artist_current_artist_selected: false,
-            user_artist_track_links: Vec::new(),
-
-            selected_link_for_playback: String::new(),
-
-            enter_for_playback_in_user_playlist: false,
-            enter_for_playback_in_liked_song: false,
-            enter_for_playback_in_user_album: false,
-            enter_for_playback_in_recently_played: false,
-            enter_for_playback_in_saved_artist: false,
-            enter_for_playback_in_made_fy: false,
-            enter_for_playback_in_new_release: false,
-
-            is_only_id: false,
             is_in_track: false,
 
-            top_tracks_all_time_names: Vec::new(),
-            top_tracks_6_months_names: Vec::new(),
-            top_tracks_4_weeks_names: Vec::new(),
-            file_name: String::new(),
+            top_tracks_all_time: Vec::new(),
+            top_tracks_6_months: Vec::new(),
+            top_tracks_4_weeks: Vec::new(),
+
+            add_track_to_playlist: Selectable::default(),
+
+            config_dir: PathBuf::new(),
+            cache_dir: PathBuf::new(),
+            disk_cache: false,
+            market: Market::FromToken,
+            hide_unplayable: false,
+            live_search: false,
+            live_search_delay: Duration::from_millis(300),
 
             track_added_to_playlist_name: String::new(),
-            playlist_index_for_track_addition: 0,
-            track_added_to_playlist_link: String::new(),
-            playlist_link_for_track_addition: String::new(),
-            add_track_to_playlist_state: ListState::default(),
+            track_added_to_playlist_id: String::new(),
+            playlist_id_for_track_addition: String::new(),
 
-            playlist_link_to_follow: String::new(),
+            playlist_id_to_follow: String::new(),
             have_playlist: true,
+
+            task_sender: None,
+
+            backend: None,
+            requests: RequestStatus::default(),
         }
     }
 }
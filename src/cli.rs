@@ -0,0 +1,183 @@
+// Parses the command line arguments
+use std::path::PathBuf;
+
+use crate::settings::paths::is_valid_profile_name;
+
+/// Options given on the command line
+#[derive(Debug, Default)]
+pub struct Args {
+    pub config_dir: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>,
+    pub profile: Option<String>,
+    pub command: Option<Command>,
+    pub help: bool,
+}
+
+/// A player action run without opening the interface
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Play,
+    Pause,
+    Next,
+    Prev,
+    Volume(u8),
+    Shuffle,
+    Repeat,
+    Status(StatusOptions),
+    Search(String),
+    Like,
+    Logout,
+}
+
+/// How `spoify status` prints the playback
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusOptions {
+    pub format: String,
+    pub follow: bool,
+}
+
+/// Format used by `spoify status` when none is given
+pub const DEFAULT_STATUS_FORMAT: &str = "{status}: {title} - {artist}";
+
+pub const USAGE: &str =
+    "Usage: spoify [--config-dir <path>] [--cache-dir <path>] [--profile <name>] [--help] [command]
+
+Flags:
+  --config-dir <path>  Keep the configuration in this folder (or set SPOIFY_CONFIG_DIR)
+  --cache-dir <path>   Keep the token and the cached data in this folder (or set SPOIFY_CACHE_DIR)
+  --profile <name>     Use the credentials, token, settings and theme of this profile (or set SPOIFY_PROFILE)
+  -h, --help           Print this help
+
+Commands:
+  play           Resume playback
+  pause          Pause playback
+  next           Play the next track
+  prev           Play the previous track
+  volume <0-100> Set the volume of the current device
+  shuffle        Toggle shuffle
+  repeat         Cycle the repeat mode
+  status         Print the current track
+    --format <format>  Print the playback with these fields: {title} {artist} {album}
+                       {progress} {duration} {device} {volume} {status} {shuffle} {repeat}
+    --follow           Keep running and print a new line whenever the output changes
+  search <query> Print the tracks, albums, artists and playlists matching the query
+  like           Add the current track to the liked songs
+  logout         Remove the saved login, the next start logs in again
+
+Without a command the interface is opened.";
+
+/// Reads the arguments given after the program name
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    // Everything from the first word on belongs to the command, including its own flags
+    let mut words: Vec<String> = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config-dir" => parsed.config_dir = Some(flag_value(&arg, args.next())?),
+            "--cache-dir" => parsed.cache_dir = Some(flag_value(&arg, args.next())?),
+            "--profile" => {
+                let name = args
+                    .next()
+                    .ok_or_else(|| usage_error("'--profile' needs a name".to_string()))?;
+                if !is_valid_profile_name(&name) {
+                    return Err(usage_error(format!(
+                        "'{}' isn't a profile name, use letters, digits, '-' and '_'",
+                        name
+                    )));
+                }
+                parsed.profile = Some(name);
+            }
+            "-h" | "--help" => parsed.help = true,
+            flag if flag.starts_with("--") && words.is_empty() => {
+                return Err(usage_error(format!("unknown argument '{}'", arg)))
+            }
+            _ => words.push(arg),
+        }
+    }
+
+    if let Some((name, rest)) = words.split_first() {
+        parsed.command = Some(parse_command(name, rest)?);
+    }
+
+    Ok(parsed)
+}
+
+fn parse_command(name: &str, rest: &[String]) -> Result<Command, String> {
+    let command = match name {
+        "play" => Command::Play,
+        "pause" => Command::Pause,
+        "next" => Command::Next,
+        "prev" => Command::Prev,
+        "shuffle" => Command::Shuffle,
+        "repeat" => Command::Repeat,
+        "status" => return parse_status(rest).map(Command::Status),
+        "like" => Command::Like,
+        "logout" => Command::Logout,
+        "volume" => {
+            return match rest {
+                [value] => value
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|volume| *volume <= 100)
+                    .map(Command::Volume)
+                    .ok_or_else(|| {
+                        usage_error(format!("'{}' isn't a volume from 0 to 100", value))
+                    }),
+                _ => Err(usage_error(
+                    "'volume' needs a value from 0 to 100".to_string(),
+                )),
+            }
+        }
+        "search" => {
+            let query = rest.join(" ");
+            if query.trim().is_empty() {
+                return Err(usage_error("'search' needs a query".to_string()));
+            }
+            return Ok(Command::Search(query));
+        }
+        _ => return Err(usage_error(format!("unknown command '{}'", name))),
+    };
+
+    match rest.first() {
+        Some(arg) => Err(usage_error(format!("unexpected argument '{}'", arg))),
+        None => Ok(command),
+    }
+}
+
+fn parse_status(rest: &[String]) -> Result<StatusOptions, String> {
+    let mut options = StatusOptions {
+        format: DEFAULT_STATUS_FORMAT.to_string(),
+        follow: false,
+    };
+    let mut rest = rest.iter();
+
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--format" => {
+                options.format = rest
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| usage_error("'--format' needs a format".to_string()))?
+            }
+            "--follow" => options.follow = true,
+            _ => return Err(usage_error(format!("unexpected argument '{}'", arg))),
+        }
+    }
+
+    Ok(options)
+}
+
+fn flag_value(flag: &str, value: Option<String>) -> Result<PathBuf, String> {
+    value
+        .map(PathBuf::from)
+        .ok_or_else(|| usage_error(format!("'{}' needs a path", flag)))
+}
+
+fn usage_error(message: String) -> String {
+    format!(
+        "{}\nRun 'spoify --help' to see the flags and commands",
+        message
+    )
+}
@@ -0,0 +1,262 @@
+// Runs a single player command from the command line, without opening the interface
+use std::io::{self, Write};
+use std::time::Duration;
+
+use tokio::runtime::Runtime;
+
+use crate::app::App;
+use crate::cli::{Command, StatusOptions};
+use crate::enums::Severity;
+use crate::settings::paths::DEFAULT_PROFILE;
+use crate::spotify::auth::forget_token;
+use crate::spotify::backend::{build_backend, request::describe};
+use crate::spotify::library_section::liked_songs::like_current_track;
+use crate::spotify::player::{
+    next_track::next_track, pause_playback::pause, play_playback::play, player::currently_playing,
+    player::process_currently_playing, previous_track::previous_track, repeat::cycle_repeat,
+    set_volume::set_volume, shuffle::toogle_shuffle,
+};
+use crate::spotify::search::search::{process_search, search};
+use crate::structs::Settings;
+use crate::ui::util::format_duration;
+
+/// Authenticates, reads the current playback and runs the command against it
+pub fn run_command(app: &mut App, settings: &mut Settings, command: Command) -> io::Result<()> {
+    // Logging out only removes the token, logging in first would defeat it
+    if command == Command::Logout {
+        return log_out(app);
+    }
+
+    let runtime: Runtime = Runtime::new()?;
+    runtime
+        .block_on(run(app, settings, command))
+        .map_err(io::Error::other)
+}
+
+fn log_out(app: &App) -> io::Result<()> {
+    forget_token(app)?;
+    let message = if app.profile == DEFAULT_PROFILE {
+        "Logged out, the next start of spoify logs in again".to_string()
+    } else {
+        format!(
+            "Logged out of the '{}' profile, its next start logs in again",
+            app.profile
+        )
+    };
+    app.log.record(Severity::Info, message.clone());
+    println!("{}", message);
+    Ok(())
+}
+
+async fn run(app: &mut App, settings: &mut Settings, command: Command) -> Result<(), String> {
+    app.backend = Some(build_backend(app).await.map_err(|e| describe(&e))?);
+
+    // Every command acts on the device and the track that are playing right now
+    let refreshed = refresh_playback(app, settings).await;
+    // Following the status outlasts a failed refresh, the error is printed until Spotify answers again
+    if let Command::Status(options) = &command {
+        if options.follow {
+            return print_status(app, settings, options, refreshed.err()).await;
+        }
+    }
+    refreshed?;
+
+    let result = match command {
+        Command::Play => play(app).await,
+        Command::Pause => pause(app).await,
+        Command::Next => next_track(app).await,
+        Command::Prev => previous_track(app).await,
+        Command::Volume(volume_percent) => set_volume(app, volume_percent).await,
+        Command::Shuffle => {
+            app.is_shuffle = !app.is_shuffle;
+            toogle_shuffle(app).await
+        }
+        Command::Repeat => cycle_repeat(app).await,
+        Command::Status(options) => return print_status(app, settings, &options, None).await,
+        Command::Search(query) => search(&query, app).await.map(|results| {
+            process_search(app, &results);
+            print_search_results(app);
+        }),
+        Command::Like => {
+            if app.current_playing_name.is_empty() {
+                return Err("Nothing is playing".to_string());
+            }
+            like_current_track(app).await
+        }
+        Command::Logout => unreachable!("logging out doesn't need Spotify"),
+    };
+
+    result.map_err(|e| describe(&e))
+}
+
+async fn refresh_playback(app: &mut App, settings: &mut Settings) -> Result<(), String> {
+    let playback = currently_playing(app).await.map_err(|e| describe(&e))?;
+    process_currently_playing(app, settings, &playback);
+    if app.current_device_id.as_deref() == Some("") {
+        app.current_device_id = None;
+    }
+    app.device_id_after_pause = app.current_device_id.clone();
+    Ok(())
+}
+
+// Prints the playback once, or every time the line changes while following it.
+// A failed refresh while following is printed in place of the status and the polling goes on
+async fn print_status(
+    app: &mut App,
+    settings: &mut Settings,
+    options: &StatusOptions,
+    mut error: Option<String>,
+) -> Result<(), String> {
+    let mut last_line: Option<String> = None;
+
+    loop {
+        let line = match &error {
+            Some(e) => format!("Couldn't refresh the playback: {}", e),
+            None => status_line(app, &options.format),
+        };
+        if last_line.as_ref() != Some(&line) {
+            let mut stdout = io::stdout().lock();
+            // Whatever read the status (a status bar, a pipe) went away, so there is nobody left to print for
+            if writeln!(stdout, "{}", line)
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                return Ok(());
+            }
+            last_line = Some(line);
+        }
+
+        if !options.follow {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        error = refresh_playback(app, settings).await.err();
+    }
+}
+
+// Replaces every known {field} of the format, anything else is printed as it is
+fn status_line(app: &App, format: &str) -> String {
+    if app.current_playing_name.is_empty() {
+        return "Nothing is playing".to_string();
+    }
+
+    let field = |name: &str| -> Option<String> {
+        Some(match name {
+            "title" => app.current_playing_name.clone(),
+            "artist" => app.currently_playing_artist.clone(),
+            "album" => app.current_playing_album.clone(),
+            "progress" => format_duration(app.currrent_timestamp.round() as i64),
+            "duration" => format_duration(app.ending_timestamp.round() as i64),
+            "device" => app.current_device_name.clone(),
+            "volume" => app.current_device_volume.clone(),
+            "status" => app.playback_status.clone(),
+            "shuffle" => app.shuffle_status.clone(),
+            "repeat" => app.repeat_status.clone(),
+            _ => return None,
+        })
+    };
+
+    let mut line = String::new();
+    let mut rest = format;
+    while let Some(start) = rest.find('{') {
+        line.push_str(&rest[..start]);
+        let value = rest[start..].find('}').and_then(|end| {
+            field(&rest[start + 1..start + end]).map(|value| (value, start + end + 1))
+        });
+        match value {
+            Some((value, next)) => {
+                line.push_str(&value);
+                rest = &rest[next..];
+            }
+            None => {
+                line.push('{');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    line.push_str(rest);
+    line
+}
+
+// One result per line as kind, uri, name and artist or owner, separated by tabs so scripts can split them
+fn print_search_results(app: &App) {
+    for track in app.searched_tracks.iter() {
+        println!(
+            "track\t{}\t{}\t{}",
+            track.uri,
+            track.name,
+            track.artist_names()
+        );
+    }
+    for album in app.searched_albums.iter() {
+        println!(
+            "album\t{}\t{}\t{}",
+            album.uri,
+            album.name,
+            album.artist_names()
+        );
+    }
+    for artist in app.searched_artists.iter() {
+        println!("artist\t{}\t{}\t", artist.uri, artist.name);
+    }
+    for playlist in app.searched_playlists.iter() {
+        println!(
+            "playlist\t{}\t{}\t{}",
+            playlist.uri, playlist.name, playlist.owner
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playing() -> App {
+        App {
+            current_playing_name: "First Light".to_string(),
+            currently_playing_artist: "Aurora Lane".to_string(),
+            currrent_timestamp: 65_400.0,
+            ending_timestamp: 210_000.0,
+            shuffle_status: "Off".to_string(),
+            ..App::default()
+        }
+    }
+
+    #[test]
+    fn the_fields_are_filled_in() {
+        assert_eq!(
+            status_line(
+                &playing(),
+                "♪ {title} - {artist} [{progress}/{duration}] {shuffle}"
+            ),
+            "♪ First Light - Aurora Lane [1:05/3:30] Off"
+        );
+        assert_eq!(
+            status_line(&playing(), "{title}{title}"),
+            "First LightFirst Light"
+        );
+    }
+
+    #[test]
+    fn unknown_fields_and_stray_braces_are_printed_as_they_are() {
+        let app = playing();
+        assert_eq!(
+            status_line(&app, "{lyrics} {title}"),
+            "{lyrics} First Light"
+        );
+        assert_eq!(status_line(&app, "{ title }"), "{ title }");
+        assert_eq!(status_line(&app, "{{title}}"), "{First Light}");
+        assert_eq!(status_line(&app, "} {"), "} {");
+        assert_eq!(status_line(&app, "{title"), "{title");
+        assert_eq!(status_line(&app, "{ti{title}"), "{tiFirst Light");
+        assert_eq!(status_line(&app, ""), "");
+    }
+
+    #[test]
+    fn nothing_playing_ignores_the_format() {
+        assert_eq!(
+            status_line(&App::default(), "{title}"),
+            "Nothing is playing"
+        );
+    }
+}
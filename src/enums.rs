@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Library {
     MadeFY,
@@ -19,14 +21,70 @@ pub enum Menu {
     Error,
     Player,
     AddTrackToPlaylist,
+    Log,
+    Profiles,
+    Setup,
+    Account,
+    SearchFilters,
+    SavedSearches,
 }
+/// A move of the selection in a list or table
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub enum SearchMenu {
-    Default,
+pub enum Movement {
+    Next,
+    Previous,
+    PageDown,
+    PageUp,
+    First,
+    Last,
+}
+/// A screen the user can navigate to, the navigation history is made of these
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum View {
+    Main,
+    Library,
+    // The items of a library section, e.g. the saved albums
+    LibrarySection(Library),
+    // The tracks of the made for you playlist, album or artist picked in a library section
+    LibraryTracks(Library),
+    Playlists,
+    PlaylistTracks,
+    NewReleases,
+    NewReleaseTracks,
+    Search,
     SearchedAlbum,
     SearchedArtist,
     SearchedPlaylist,
-    SearchedTrack,
+}
+/// How important a notification is, it decides its color and how long it stays on screen
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+/// How spoify logs in to Spotify, set by `Auth Flow` in `creds.yml`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AuthFlow {
+    // Authorization Code, needs the Client ID and the Client Secret
+    ClientSecret,
+    // Authorization Code with PKCE, the Client ID is enough
+    Pkce,
+}
+/// A step of the setup screen, shown when there are no credentials yet or to log in again
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum SetupStep {
+    // The steps on the Spotify dashboard
+    #[default]
+    Instructions,
+    ClientId,
+    ClientSecret,
+    // The credentials are checked and the login runs in the background
+    LoggingIn,
+    Failed,
+    // The token was removed from the account screen
+    LoggedOut,
 }
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum InputMode {
@@ -35,6 +93,69 @@ pub enum InputMode {
     SearchResults,
 }
 
+/// The types of search results, each one is shown in its own pane and paged on its own
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SearchCategory {
+    Tracks,
+    Artists,
+    Albums,
+    Playlists,
+}
+
+impl SearchCategory {
+    pub const ALL: [SearchCategory; 4] = [
+        SearchCategory::Albums,
+        SearchCategory::Artists,
+        SearchCategory::Playlists,
+        SearchCategory::Tracks,
+    ];
+}
+
+/// The fields of the search filter form, in the order they are shown
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FilterField {
+    Artist,
+    Album,
+    Year,
+    Genre,
+    Isrc,
+    NewReleases,
+    Tracks,
+    Artists,
+    Albums,
+    Playlists,
+}
+
+impl FilterField {
+    pub const ALL: [FilterField; 10] = [
+        FilterField::Artist,
+        FilterField::Album,
+        FilterField::Year,
+        FilterField::Genre,
+        FilterField::Isrc,
+        FilterField::NewReleases,
+        FilterField::Tracks,
+        FilterField::Artists,
+        FilterField::Albums,
+        FilterField::Playlists,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterField::Artist => "Artist",
+            FilterField::Album => "Album",
+            FilterField::Year => "Year",
+            FilterField::Genre => "Genre",
+            FilterField::Isrc => "ISRC",
+            FilterField::NewReleases => "New releases only",
+            FilterField::Tracks => "Search songs",
+            FilterField::Artists => "Search artists",
+            FilterField::Albums => "Search albums",
+            FilterField::Playlists => "Search playlists",
+        }
+    }
+}
+
 impl From<Menu> for usize {
     fn from(input: Menu) -> usize {
         match input {
@@ -48,6 +169,12 @@ impl From<Menu> for usize {
             Menu::Error => 7,
             Menu::Player => 8,
             Menu::AddTrackToPlaylist => 9,
+            Menu::Log => 10,
+            Menu::Profiles => 11,
+            Menu::Setup => 12,
+            Menu::Account => 13,
+            Menu::SearchFilters => 14,
+            Menu::SavedSearches => 15,
         }
     }
 }
@@ -63,14 +190,155 @@ impl From<Library> for usize {
         }
     }
 }
-impl From<SearchMenu> for usize {
-    fn from(input: SearchMenu) -> usize {
-        match input {
-            SearchMenu::Default => 0,
-            SearchMenu::SearchedAlbum => 1,
-            SearchMenu::SearchedArtist => 2,
-            SearchMenu::SearchedPlaylist => 3,
-            SearchMenu::SearchedTrack => 4,
+impl Library {
+    /// Library sections in the order they are listed
+    pub const ALL: [Library; 6] = [
+        Library::MadeFY,
+        Library::RecentlyPlayed,
+        Library::LikedSongs,
+        Library::Albums,
+        Library::Artists,
+        Library::Podcasts,
+    ];
+}
+impl View {
+    /// Menu that shows the view
+    pub fn menu(self) -> Menu {
+        match self {
+            View::Main => Menu::Default,
+            View::Library | View::LibrarySection(_) | View::LibraryTracks(_) => Menu::Library,
+            View::Playlists | View::PlaylistTracks => Menu::Playlists,
+            View::NewReleases | View::NewReleaseTracks => Menu::NewRelease,
+            View::Search | View::SearchedAlbum | View::SearchedArtist | View::SearchedPlaylist => {
+                Menu::Search
+            }
+        }
+    }
+}
+
+impl Severity {
+    /// How long a notification stays on screen
+    pub fn duration(self) -> Duration {
+        match self {
+            Severity::Info | Severity::Success => Duration::from_secs(3),
+            Severity::Warning => Duration::from_secs(5),
+            Severity::Error => Duration::from_secs(8),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "Info",
+            Severity::Success => "Success",
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+        }
+    }
+}
+
+/// Spotify requests that are queued for the background worker
+#[derive(Clone, Debug)]
+pub enum Task {
+    // Player
+    CurrentlyPlaying,
+    NextTrack,
+    PreviousTrack,
+    Pause,
+    Play,
+    CycleRepeat,
+    ToggleShuffle,
+    StartPlayback,
+    // The volume the device is set to, worked out from the volume shown when the key was pressed
+    Volume(u8),
+
+    // Library
+    MadeFY,
+    MadeFYTracks,
+    LikedSongs,
+    RecentlyPlayed,
+    UserAlbums,
+    UserAlbumTracks,
+    UserArtists,
+    UserArtistTracks,
+    Podcasts,
+
+    // New Release
+    NewReleases,
+    NewReleaseTracks,
+
+    // User's playlists
+    UserPlaylists,
+    UserPlaylistTracks,
+
+    // Top Tracks
+    TopTracks,
+
+    // Search, each type of results is its own task so the panes fill as the requests return
+    Search(String, SearchCategory),
+    // The next page of one type of results for the query
+    SearchMore(String, SearchCategory),
+    SearchedAlbumTracks,
+    SearchedArtistTracks,
+    SearchedPlaylistTracks,
+
+    // Playlist control
+    AddTrackToPlaylist,
+    FollowPlaylist,
+    UnfollowPlaylist,
+
+    // Account
+    Account,
+}
+impl Task {
+    /// Whether the task changes the playback, these reach Spotify in the order the keys were pressed
+    pub fn is_player_command(&self) -> bool {
+        matches!(
+            self,
+            Task::NextTrack
+                | Task::PreviousTrack
+                | Task::Pause
+                | Task::Play
+                | Task::CycleRepeat
+                | Task::ToggleShuffle
+                | Task::StartPlayback
+                | Task::Volume(_)
+        )
+    }
+
+    /// What the request does, used to tell the user which one failed
+    pub fn description(&self) -> &'static str {
+        match self {
+            Task::CurrentlyPlaying => "Fetching the playback",
+            Task::NextTrack => "Skipping to the next track",
+            Task::PreviousTrack => "Going back to the previous track",
+            Task::Pause => "Pausing",
+            Task::Play => "Resuming",
+            Task::CycleRepeat => "Changing the repeat mode",
+            Task::ToggleShuffle => "Toggling shuffle",
+            Task::StartPlayback => "Playing the track",
+            Task::Volume(_) => "Changing the volume",
+            Task::MadeFY => "Fetching the made for you playlists",
+            Task::MadeFYTracks | Task::UserPlaylistTracks | Task::SearchedPlaylistTracks => {
+                "Fetching the playlist"
+            }
+            Task::LikedSongs => "Fetching the liked songs",
+            Task::RecentlyPlayed => "Fetching the recently played tracks",
+            Task::UserAlbums => "Fetching the saved albums",
+            Task::UserAlbumTracks | Task::NewReleaseTracks | Task::SearchedAlbumTracks => {
+                "Fetching the album"
+            }
+            Task::UserArtists => "Fetching the followed artists",
+            Task::UserArtistTracks | Task::SearchedArtistTracks => "Fetching the artist",
+            Task::Podcasts => "Fetching the podcasts",
+            Task::NewReleases => "Fetching the new releases",
+            Task::UserPlaylists => "Fetching the playlists",
+            Task::TopTracks => "Fetching the top tracks",
+            Task::Search(..) => "Searching",
+            Task::SearchMore(..) => "Loading more search results",
+            Task::AddTrackToPlaylist => "Adding the track to the playlist",
+            Task::FollowPlaylist => "Following the playlist",
+            Task::UnfollowPlaylist => "Unfollowing the playlist",
+            Task::Account => "Fetching the account",
         }
     }
 }
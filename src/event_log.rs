@@ -0,0 +1,120 @@
+// Keeps the errors and notable events of the session in memory and in a rotating log file
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::enums::Severity;
+
+/// Name of the log file in the cache directory, the rotated ones get `.1`, `.2` and `.3` appended
+pub const LOG_FILE_NAME: &str = "spoify.log";
+
+// Entries kept in memory, the oldest ones are dropped first
+const CAPACITY: usize = 1000;
+// The log file is rotated once it grows past this size
+const MAX_FILE_SIZE: u64 = 512 * 1024;
+const ROTATED_FILES: usize = 3;
+
+/// A timestamped line of the log
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl LogEntry {
+    /// The entry as it is written to the log file and copied to the clipboard
+    pub fn line(&self) -> String {
+        format!(
+            "{} [{}] {}",
+            self.time.format("%Y-%m-%d %H:%M:%S"),
+            self.severity.label(),
+            self.message
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct LogState {
+    entries: VecDeque<LogEntry>,
+    file: Option<PathBuf>,
+}
+
+/// The log of the session
+///
+/// Clones share the same log, so a background task working on an app snapshot can write to it too.
+#[derive(Clone, Debug, Default)]
+pub struct EventLog {
+    state: Arc<Mutex<LogState>>,
+}
+
+impl EventLog {
+    // A panic while logging must not take the log down with it, so a poisoned lock is reused
+    fn state(&self) -> MutexGuard<'_, LogState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Starts writing every new entry to the log file in `dir`
+    pub fn set_file_dir(&self, dir: &Path) {
+        self.state().file = Some(dir.join(LOG_FILE_NAME));
+    }
+
+    pub fn file(&self) -> Option<PathBuf> {
+        self.state().file.clone()
+    }
+
+    pub fn record(&self, severity: Severity, message: impl Into<String>) {
+        let entry = LogEntry {
+            time: Local::now(),
+            severity,
+            message: message.into(),
+        };
+
+        let mut state = self.state();
+        // The log is where problems get reported, so a log file that can't be written is only skipped
+        if let Some(file) = &state.file {
+            let _ = append(file, &entry.line());
+        }
+        state.entries.push_back(entry);
+        while state.entries.len() > CAPACITY {
+            state.entries.pop_front();
+        }
+    }
+
+    /// Every entry still in memory, oldest first
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.state().entries.iter().cloned().collect()
+    }
+
+    pub fn last_message(&self) -> Option<String> {
+        self.state()
+            .entries
+            .back()
+            .map(|entry| entry.message.clone())
+    }
+}
+
+fn append(file: &Path, line: &str) -> io::Result<()> {
+    if fs::metadata(file).is_ok_and(|metadata| metadata.len() > MAX_FILE_SIZE) {
+        rotate(file)?;
+    }
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(file)?;
+    writeln!(file, "{}", line)
+}
+
+// spoify.log becomes spoify.log.1, spoify.log.1 becomes spoify.log.2 and so on, the oldest one is overwritten
+fn rotate(file: &Path) -> io::Result<()> {
+    let rotated = |index: usize| PathBuf::from(format!("{}.{}", file.display(), index));
+    for index in (1..ROTATED_FILES).rev() {
+        if rotated(index).exists() {
+            fs::rename(rotated(index), rotated(index + 1))?;
+        }
+    }
+    fs::rename(file, rotated(1))
+}
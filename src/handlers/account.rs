@@ -0,0 +1,47 @@
+use crate::{
+    app::App,
+    enums::{Menu, SetupStep, Severity, Task},
+    handlers::setup::start_relogin,
+    spotify::auth::forget_token,
+    structs::Setup,
+};
+
+pub fn go_to_account_event(app: &mut App) {
+    if app.selected_menu == Menu::Account {
+        app.show_current_view();
+        return;
+    }
+
+    // The token may have been refreshed since the screen was last opened
+    app.account = None;
+    app.dispatch(Task::Account);
+    app.selected_menu = Menu::Account;
+}
+
+// The error screen only comes up for a login that is no longer valid, so it offers the login too
+pub fn account_enter_event(app: &mut App) {
+    if matches!(app.selected_menu, Menu::Account | Menu::Error) {
+        start_relogin(app);
+    }
+}
+
+pub fn account_log_out_event(app: &mut App) {
+    if app.selected_menu != Menu::Account {
+        return;
+    }
+    if let Err(e) = forget_token(app) {
+        app.notify(Severity::Error, format!("Couldn't remove the token: {}", e));
+        return;
+    }
+
+    // Without a backend the main loop stops the player updates and nothing else is fetched
+    app.backend = None;
+    app.account = None;
+    app.setup = Setup {
+        relogin: true,
+        step: SetupStep::LoggedOut,
+        ..Setup::default()
+    };
+    app.selected_menu = Menu::Setup;
+    app.log.record(Severity::Info, "Logged out of Spotify");
+}
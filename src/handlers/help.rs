@@ -2,7 +2,7 @@ use crate::{app::App, enums::Menu};
 
 pub fn go_to_help_event(app: &mut App) {
     if app.selected_menu == Menu::Help {
-        app.selected_menu = Menu::Default;
+        app.show_current_view();
     } else {
         app.selected_menu = Menu::Help;
     }
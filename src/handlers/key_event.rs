@@ -1,14 +1,13 @@
+use super::account::{account_enter_event, account_log_out_event, go_to_account_event};
 use super::change_keybindings::change_keybindings;
-use super::error_screen::go_to_error_event;
 use super::exit::exit_event;
 use super::help::go_to_help_event;
 use super::library::{
-    go_to_library_event, library_down_event, library_enter_event, library_tab_event,
-    library_up_event,
+    go_to_library_event, library_enter_event, library_move_event, library_tab_event,
 };
+use super::log_screen::{go_to_log_event, log_copy_event, log_filter_event, log_move_event};
 use super::new_release::{
-    go_to_new_release_event, new_release_down_event, new_release_enter_event,
-    new_release_tab_event, new_release_up_event,
+    go_to_new_release_event, new_release_enter_event, new_release_move_event, new_release_tab_event,
 };
 use super::open_configure_folder::open_config_folder;
 use super::player::{
@@ -16,24 +15,30 @@ use super::player::{
     repeat_event, shuffle_event, volume_decreament_event, volume_increment_event,
 };
 use super::playlist_control::add_track_to_playlist::{
-    add_track_to_playlist_down_event, add_track_to_playlist_enter_event,
-    add_track_to_playlist_event, add_track_to_playlist_up_event,
+    add_track_to_playlist_enter_event, add_track_to_playlist_event,
+    add_track_to_playlist_move_event,
 };
 use super::playlist_control::{
     follow_playlist::follow_playlist_event, unfollow_playlist::unfollow_playlist_event,
 };
+use super::profiles::{go_to_profiles_event, profiles_enter_event, profiles_move_event};
 use super::refresh::refresh_event;
+use super::saved_searches::{
+    go_to_saved_searches_event, remember_search_history, saved_searches_key_event,
+};
 use super::search::{
-    go_to_search_event, search_down_event, search_enter_event, search_tab_event, search_up_event,
+    go_to_search_event, search_enter_event, search_move_event, search_tab_event, start_search,
 };
+use super::search_filters::{open_search_filters, search_filters_key_event};
+use super::setup::setup_key_event;
 use super::user_playlist::{
-    go_to_user_playlists_event, user_playlist_down_event, user_playlist_enter_event,
-    user_playlist_tab_event, user_playlist_up_event,
+    go_to_user_playlists_event, user_playlist_enter_event, user_playlist_move_event,
+    user_playlist_tab_event,
 };
-use super::util::{default_nav, delete_char, move_cursor_left, move_cursor_right, reset_cursor};
+use super::util::{delete_char, move_cursor_left, move_cursor_right, reset_cursor};
 use crate::app::App;
-use crate::enums::{InputMode, Menu};
-use crate::spotify::search::search::process_search;
+use crate::enums::{InputMode, Menu, Movement, View};
+use crate::spotify::search::query::SavedSearch;
 use crate::structs::{Key, Settings, Themes};
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use std::io::{self, Write};
@@ -61,6 +66,33 @@ pub fn handle_key_event(
     let change_keybind: char = key.change_keybind;
     let refresh_key: char = key.refresh_key;
     let open_config_fold_key: char = key.open_config_fold_key;
+    let profiles_key: char = key.profiles_key;
+    let account_key: char = key.account_key;
+    let saved_searches_key: char = key.saved_searches_key;
+
+    // Until the setup is done none of the other screens can be used
+    if app.selected_menu == Menu::Setup {
+        if key_event.kind == KeyEventKind::Press {
+            setup_key_event(app, key_event);
+        }
+        return;
+    }
+
+    // The filter form takes the typed keys, like the search box
+    if app.selected_menu == Menu::SearchFilters {
+        if key_event.kind == KeyEventKind::Press {
+            search_filters_key_event(app, key_event);
+        }
+        return;
+    }
+
+    // So does the name field of the saved searches screen
+    if app.selected_menu == Menu::SavedSearches {
+        if key_event.kind == KeyEventKind::Press {
+            saved_searches_key_event(app, key_event);
+        }
+        return;
+    }
 
     if key_event.kind == KeyEventKind::Press {
         match key_event.code {
@@ -80,6 +112,11 @@ pub fn handle_key_event(
                 repeat_event(app);
             }
 
+            // Copy every entry on the log screen
+            KeyCode::Char('y') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                log_copy_event(app, true);
+            }
+
             KeyCode::Char('p') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
                 add_track_to_playlist_event(app);
             }
@@ -94,6 +131,11 @@ pub fn handle_key_event(
                 unfollow_playlist_event(app);
             }
 
+            // Log out from the account screen
+            KeyCode::Char('o') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                account_log_out_event(app);
+            }
+
             // Exit the application when 'q' is pressed in Normal mode
             code if code == KeyCode::Char(exit_application_key)
                 && app.input_mode != InputMode::Editing =>
@@ -173,9 +215,26 @@ pub fn handle_key_event(
                 previous_track_event(app);
             }
 
-            // Key for Error Screen
+            // Key for the log screen
             code if code == KeyCode::Char(error_key) && app.input_mode != InputMode::Editing => {
-                go_to_error_event(app);
+                go_to_log_event(app);
+            }
+
+            // Key for the profile screen
+            code if code == KeyCode::Char(profiles_key) && app.input_mode != InputMode::Editing => {
+                go_to_profiles_event(app);
+            }
+
+            // Key for the account screen
+            code if code == KeyCode::Char(account_key) && app.input_mode != InputMode::Editing => {
+                go_to_account_event(app);
+            }
+
+            // Key for the saved searches screen
+            code if code == KeyCode::Char(saved_searches_key)
+                && app.input_mode != InputMode::Editing =>
+            {
+                go_to_saved_searches_event(app);
             }
 
             code if code == KeyCode::Char(change_keybind)
@@ -184,38 +243,24 @@ pub fn handle_key_event(
                 change_keybindings(app, key);
             }
 
-            // Down keybinding for all the menus
+            // Moving the selection in all the menus
             KeyCode::Down if app.input_mode != InputMode::Editing => {
-                library_down_event(app);
-                new_release_down_event(app);
-                user_playlist_down_event(app);
-                search_down_event(app);
-                add_track_to_playlist_down_event(app);
-
-                if app.can_navigate_menu {
-                    let next_index: usize = app.library_state.selected().unwrap_or(0) + 1;
-                    app.library_state.select(Some(next_index % 6)); //wrapping around the last option
-                    default_nav(app);
-                }
+                move_event(app, Movement::Next);
             }
-
-            // Up keybinding for all the menus
             KeyCode::Up if app.input_mode != InputMode::Editing => {
-                library_up_event(app);
-                new_release_up_event(app);
-                user_playlist_up_event(app);
-                search_up_event(app);
-                add_track_to_playlist_up_event(app);
-
-                if app.can_navigate_menu {
-                    let prev_index = if app.library_state.selected().unwrap_or(0) == 0 {
-                        5 //wrapping to the last option when user presses up at the first option
-                    } else {
-                        app.library_state.selected().unwrap_or(0) - 1
-                    };
-                    app.library_state.select(Some(prev_index));
-                    default_nav(app);
-                }
+                move_event(app, Movement::Previous);
+            }
+            KeyCode::PageDown if app.input_mode != InputMode::Editing => {
+                move_event(app, Movement::PageDown);
+            }
+            KeyCode::PageUp if app.input_mode != InputMode::Editing => {
+                move_event(app, Movement::PageUp);
+            }
+            KeyCode::Home if app.input_mode != InputMode::Editing => {
+                move_event(app, Movement::First);
+            }
+            KeyCode::End if app.input_mode != InputMode::Editing => {
+                move_event(app, Movement::Last);
             }
 
             // Enter keybinding for all the menus
@@ -225,6 +270,9 @@ pub fn handle_key_event(
                 library_enter_event(app);
                 search_enter_event(app);
                 add_track_to_playlist_enter_event(app);
+                log_copy_event(app, false);
+                profiles_enter_event(app);
+                account_enter_event(app);
             }
 
             // Tab keybinding for all the menus
@@ -233,6 +281,7 @@ pub fn handle_key_event(
                 new_release_tab_event(app);
                 library_tab_event(app);
                 search_tab_event(app);
+                log_filter_event(app);
             }
 
             // Pause/Play using Spacebar
@@ -240,18 +289,30 @@ pub fn handle_key_event(
                 play_pause_event(app);
             }
 
-            // Just exit from Search Menu
+            // Go back and forward through the view history with Alt+Left and Alt+Right
+            KeyCode::Left
+                if key_event.modifiers.contains(KeyModifiers::ALT)
+                    && app.input_mode != InputMode::Editing =>
+            {
+                app.go_back();
+            }
+            KeyCode::Right
+                if key_event.modifiers.contains(KeyModifiers::ALT)
+                    && app.input_mode != InputMode::Editing =>
+            {
+                app.go_forward();
+            }
+
+            // Leave the menus for the main screen
             KeyCode::Esc if app.input_mode != InputMode::Editing => {
-                app.selected_menu = Menu::Default;
+                app.navigate(View::Main);
             }
 
             // Handle character input in search mode
-            KeyCode::Char(c) if app.input_mode == InputMode::Editing => {
-                // Handle character input in search mode
-                if !c.is_control() {
-                    app.input.push(c);
-                    move_cursor_right(app);
-                }
+            KeyCode::Char(c) if app.input_mode == InputMode::Editing && !c.is_control() => {
+                app.input.push(c);
+                move_cursor_right(app);
+                app.search_history.edited(&app.input);
             }
 
             _ => {}
@@ -259,6 +320,29 @@ pub fn handle_key_event(
     }
 }
 
+// Only the menu on screen reacts to a movement
+fn move_event(app: &mut App, movement: Movement) {
+    library_move_event(app, movement);
+    new_release_move_event(app, movement);
+    user_playlist_move_event(app, movement);
+    search_move_event(app, movement);
+    add_track_to_playlist_move_event(app, movement);
+    log_move_event(app, movement);
+    profiles_move_event(app, movement);
+}
+
+/// The character a key types into a text field, None for Ctrl and Alt shortcuts
+pub fn typed_char(key_event: &KeyEvent) -> Option<char> {
+    match key_event.code {
+        KeyCode::Char(c)
+            if (key_event.modifiers - KeyModifiers::SHIFT).is_empty() && !c.is_control() =>
+        {
+            Some(c)
+        }
+        _ => None,
+    }
+}
+
 /// Function to handle search input and related key events
 pub fn search_input(app: &mut App, key_event: KeyEvent) -> io::Result<()> {
     if key_event.kind == KeyEventKind::Press {
@@ -272,6 +356,7 @@ pub fn search_input(app: &mut App, key_event: KeyEvent) -> io::Result<()> {
                 // Delete a character when Backspace is pressed
                 KeyCode::Backspace => {
                     delete_char(app);
+                    app.search_history.edited(&app.input);
                     std::io::sink().write_all(&[0])?;
                 }
                 // Move the cursor left when Left arrow is pressed
@@ -284,10 +369,27 @@ pub fn search_input(app: &mut App, key_event: KeyEvent) -> io::Result<()> {
                     move_cursor_right(app);
                     std::io::sink().write_all(&[0])?;
                 }
+                // Step through the queries searched lately
+                KeyCode::Up => {
+                    if let Some(query) = app.search_history.older(&app.input) {
+                        app.input = query.to_string();
+                        app.cursor_position = app.input.chars().count();
+                    }
+                }
+                KeyCode::Down => {
+                    if let Some(query) = app.search_history.newer() {
+                        app.input = query.to_string();
+                        app.cursor_position = app.input.chars().count();
+                    }
+                }
+                // Pick the field filters and result types on their own form
+                KeyCode::Tab => {
+                    open_search_filters(app);
+                }
                 // Exit search mode when Esc is pressed
                 KeyCode::Esc => {
                     app.input_mode = InputMode::Normal;
-                    app.search_results_rendered = false;
+                    app.search_history.edited(&app.input);
                     std::io::sink().write_all(&[0])?;
                 }
                 _ => {}
@@ -299,20 +401,28 @@ pub fn search_input(app: &mut App, key_event: KeyEvent) -> io::Result<()> {
     Ok(())
 }
 
-// Submit the search query and process the search results
-fn submit_message(app: &mut App) {
-    app.search_query = app.input.clone();
-
-    let binding = app.search_query.clone();
-    let query = binding.as_str();
-
-    let _ = process_search(app, query);
+/// Submits the search query with the filters, the results are shown once the search finishes
+pub fn submit_message(app: &mut App) {
+    let query = app.search_filters.compose(&app.input);
+
+    // Kept for Up in the search box and for the saved searches screen
+    app.search_history.record(&app.input);
+    remember_search_history(app);
+    app.last_search = Some(SavedSearch {
+        name: String::new(),
+        text: app.input.trim().to_string(),
+        filters: app.search_filters.clone(),
+    });
+
+    // Search-as-you-type may have searched for it already
+    app.live_search_due = None;
+    if !(app.live_search && query == app.search_query) {
+        app.search_query = query;
+        start_search(app);
+    }
 
     app.input.clear();
     reset_cursor(app);
 
     app.input_mode = InputMode::SearchResults;
-    app.search_results_rendered = true;
-    app.selected_search = true;
-    app.search_state.select(Some(0));
 }
@@ -0,0 +1,63 @@
+use crate::{
+    app::App,
+    enums::{Menu, Movement, Severity},
+    ui::tui::copy_to_clipboard,
+};
+
+pub fn go_to_log_event(app: &mut App) {
+    // The key also closes the error screen, which is only opened for problems the app can't carry on from
+    if matches!(app.selected_menu, Menu::Log | Menu::Error) {
+        app.show_current_view();
+    } else {
+        app.selected_menu = Menu::Log;
+        app.refresh_log_entries();
+        app.log_entries.apply(Movement::Last);
+    }
+}
+
+pub fn log_move_event(app: &mut App, movement: Movement) {
+    if app.selected_menu == Menu::Log {
+        app.log_entries.apply(movement);
+    }
+}
+
+// Cycles between every entry and the entries of one severity, the newest entry is selected
+pub fn log_filter_event(app: &mut App) {
+    if app.selected_menu == Menu::Log {
+        app.log_filter = match app.log_filter {
+            None => Some(Severity::Error),
+            Some(Severity::Error) => Some(Severity::Warning),
+            Some(Severity::Warning) => Some(Severity::Success),
+            Some(Severity::Success) => Some(Severity::Info),
+            Some(Severity::Info) => None,
+        };
+        app.refresh_log_entries();
+        app.log_entries.apply(Movement::Last);
+    }
+}
+
+/// Copies the selected entry, or every entry on screen with `whole_log`, to the clipboard
+pub fn log_copy_event(app: &mut App, whole_log: bool) {
+    if app.selected_menu != Menu::Log {
+        return;
+    }
+
+    let lines: Vec<String> = if whole_log {
+        app.log_entries.iter().map(|entry| entry.line()).collect()
+    } else {
+        app.log_entries
+            .selected()
+            .map(|entry| entry.line())
+            .into_iter()
+            .collect()
+    };
+    if lines.is_empty() {
+        return;
+    }
+
+    match copy_to_clipboard(&lines.join("\n")) {
+        Ok(()) if lines.len() == 1 => app.notify(Severity::Success, "Copied the entry"),
+        Ok(()) => app.notify(Severity::Success, format!("Copied {} entries", lines.len())),
+        Err(e) => app.notify(Severity::Error, format!("Couldn't copy: {}", e)),
+    }
+}
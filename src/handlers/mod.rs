@@ -1,14 +1,20 @@
+pub mod account;
 pub mod change_keybindings;
-pub mod error_screen;
 pub mod exit;
 pub mod help;
 pub mod key_event;
 pub mod library;
+pub mod log_screen;
 pub mod new_release;
 pub mod open_configure_folder;
 pub mod player;
 pub mod playlist_control;
+pub mod profiles;
 pub mod refresh;
+pub mod saved_searches;
 pub mod search;
+pub mod search_filters;
+pub mod setup;
+pub mod task;
 pub mod user_playlist;
 pub mod util;
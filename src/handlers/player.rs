@@ -1,67 +1,62 @@
 use crate::{
     app::App,
-    enums::Menu,
-    spotify::player::{
-        next_track::next_track, pause_playback::pause, play_playback::play,
-        previous_track::previous_track, repeat::cycle_repeat, shuffle::toogle_shuffle,
-        volume_decrease::volume_decreament, volume_increase::volume_increment,
-    },
+    enums::{Menu, Severity, Task},
     structs::Settings,
 };
 
 pub fn fullscreen_player_event(app: &mut App) {
     if app.selected_menu == Menu::Player {
-        app.selected_menu = Menu::Default;
+        app.show_current_view();
     } else {
         app.selected_menu = Menu::Player;
     }
 }
 
 pub fn repeat_event(app: &mut App) {
-    if let Err(e) = cycle_repeat(app) {
-        println!("{}", e);
-    }
+    app.dispatch(Task::CycleRepeat);
 }
 
 pub fn shuffle_event(app: &mut App) {
     app.is_shuffle = !app.is_shuffle;
-    if let Err(e) = toogle_shuffle(app) {
-        println!("{}", e);
-    }
+    app.dispatch(Task::ToggleShuffle);
 }
 
+// The volume is changed here rather than when Spotify answers, so presses quicker than a request build on each other
 pub fn volume_decreament_event(app: &mut App, settings: &mut Settings) {
-    if let Err(e) = volume_decreament(app, settings) {
-        println!("{}", e);
+    if settings.volume_percent == 0 {
+        app.notify(Severity::Warning, "Volume is already at 0%");
+        return;
     }
+    settings.volume_percent = settings
+        .volume_percent
+        .saturating_sub(settings.volume_decreament_value);
+    app.dispatch(Task::Volume(settings.volume_percent));
 }
 
 pub fn volume_increment_event(app: &mut App, settings: &mut Settings) {
-    if let Err(e) = volume_increment(app, settings) {
-        println!("{}", e);
+    if settings.volume_percent >= 100 {
+        app.notify(Severity::Warning, "Volume is already at 100%");
+        return;
     }
+    settings.volume_percent = settings
+        .volume_percent
+        .saturating_add(settings.volume_increment_value)
+        .min(100);
+    app.dispatch(Task::Volume(settings.volume_percent));
 }
 
 pub fn next_track_event(app: &mut App) {
-    if let Err(e) = next_track(app) {
-        println!("{}", e);
-    }
+    app.dispatch(Task::NextTrack);
 }
 
 pub fn previous_track_event(app: &mut App) {
-    if let Err(e) = previous_track(app) {
-        println!("{}", e);
-    }
+    app.dispatch(Task::PreviousTrack);
 }
 
 pub fn play_pause_event(app: &mut App) {
     if app.playback_status == "Paused" {
-        if let Err(e) = play(app) {
-            println!("{}", e);
-        }
+        app.dispatch(Task::Play);
     } else if app.playback_status == "Playing" {
-        if let Err(e) = pause(app) {
-            println!("{}", e);
-        }
+        app.dispatch(Task::Pause);
     }
 }
@@ -0,0 +1,37 @@
+use crate::{
+    app::App,
+    enums::{Menu, Movement},
+    settings::paths::list_profiles,
+};
+
+pub fn go_to_profiles_event(app: &mut App) {
+    if app.selected_menu == Menu::Profiles {
+        app.show_current_view();
+        return;
+    }
+
+    // Profiles made with `--profile` since the screen was last opened show up too
+    app.profiles = list_profiles(&app.root_config_dir).into();
+    if let Some(index) = app.profiles.iter().position(|name| *name == app.profile) {
+        app.profiles.select(index);
+    }
+    app.selected_menu = Menu::Profiles;
+}
+
+pub fn profiles_move_event(app: &mut App, movement: Movement) {
+    if app.selected_menu == Menu::Profiles {
+        app.profiles.apply(movement);
+    }
+}
+
+// The switch needs the runtime to log in, so the main loop makes it
+pub fn profiles_enter_event(app: &mut App) {
+    if app.selected_menu == Menu::Profiles {
+        if let Some(profile) = app.profiles.selected().cloned() {
+            if profile != app.profile {
+                app.switch_to_profile = Some(profile);
+            }
+        }
+        app.show_current_view();
+    }
+}
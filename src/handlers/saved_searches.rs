@@ -0,0 +1,140 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use serde::de::DeserializeOwned;
+
+use super::key_event::{submit_message, typed_char};
+use super::search::go_to_search_event;
+use super::search_filters::remember_search_filters;
+use crate::{
+    app::App,
+    enums::{Menu, Movement, Severity},
+    settings::paths::{read_yaml, write_yaml},
+    structs::SearchHistory,
+};
+
+/// Name of the file in the configuration directory the queries searched lately are remembered in
+pub const HISTORY_FILE: &str = "search_history.yml";
+/// Name of the file in the configuration directory the saved searches are kept in
+pub const SAVED_SEARCHES_FILE: &str = "saved_searches.yml";
+
+/// Opens the saved searches screen, or closes it when it is open
+pub fn go_to_saved_searches_event(app: &mut App) {
+    if app.selected_menu == Menu::SavedSearches {
+        app.show_current_view();
+        return;
+    }
+
+    app.saved_search_name.clear();
+    app.selected_menu = Menu::SavedSearches;
+}
+
+/// Handles every key while the saved searches screen is shown, the name field takes the keys the keybindings would
+pub fn saved_searches_key_event(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Down => app.saved_searches.apply(Movement::Next),
+        KeyCode::Up => app.saved_searches.apply(Movement::Previous),
+
+        // With a name typed the last search is saved, otherwise the selected one is run
+        KeyCode::Enter if !app.saved_search_name.trim().is_empty() => save_last_search(app),
+        KeyCode::Enter => run_saved_search(app),
+        KeyCode::Delete => {
+            if let Some(index) = app.saved_searches.selected_index() {
+                let mut searches = app.saved_searches.items().to_vec();
+                searches.remove(index);
+                app.saved_searches.set_items(searches);
+                remember_saved_searches(app);
+            }
+        }
+        KeyCode::Esc => app.show_current_view(),
+
+        KeyCode::Backspace => {
+            app.saved_search_name.pop();
+        }
+        _ => {
+            if let Some(c) = typed_char(&key_event) {
+                app.saved_search_name.push(c);
+            }
+        }
+    }
+}
+
+/// Writes the search history to the configuration directory, so Up in the search box finds the queries after a restart
+pub fn remember_search_history(app: &App) {
+    let path = app.config_dir.join(HISTORY_FILE);
+    if let Err(e) = write_yaml(&path, &app.search_history.entries()) {
+        app.log.record(
+            Severity::Warning,
+            format!("Couldn't remember the search history: {}", e),
+        );
+    }
+}
+
+/// Reads the search history and the saved searches kept by the last runs
+pub fn load_searches(app: &mut App) {
+    app.search_history = SearchHistory::new(load_list(app, HISTORY_FILE));
+    let saved_searches = load_list(app, SAVED_SEARCHES_FILE);
+    app.saved_searches.set_items(saved_searches);
+}
+
+// Keeps the last search under the typed name, a saved search with the same name is replaced
+fn save_last_search(app: &mut App) {
+    let Some(mut search) = app.last_search.clone() else {
+        app.notify(
+            Severity::Warning,
+            "Search for something first, then save the search here",
+        );
+        return;
+    };
+    search.name = app.saved_search_name.trim().to_string();
+
+    let mut searches = app.saved_searches.items().to_vec();
+    let index = match searches.iter().position(|saved| saved.name == search.name) {
+        Some(index) => {
+            searches[index] = search;
+            index
+        }
+        None => {
+            searches.push(search);
+            searches.len() - 1
+        }
+    };
+    app.saved_searches.set_items(searches);
+    app.saved_searches.select(index);
+    app.saved_search_name.clear();
+    remember_saved_searches(app);
+}
+
+// Searches again with the saved text and filters, the filters stay picked for the next searches
+fn run_saved_search(app: &mut App) {
+    let Some(search) = app.saved_searches.selected().cloned() else {
+        return;
+    };
+
+    go_to_search_event(app);
+    app.search_filters = search.filters;
+    remember_search_filters(app);
+    app.input = search.text;
+    submit_message(app);
+}
+
+fn remember_saved_searches(app: &App) {
+    let path = app.config_dir.join(SAVED_SEARCHES_FILE);
+    if let Err(e) = write_yaml(&path, &app.saved_searches.items()) {
+        app.log.record(
+            Severity::Warning,
+            format!("Couldn't keep the saved searches: {}", e),
+        );
+    }
+}
+
+// A list kept in the configuration directory, empty until something is written to it
+fn load_list<T: DeserializeOwned>(app: &App, name: &str) -> Vec<T> {
+    let path = app.config_dir.join(name);
+    if !path.exists() {
+        return Vec::new();
+    }
+    read_yaml(&path).unwrap_or_else(|e| {
+        // The error names the file
+        app.log.record(Severity::Warning, e.to_string());
+        Vec::new()
+    })
+}
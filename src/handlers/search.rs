@@ -1,219 +1,176 @@
-use super::util::{
-    default, default_search, down_key_for_list, down_key_for_table, up_key_for_list,
-    up_key_for_table,
-};
+use super::util::{default, play_selected_track};
 use crate::{
     app::App,
-    enums::{InputMode, Menu, SearchMenu},
-    spotify::{
-        player::start_playback::start_playback,
-        search::{
-            search_albums::{process_selected_album_tracks, search_selected_album_tracks},
-            search_artists::{process_selected_artist_tracks, search_selected_artist_tracks},
-            search_playlists::{process_selected_playlist_tracks, search_selected_playlist_tracks},
-        },
-    },
+    enums::{InputMode, Menu, Movement, SearchCategory, Task, View},
+    spotify::search::search::SearchPaging,
 };
 
 pub fn go_to_search_event(app: &mut App) {
-    app.selected_menu = Menu::Search;
+    app.navigate(View::Search);
     default(app);
     app.input_mode = InputMode::Editing;
 }
 
-pub fn search_down_event(app: &mut App) {
-    if app.selected_menu == Menu::Search {
-        if app.selected_search {
-            if app.selected_track_in_search_result {
-                (app.track_state_in_search_result, app.track_index) = down_key_for_list(
-                    app.track_names_search_results.clone(),
-                    app.track_state_in_search_result.clone(),
-                );
-            }
-            if app.selected_album_in_search_result {
-                (app.album_state_in_search_result, app.album_index) = down_key_for_list(
-                    app.album_names_search_results.clone(),
-                    app.album_state_in_search_result.clone(),
-                );
-            }
-            if app.selected_artist_in_search_result {
-                (app.artist_state_in_search_result, app.artist_index) = down_key_for_list(
-                    app.artist_names_search_results.clone(),
-                    app.artist_state_in_search_result.clone(),
-                );
-            }
-            if app.selected_playlist_in_search_result {
-                (app.playlist_state_in_search_result, app.playlist_index) = down_key_for_list(
-                    app.playlist_names_search_results.clone(),
-                    app.playlist_state_in_search_result.clone(),
-                );
-            }
-        }
-        if app.search_menu == SearchMenu::SearchedAlbum && app.searched_album_selected {
-            (app.searched_album_state, app.searched_album_index) = down_key_for_table(
-                app.selected_album_tracks_names.clone(),
-                app.searched_album_state.clone(),
-            );
-        }
-        if app.search_menu == SearchMenu::SearchedArtist && app.searched_artist_selected {
-            (app.searched_artist_state, app.searched_artist_index) = down_key_for_table(
-                app.selected_artist_tracks_names.clone(),
-                app.searched_artist_state.clone(),
-            );
-        }
-        if app.search_menu == SearchMenu::SearchedPlaylist && app.searched_playlist_selected {
-            (app.searched_playlist_state, app.searched_playlist_index) = down_key_for_table(
-                app.selected_playlist_tracks_names.clone(),
-                app.searched_playlist_state.clone(),
-            );
-        }
+/// Searches for `app.search_query`, every type of results is asked for at once and fills its pane when it returns
+pub fn start_search(app: &mut App) {
+    app.search_generation += 1;
+    app.search_paging = SearchPaging::default();
+    app.search_loading = None;
+    app.searched_tracks.set_items(Vec::new());
+    app.searched_artists.set_items(Vec::new());
+    app.searched_albums.set_items(Vec::new());
+    app.searched_playlists.set_items(Vec::new());
+    app.search_state.select(Some(0));
+
+    // The types left out on the filter form stay empty
+    app.search_pending = SearchCategory::ALL
+        .into_iter()
+        .filter(|&category| app.search_filters.includes(category))
+        .collect();
+    for &category in &app.search_pending {
+        app.dispatch(Task::Search(app.search_query.clone(), category));
+    }
+}
+
+/// Searches for the text typed so far, search-as-you-type calls it once the typing pauses
+pub fn live_search_event(app: &mut App) {
+    if app.selected_menu != Menu::Search
+        || app.input_mode != InputMode::Editing
+        || app.input.trim().is_empty()
+    {
+        return;
+    }
+
+    let query = app.search_filters.compose(&app.input);
+    if query != app.search_query {
+        app.search_query = query;
+        start_search(app);
     }
 }
 
-pub fn search_up_event(app: &mut App) {
+pub fn search_move_event(app: &mut App, movement: Movement) {
     if app.selected_menu == Menu::Search {
-        if app.selected_search {
-            if app.selected_track_in_search_result {
-                (app.track_state_in_search_result, app.track_index) = up_key_for_list(
-                    app.track_names_search_results.clone(),
-                    app.track_state_in_search_result.clone(),
-                );
-            }
-            if app.selected_album_in_search_result {
-                (app.album_state_in_search_result, app.album_index) = up_key_for_list(
-                    app.album_names_search_results.clone(),
-                    app.album_state_in_search_result.clone(),
-                );
-            }
-            if app.selected_artist_in_search_result {
-                (app.artist_state_in_search_result, app.artist_index) = up_key_for_list(
-                    app.artist_names_search_results.clone(),
-                    app.artist_state_in_search_result.clone(),
-                );
+        if app.search_results_shown() {
+            if let Some(category) = focused_results(app) {
+                move_through_results(app, category, movement);
             }
-            if app.selected_playlist_in_search_result {
-                (app.playlist_state_in_search_result, app.playlist_index) = up_key_for_list(
-                    app.playlist_names_search_results.clone(),
-                    app.playlist_state_in_search_result.clone(),
-                );
-            }
-        }
-        if app.search_menu == SearchMenu::SearchedAlbum && app.searched_album_selected {
-            (app.searched_album_state, app.searched_album_index) = up_key_for_table(
-                app.selected_album_tracks_names.clone(),
-                app.searched_album_state.clone(),
-            );
         }
-        if app.search_menu == SearchMenu::SearchedArtist && app.searched_artist_selected {
-            (app.searched_artist_state, app.searched_artist_index) = up_key_for_table(
-                app.selected_artist_tracks_names.clone(),
-                app.searched_artist_state.clone(),
-            );
-        }
-        if app.search_menu == SearchMenu::SearchedPlaylist && app.searched_playlist_selected {
-            (app.searched_playlist_state, app.searched_playlist_index) = up_key_for_table(
-                app.selected_playlist_tracks_names.clone(),
-                app.searched_playlist_state.clone(),
-            );
+        match app.navigation.current() {
+            View::SearchedAlbum => app.selected_album_tracks.apply(movement),
+            View::SearchedArtist => app.selected_artist_tracks.apply(movement),
+            View::SearchedPlaylist => app.selected_playlist_tracks.apply(movement),
+            _ => {}
         }
     }
 }
 
-pub fn search_enter_event(app: &mut App) {
-    if app.selected_menu == Menu::Search {
-        if app.is_in_track {
-            app.is_only_id = true;
-            app.selected_link_for_playback =
-                app.track_links_search_results[app.track_index].clone();
-            if let Err(e) = start_playback(app) {
-                println!("{}", e);
-            }
-        }
+// The pane of results that has the focus
+fn focused_results(app: &App) -> Option<SearchCategory> {
+    if app.selected_track_in_search_result {
+        Some(SearchCategory::Tracks)
+    } else if app.selected_artist_in_search_result {
+        Some(SearchCategory::Artists)
+    } else if app.selected_album_in_search_result {
+        Some(SearchCategory::Albums)
+    } else if app.selected_playlist_in_search_result {
+        Some(SearchCategory::Playlists)
+    } else {
+        None
+    }
+}
 
-        if app.search_menu == SearchMenu::SearchedAlbum {
-            app.is_only_id = false;
-            app.selected_link_for_playback =
-                app.selected_album_tracks_links[app.searched_album_index].clone();
-            if let Err(e) = start_playback(app) {
-                println!("{}", e);
-            }
-        } else if app.selected_album_in_search_result {
-            if let Err(e) = search_selected_album_tracks(app) {
-                println!("{}", e);
-            }
-            process_selected_album_tracks(app);
-            default_search(app);
-            app.search_menu = SearchMenu::SearchedAlbum;
-            app.searched_album_selected = true;
-        }
+// Reaching the last result that was fetched asks Spotify for the next page
+fn move_through_results(app: &mut App, category: SearchCategory, movement: Movement) {
+    let more = app.search_paging.get(category).has_more();
+    let at_end = |app: &App| {
+        let (len, selected) = match category {
+            SearchCategory::Tracks => (
+                app.searched_tracks.items().len(),
+                app.searched_tracks.selected_index(),
+            ),
+            SearchCategory::Artists => (
+                app.searched_artists.items().len(),
+                app.searched_artists.selected_index(),
+            ),
+            SearchCategory::Albums => (
+                app.searched_albums.items().len(),
+                app.searched_albums.selected_index(),
+            ),
+            SearchCategory::Playlists => (
+                app.searched_playlists.items().len(),
+                app.searched_playlists.selected_index(),
+            ),
+        };
+        selected.is_some_and(|index| index + 1 >= len)
+    };
 
-        if app.search_menu == SearchMenu::SearchedArtist {
-            app.is_only_id = false;
-            app.selected_link_for_playback =
-                app.selected_artist_tracks_links[app.searched_artist_index].clone();
-            if let Err(e) = start_playback(app) {
-                println!("{}", e);
-            }
-        } else if app.selected_artist_in_search_result {
-            if let Err(e) = search_selected_artist_tracks(app) {
-                println!("{}", e);
-            }
-            process_selected_artist_tracks(app);
-            default_search(app);
-            app.search_menu = SearchMenu::SearchedArtist;
-            app.searched_artist_selected = true;
+    // With more results to come the selection waits at the end instead of wrapping to the top
+    let waits = more
+        && at_end(app)
+        && matches!(
+            movement,
+            Movement::Next | Movement::PageDown | Movement::Last
+        );
+    if !waits {
+        match category {
+            SearchCategory::Tracks => app.searched_tracks.apply(movement),
+            SearchCategory::Artists => app.searched_artists.apply(movement),
+            SearchCategory::Albums => app.searched_albums.apply(movement),
+            SearchCategory::Playlists => app.searched_playlists.apply(movement),
         }
+    }
 
-        if app.search_menu == SearchMenu::SearchedPlaylist {
-            app.is_only_id = false;
-            app.selected_link_for_playback =
-                app.selected_playlist_tracks_links[app.searched_playlist_index].clone();
-            if let Err(e) = start_playback(app) {
-                println!("{}", e);
-            }
-        } else if app.selected_playlist_in_search_result {
-            if let Err(e) = search_selected_playlist_tracks(app) {
-                println!("{}", e);
-            }
-            process_selected_playlist_tracks(app);
-            default_search(app);
-            app.search_menu = SearchMenu::SearchedPlaylist;
-            app.searched_playlist_selected = true;
+    if more && at_end(app) && app.search_loading.is_none() {
+        app.search_loading = Some(category);
+        app.dispatch(Task::SearchMore(app.search_query.clone(), category));
+    }
+}
+
+pub fn search_enter_event(app: &mut App) {
+    if app.selected_menu == Menu::Search {
+        if app.navigation.current() != View::Search || app.is_in_track {
+            play_selected_track(app);
+        } else if app.selected_album_in_search_result && !app.searched_albums.is_empty() {
+            app.dispatch(Task::SearchedAlbumTracks);
+        } else if app.selected_artist_in_search_result && !app.searched_artists.is_empty() {
+            app.dispatch(Task::SearchedArtistTracks);
+        } else if app.selected_playlist_in_search_result && !app.searched_playlists.is_empty() {
+            app.dispatch(Task::SearchedPlaylistTracks);
         }
     }
 }
 
 pub fn search_tab_event(app: &mut App) {
-    if app.selected_search {
+    if app.search_results_shown() {
         app.can_navigate_menu = false;
-        app.track_state_in_search_result.select(None);
-        app.artist_state_in_search_result.select(None);
-        app.album_state_in_search_result.select(None);
-        app.playlist_state_in_search_result.select(None);
+        app.searched_tracks.unselect();
+        app.searched_artists.unselect();
+        app.searched_albums.unselect();
+        app.searched_playlists.unselect();
 
         if app.search_state.selected() == Some(0) {
-            app.track_state_in_search_result.select(Some(0));
+            app.searched_tracks.select(0);
             app.selected_track_in_search_result = !app.selected_track_in_search_result;
             app.selected_artist_in_search_result = false;
             app.selected_album_in_search_result = false;
             app.selected_playlist_in_search_result = false;
             app.is_in_track = true;
         } else if app.search_state.selected() == Some(1) {
-            app.artist_state_in_search_result.select(Some(0));
+            app.searched_artists.select(0);
             app.selected_artist_in_search_result = !app.selected_artist_in_search_result;
             app.selected_track_in_search_result = false;
             app.selected_album_in_search_result = false;
             app.selected_playlist_in_search_result = false;
             app.is_in_track = false;
         } else if app.search_state.selected() == Some(2) {
-            app.album_state_in_search_result.select(Some(0));
+            app.searched_albums.select(0);
             app.selected_album_in_search_result = !app.selected_album_in_search_result;
             app.selected_track_in_search_result = false;
             app.selected_artist_in_search_result = false;
             app.selected_playlist_in_search_result = false;
             app.is_in_track = false;
         } else if app.search_state.selected() == Some(3) {
-            app.playlist_state_in_search_result.select(Some(0));
+            app.searched_playlists.select(0);
             app.selected_playlist_in_search_result = !app.selected_playlist_in_search_result;
             app.selected_track_in_search_result = false;
             app.selected_artist_in_search_result = false;
@@ -0,0 +1,117 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::key_event::{submit_message, typed_char};
+use crate::{
+    app::App,
+    enums::{FilterField, InputMode, Menu, Movement, Severity},
+    settings::paths::{read_yaml, write_yaml},
+    spotify::search::query::SearchFilters,
+};
+
+/// Name of the file in the configuration directory the filters are remembered in, next to the settings
+pub const FILTERS_FILE: &str = "search_filters.yml";
+
+/// Opens the filter form from the search box, the typed text is kept for the search
+pub fn open_search_filters(app: &mut App) {
+    app.search_filter_fields.select(0);
+    app.input_mode = InputMode::Normal;
+    app.selected_menu = Menu::SearchFilters;
+}
+
+/// Handles every key while the filter form is open, its text fields take the keys the keybindings would
+pub fn search_filters_key_event(app: &mut App, key_event: KeyEvent) {
+    let Some(field) = app.search_filter_fields.selected().copied() else {
+        return;
+    };
+
+    match key_event.code {
+        KeyCode::Down | KeyCode::Tab => app.search_filter_fields.apply(Movement::Next),
+        KeyCode::Up | KeyCode::BackTab => app.search_filter_fields.apply(Movement::Previous),
+
+        // Searches for the text in the search box with the filters
+        KeyCode::Enter => {
+            close_search_filters(app);
+            submit_message(app);
+        }
+        KeyCode::Esc => close_search_filters(app),
+        KeyCode::Delete => app.search_filters = SearchFilters::default(),
+
+        KeyCode::Backspace => {
+            if let Some(value) = text_field(&mut app.search_filters, field) {
+                value.pop();
+            }
+        }
+        _ => {
+            let Some(c) = typed_char(&key_event) else {
+                return;
+            };
+            match text_field(&mut app.search_filters, field) {
+                // A year is a number or a range of them
+                Some(value) if field != FilterField::Year || c.is_ascii_digit() || c == '-' => {
+                    value.push(c)
+                }
+                Some(_) => {}
+                None if c == ' ' => toggle(&mut app.search_filters, field),
+                None => {}
+            }
+        }
+    }
+}
+
+// Goes back to the search box and remembers the filters for the next run
+fn close_search_filters(app: &mut App) {
+    app.selected_menu = Menu::Search;
+    app.input_mode = InputMode::Editing;
+    remember_search_filters(app);
+}
+
+/// Writes the filters to the configuration directory, they are picked again on the next run
+pub fn remember_search_filters(app: &App) {
+    if let Err(e) = write_yaml(&app.config_dir.join(FILTERS_FILE), &app.search_filters) {
+        app.log.record(
+            Severity::Warning,
+            format!("Couldn't remember the search filters: {}", e),
+        );
+    }
+}
+
+/// Reads the filters picked on the filter form last time, none are picked before the form is first used
+pub fn load_search_filters(app: &mut App) {
+    let path = app.config_dir.join(FILTERS_FILE);
+    if !path.exists() {
+        app.search_filters = SearchFilters::default();
+        return;
+    }
+    app.search_filters = read_yaml(&path).unwrap_or_else(|e| {
+        app.log.record(
+            Severity::Warning,
+            format!("Couldn't read the search filters: {}", e),
+        );
+        SearchFilters::default()
+    });
+}
+
+// The value of a text field, None for the fields that are switched on and off
+fn text_field(filters: &mut SearchFilters, field: FilterField) -> Option<&mut String> {
+    match field {
+        FilterField::Artist => Some(&mut filters.artist),
+        FilterField::Album => Some(&mut filters.album),
+        FilterField::Year => Some(&mut filters.year),
+        FilterField::Genre => Some(&mut filters.genre),
+        FilterField::Isrc => Some(&mut filters.isrc),
+        _ => None,
+    }
+}
+
+// Switches a field on or off, the text fields are left alone
+fn toggle(filters: &mut SearchFilters, field: FilterField) {
+    let value = match field {
+        FilterField::NewReleases => &mut filters.new_releases,
+        FilterField::Tracks => &mut filters.tracks,
+        FilterField::Artists => &mut filters.artists,
+        FilterField::Albums => &mut filters.albums,
+        FilterField::Playlists => &mut filters.playlists,
+        _ => return,
+    };
+    *value = !*value;
+}
@@ -0,0 +1,142 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use std::mem;
+
+use super::key_event::typed_char;
+use crate::{
+    app::App,
+    enums::{AuthFlow, Menu, SetupStep},
+    structs::Setup,
+};
+
+/// Handles every key while the setup screen is shown, its text fields take the keys the keybindings would
+pub fn setup_key_event(app: &mut App, key_event: KeyEvent) {
+    let setup = &mut app.setup;
+    match (setup.step, key_event.code) {
+        (SetupStep::Instructions, KeyCode::Enter) => setup.step = SetupStep::ClientId,
+        (SetupStep::Instructions, KeyCode::Esc) => app.exit(),
+        (SetupStep::Failed | SetupStep::LoggedOut, KeyCode::Esc) if setup.relogin => {
+            leave_relogin(app)
+        }
+        (SetupStep::Failed, KeyCode::Esc) => app.exit(),
+
+        (SetupStep::ClientId, KeyCode::Enter) => {
+            let client_id = setup.client_id.trim().to_string();
+            if is_client_id(&client_id) {
+                setup.client_id = client_id;
+                setup.error.clear();
+                setup.step = SetupStep::ClientSecret;
+            } else {
+                setup.error =
+                    "A Client ID is 32 letters and digits, copy it from your app's settings on the dashboard"
+                        .to_string();
+            }
+        }
+        (SetupStep::ClientId, KeyCode::Esc) => {
+            setup.error.clear();
+            setup.step = SetupStep::Instructions;
+        }
+
+        (SetupStep::ClientSecret, KeyCode::Enter) => {
+            setup.client_secret = setup.client_secret.trim().to_string();
+            app.client_id = setup.client_id.clone();
+            app.client_secret = setup.client_secret.clone();
+            app.auth_flow = if app.client_secret.is_empty() {
+                AuthFlow::Pkce
+            } else {
+                AuthFlow::ClientSecret
+            };
+            begin_login(setup);
+        }
+        (SetupStep::ClientSecret, KeyCode::Esc) => setup.step = SetupStep::ClientId,
+
+        (SetupStep::LoggingIn, KeyCode::Enter) if setup.login.paste_reason().is_some() => {
+            let url = mem::take(&mut setup.redirected_url);
+            setup.login.paste(url);
+        }
+        // Leaving the step makes the main loop stop the login
+        (SetupStep::LoggingIn, KeyCode::Esc) if setup.relogin => {
+            if app.backend.is_some() {
+                leave_relogin(app);
+            } else {
+                setup.step = SetupStep::LoggedOut;
+            }
+        }
+        (SetupStep::LoggingIn, KeyCode::Esc) => setup.step = SetupStep::ClientSecret,
+
+        (SetupStep::Failed | SetupStep::LoggedOut, KeyCode::Enter) if setup.relogin => {
+            begin_login(setup)
+        }
+        (SetupStep::Failed, KeyCode::Enter) => {
+            setup.error.clear();
+            setup.step = SetupStep::ClientId;
+        }
+
+        (_, KeyCode::Backspace) => {
+            if let Some(field) = input_field(setup) {
+                field.pop();
+            }
+        }
+        _ => {
+            if let (Some(c), Some(field)) = (typed_char(&key_event), input_field(setup)) {
+                field.push(c);
+            }
+        }
+    }
+}
+
+/// Opens the setup screen on its login step, to log in again with the creds already saved
+pub fn start_relogin(app: &mut App) {
+    app.setup = Setup {
+        relogin: true,
+        ..Setup::default()
+    };
+    begin_login(&mut app.setup);
+    app.selected_menu = Menu::Setup;
+}
+
+/// Opens the setup screen on its login step for the profile being switched to, a profile with a token logs in by itself
+pub fn start_profile_login(app: &mut App, profile: &str) {
+    app.setup = Setup {
+        relogin: true,
+        profile: Some(profile.to_string()),
+        ..Setup::default()
+    };
+    begin_login(&mut app.setup);
+    app.selected_menu = Menu::Setup;
+}
+
+// Shows the login step, the main loop starts the login itself
+fn begin_login(setup: &mut Setup) {
+    setup.login = Default::default();
+    setup.redirected_url.clear();
+    setup.error.clear();
+    setup.step = SetupStep::LoggingIn;
+    setup.start_login = true;
+}
+
+// Goes back to the app when it still has a backend, otherwise there is nothing to go back to
+fn leave_relogin(app: &mut App) {
+    if app.backend.is_some() {
+        app.setup = Setup::default();
+        app.show_current_view();
+    } else {
+        app.exit();
+    }
+}
+
+// The text field of the current step, None for the steps that only show text
+fn input_field(setup: &mut Setup) -> Option<&mut String> {
+    match setup.step {
+        SetupStep::ClientId => Some(&mut setup.client_id),
+        SetupStep::ClientSecret => Some(&mut setup.client_secret),
+        SetupStep::LoggingIn if setup.login.paste_reason().is_some() => {
+            Some(&mut setup.redirected_url)
+        }
+        _ => None,
+    }
+}
+
+// The dashboard shows the Client ID as 32 hexadecimal digits
+fn is_client_id(client_id: &str) -> bool {
+    client_id.len() == 32 && client_id.chars().all(|c| c.is_ascii_hexdigit())
+}
@@ -0,0 +1,217 @@
+use crate::{
+    app::App,
+    enums::{Library, Menu, Severity, Task, View},
+    spotify::{
+        backend::request::RequestError,
+        library_section::{
+            liked_songs::process_liked_tracks, made_fy::process_made_fy,
+            made_fy_tracks::process_made_fy_tracks, podcast::process_podcasts,
+            recently_played::process_recently_played, user_album_tracks::process_user_album_tracks,
+            user_albums::process_user_albums, user_artist_tracks::process_user_artist_tracks,
+            user_artists::process_user_artists,
+        },
+        new_release_section::{
+            new_releases::process_new_releases, new_releases_tracks::process_new_releases_tracks,
+        },
+        player::player::{idle_playback, process_currently_playing, OFFLINE},
+        search::{
+            search::{process_more_search_results, process_search_results},
+            search_albums::process_selected_album_tracks,
+            search_artists::process_selected_artist_tracks,
+            search_playlists::process_selected_playlist_tracks,
+        },
+        task::{TaskData, TaskResult},
+        user_playlist::{
+            user_playlist::process_user_playlists, user_playlist_track::process_playlist_tracks,
+        },
+        user_stats::top_tracks::process_top_tracks,
+    },
+    structs::Settings,
+};
+
+/// Applies the result of a finished background request to the app
+pub fn task_completed_event(app: &mut App, settings: &mut Settings, result: TaskResult) {
+    let TaskResult {
+        task,
+        app: snapshot,
+        data,
+    } = result;
+
+    // The request was made for the profile the user switched away from
+    if snapshot.profile != app.profile {
+        return;
+    }
+
+    // Following or unfollowing changes the user's playlists, even a failed attempt refetches them
+    if matches!(task, Task::FollowPlaylist | Task::UnfollowPlaylist) {
+        app.dispatch(Task::UserPlaylists);
+    }
+
+    // A pane stops waiting once its results are in, or once they failed, and another page can be asked for
+    match &task {
+        Task::Search(_, category) if snapshot.search_generation == app.search_generation => {
+            app.search_pending.retain(|pending| pending != category);
+        }
+        Task::SearchMore(..) if snapshot.search_generation == app.search_generation => {
+            app.search_loading = None
+        }
+        _ => {}
+    }
+
+    let data = match data {
+        Ok(data) => data,
+        Err(e) => {
+            let message = format!("{} failed: {}", task.description(), e.message);
+            // Only the first failure opens the error screen, the player keeps polling until the login is renewed
+            if e.fatal && !app.login_expired && app.selected_menu != Menu::Setup {
+                app.log.record(Severity::Error, message.clone());
+                app.login_expired = true;
+                app.error_text = format!("{}\n\nPress Enter to log in again", message);
+                app.selected_menu = Menu::Error;
+            } else if matches!(task, Task::CurrentlyPlaying) {
+                // A rate limit or a hiccup keeps the last playback shown, losing the connection doesn't
+                if e.request == Some(RequestError::Offline) {
+                    process_currently_playing(app, settings, &idle_playback(OFFLINE));
+                }
+                // The player is polled constantly, an offline device shouldn't flood the screen or the log
+                if app.log.last_message() != Some(message.clone()) {
+                    app.log.record(Severity::Warning, message);
+                }
+            } else {
+                app.notify(Severity::Error, message);
+            }
+            return;
+        }
+    };
+
+    // Only update a view if the user is still on the screen that asked for the data
+    let same_view: bool = app.navigation.current() == snapshot.navigation.current()
+        && app.selected_menu == snapshot.selected_menu;
+    let same_library: bool =
+        same_view && app.library.selected_index() == snapshot.library.selected_index();
+
+    match (task, data) {
+        (Task::CurrentlyPlaying, TaskData::Playback(playback)) => {
+            let previous_id = app.current_playing_id.clone();
+            process_currently_playing(app, settings, &playback);
+            if app.current_playing_id != previous_id && !app.current_playing_name.is_empty() {
+                app.log.record(
+                    Severity::Info,
+                    format!(
+                        "Now playing {} - {}",
+                        app.current_playing_name, app.currently_playing_artist
+                    ),
+                );
+            }
+        }
+        (Task::Pause, _) => app.device_id_after_pause = snapshot.device_id_after_pause,
+
+        (Task::MadeFY, TaskData::Playlists(playlists)) if same_library => {
+            process_made_fy(app, &playlists);
+            app.made_fy_playlists.select(0);
+            app.navigate(View::LibrarySection(Library::MadeFY));
+        }
+        (Task::MadeFYTracks, TaskData::PlaylistItems(items)) if same_view => {
+            process_made_fy_tracks(app, &items);
+            app.made_fy_tracks.select(0);
+            app.navigate(View::LibraryTracks(Library::MadeFY));
+        }
+        (Task::LikedSongs, TaskData::SavedTracks(tracks)) if same_library => {
+            process_liked_tracks(app, &tracks);
+            app.liked_songs.select(0);
+            app.navigate(View::LibrarySection(Library::LikedSongs));
+        }
+        (Task::RecentlyPlayed, TaskData::PlayHistory(history)) if same_library => {
+            process_recently_played(app, &history);
+            app.recently_played.select(0);
+            app.navigate(View::LibrarySection(Library::RecentlyPlayed));
+        }
+        (Task::UserAlbums, TaskData::SavedAlbums(albums)) if same_library => {
+            process_user_albums(app, &albums);
+            app.user_albums.select(0);
+            app.navigate(View::LibrarySection(Library::Albums));
+        }
+        (Task::UserAlbumTracks, TaskData::AlbumTracks(tracks)) if same_view => {
+            process_user_album_tracks(app, &tracks);
+            app.user_album_tracks.select(0);
+            app.navigate(View::LibraryTracks(Library::Albums));
+        }
+        (Task::UserArtists, TaskData::Artists(artists)) if same_library => {
+            process_user_artists(app, &artists);
+            app.user_artists.select(0);
+            app.navigate(View::LibrarySection(Library::Artists));
+        }
+        (Task::UserArtistTracks, TaskData::Tracks(tracks)) if same_view => {
+            process_user_artist_tracks(app, &tracks);
+            app.user_artist_tracks.select(0);
+            app.navigate(View::LibraryTracks(Library::Artists));
+        }
+        (Task::Podcasts, TaskData::Shows(shows)) if same_library => {
+            process_podcasts(app, &shows);
+            app.podcasts.select(0);
+            app.navigate(View::LibrarySection(Library::Podcasts));
+        }
+
+        (Task::AddTrackToPlaylist, _) => app.notify(
+            Severity::Success,
+            format!(
+                "Added {} to the playlist",
+                snapshot.track_added_to_playlist_name
+            ),
+        ),
+        (Task::FollowPlaylist, _) => app.notify(Severity::Success, "Followed the playlist"),
+        (Task::UnfollowPlaylist, _) => app.notify(Severity::Success, "Unfollowed the playlist"),
+
+        (Task::NewReleases, TaskData::Albums(albums)) => process_new_releases(app, &albums),
+        (Task::NewReleaseTracks, TaskData::AlbumTracks(tracks)) if same_view => {
+            process_new_releases_tracks(app, &tracks);
+            app.new_release_tracks.select(0);
+            app.navigate(View::NewReleaseTracks);
+        }
+
+        (Task::UserPlaylists, TaskData::Playlists(playlists)) => {
+            process_user_playlists(app, &playlists);
+            app.have_playlist = !playlists.is_empty();
+        }
+        (Task::UserPlaylistTracks, TaskData::PlaylistItems(items)) if same_view => {
+            process_playlist_tracks(app, &items);
+            app.user_playlist_tracks.select(0);
+            app.navigate(View::PlaylistTracks);
+        }
+
+        (Task::TopTracks, TaskData::TopTracks(all_time, six_months, four_weeks)) => {
+            process_top_tracks(app, &all_time, &six_months, &four_weeks);
+        }
+
+        // Ignore the results of a search that has since been replaced by a newer one, even of the same query
+        (Task::Search(_, category), TaskData::Search(results))
+            if snapshot.search_generation == app.search_generation =>
+        {
+            process_search_results(app, category, &results);
+        }
+        // A page loaded for an earlier search, even of the same query, doesn't belong after the new first page
+        (Task::SearchMore(_, category), TaskData::Search(results))
+            if snapshot.search_generation == app.search_generation =>
+        {
+            process_more_search_results(app, category, &results);
+        }
+        (Task::SearchedAlbumTracks, TaskData::AlbumTracks(tracks)) if same_view => {
+            process_selected_album_tracks(app, &tracks);
+            app.selected_album_tracks.select(0);
+            app.navigate(View::SearchedAlbum);
+        }
+        (Task::SearchedArtistTracks, TaskData::Tracks(tracks)) if same_view => {
+            process_selected_artist_tracks(app, &tracks);
+            app.selected_artist_tracks.select(0);
+            app.navigate(View::SearchedArtist);
+        }
+        (Task::SearchedPlaylistTracks, TaskData::PlaylistItems(items)) if same_view => {
+            process_selected_playlist_tracks(app, &items);
+            app.selected_playlist_tracks.select(0);
+            app.navigate(View::SearchedPlaylist);
+        }
+        (Task::Account, TaskData::Account(account)) => app.account = Some(*account),
+
+        _ => {}
+    }
+}
@@ -1,8 +1,7 @@
-use ratatui::widgets::{ListState, TableState};
-
 use crate::{
     app::App,
-    enums::{InputMode, SearchMenu},
+    enums::{InputMode, Library, Severity, Task, View},
+    models::Track,
 };
 
 // Helper functions for cursor movement and character deletion
@@ -41,126 +40,40 @@ pub fn reset_cursor(app: &mut App) {
     app.cursor_position = 0;
 }
 
-pub fn down_key_for_table(names: Vec<String>, mut state: TableState) -> (TableState, usize) {
-    let length: usize = names.len();
-    let next_index: usize = state.selected().unwrap_or(0) + 1;
-    state.select(Some(next_index % length));
-
-    (state, next_index)
-}
-
-pub fn down_key_for_list(names: Vec<String>, mut state: ListState) -> (ListState, usize) {
-    let length: usize = names.len();
-    let next_index: usize = state.selected().unwrap_or(0) + 1;
-    state.select(Some(next_index % length));
-
-    (state, next_index)
-}
-
-pub fn up_key_for_table(names: Vec<String>, mut state: TableState) -> (TableState, usize) {
-    let length: usize = names.len();
-    let prev_index: usize = if state.selected().unwrap_or(0) == 0 {
-        length - 1
-    } else {
-        state.selected().unwrap_or(0) - 1
-    };
-    state.select(Some(prev_index));
-
-    (state, prev_index)
-}
-
-pub fn up_key_for_list(names: Vec<String>, mut state: ListState) -> (ListState, usize) {
-    let length: usize = names.len();
-    let prev_index: usize = if state.selected().unwrap_or(length) == 0 {
-        length - 1
-    } else {
-        state.selected().unwrap_or(length) - 1
-    };
-    state.select(Some(prev_index));
-
-    (state, prev_index)
-}
-
 pub fn default(app: &mut App) {
-    app.search_results_rendered = false;
     app.input_mode = InputMode::Normal;
-    app.user_playlist_display = false;
-    app.liked_song_display = false;
-    app.selected_search = false;
-    app.user_album_display = false;
-    app.recently_played_display = false;
     app.can_navigate_menu = true;
-    app.podcast_display = false;
-    app.user_artist_display = false;
-    app.searched_album_selected = false;
-    app.searched_artist_selected = false;
-    app.searched_playlist_selected = false;
-    app.made_fy_display = false;
-    app.made_fy_track_display = false;
-    app.made_fy_track_selected = false;
-    app.made_fy_current_playlist_selected = false;
-    app.user_album_current_album_selected = false;
-    app.user_album_track_selected = false;
-    app.user_album_track_display = false;
-    app.user_artist_current_artist_selected = false;
-    app.user_artist_track_selected = false;
-    app.user_artist_track_display = false;
-    app.enter_for_playback_in_made_fy = false;
-    app.enter_for_playback_in_liked_song = false;
-    app.enter_for_playback_in_user_album = false;
-    app.enter_for_playback_in_recently_played = false;
-    app.enter_for_playback_in_saved_artist = false;
-    app.enter_for_playback_in_user_playlist = false;
-    app.enter_for_playback_in_new_release = false;
-    app.is_only_id = false;
-    app.selected_link_for_playback.clear();
+    app.selected_track_id.clear();
     app.is_in_track = false;
-    app.search_menu = SearchMenu::Default;
 }
 
-pub fn default_nav(app: &mut App) {
-    app.search_results_rendered = false;
-    app.liked_song_display = false;
-    app.user_album_display = false;
-    app.recently_played_display = false;
-    app.podcast_display = false;
-    app.user_artist_display = false;
-    app.made_fy_display = false;
-    app.made_fy_track_display = false;
-    app.made_fy_track_selected = false;
-    app.user_album_current_album_selected = false;
-    app.user_album_track_selected = false;
-    app.user_album_track_display = false;
-    app.user_artist_current_artist_selected = false;
-    app.user_artist_track_selected = false;
-    app.user_artist_track_display = false;
-    app.enter_for_playback_in_made_fy = false;
-    app.enter_for_playback_in_liked_song = false;
-    app.enter_for_playback_in_user_album = false;
-    app.enter_for_playback_in_recently_played = false;
-    app.enter_for_playback_in_saved_artist = false;
-    app.enter_for_playback_in_user_playlist = false;
-    app.enter_for_playback_in_new_release = false;
-    app.is_only_id = false;
-    app.selected_link_for_playback.clear();
-    app.is_in_track = false;
+/// Returns the track highlighted in the current view, if that view lists tracks
+pub fn selected_track(app: &App) -> Option<&Track> {
+    match app.navigation.current() {
+        View::LibrarySection(Library::RecentlyPlayed) => app.recently_played.selected(),
+        View::LibrarySection(Library::LikedSongs) => app.liked_songs.selected(),
+        View::LibraryTracks(Library::MadeFY) => app.made_fy_tracks.selected(),
+        View::LibraryTracks(Library::Albums) => app.user_album_tracks.selected(),
+        View::LibraryTracks(Library::Artists) => app.user_artist_tracks.selected(),
+        View::PlaylistTracks => app.user_playlist_tracks.selected(),
+        View::NewReleaseTracks => app.new_release_tracks.selected(),
+        View::Search if app.is_in_track => app.searched_tracks.selected(),
+        View::SearchedAlbum => app.selected_album_tracks.selected(),
+        View::SearchedArtist => app.selected_artist_tracks.selected(),
+        View::SearchedPlaylist => app.selected_playlist_tracks.selected(),
+        _ => None,
+    }
 }
 
-pub fn default_search(app: &mut App) {
-    app.search_results_rendered = false;
-    app.liked_song_display = false;
-    app.user_album_display = false;
-    app.recently_played_display = false;
-    app.can_navigate_menu = true;
-    app.podcast_display = false;
-    app.user_artist_display = false;
-    app.searched_album_selected = false;
-    app.searched_artist_selected = false;
-    app.searched_album_selected = false;
-    app.searched_playlist_selected = false;
-    app.selected_search = false;
-    app.is_only_id = false;
-    app.selected_link_for_playback.clear();
-    app.is_in_track = false;
-    app.search_menu = SearchMenu::Default;
+/// Starts playback of the track highlighted in the current view
+pub fn play_selected_track(app: &mut App) {
+    if let Some(track) = selected_track(app) {
+        if track.unplayable {
+            let message = format!("{} isn't available in your country", track.name);
+            app.notify(Severity::Warning, message);
+            return;
+        }
+        app.selected_track_id = track.id.clone();
+        app.dispatch(Task::StartPlayback);
+    }
 }
@@ -1,17 +1,27 @@
+#![allow(clippy::module_inception)]
+
+use std::env;
 use std::io;
-use std::sync::mpsc;
-use std::thread;
+use std::process;
 
-use settings::creds::{read_creds, set_creds};
+use cli::{parse_args, Command, USAGE};
+use command::run_command;
+use settings::migrate::import_legacy_config;
+use settings::paths::{cache_dir, config_dir, profile_name, DEFAULT_PROFILE};
+use settings::profile::load_profile;
 use structs::{Key, Settings, Themes};
-use ui::tui;
-use util::{instruction, save_creds_to_yml, startup, update_player_info};
 
 use crate::app::App;
+use crate::enums::Severity;
+use crate::spotify::backend::fake_backend_requested;
 
 mod app;
+mod cli;
+mod command;
 mod enums;
+mod event_log;
 mod handlers;
+mod models;
 mod settings;
 mod spotify;
 mod structs;
@@ -24,40 +34,81 @@ fn main() -> io::Result<()> {
     let mut theme: Themes = Themes::default();
     let mut settings: Settings = Settings::default();
 
-    app.file_name = "spoify-0.2.12".to_string(); //-0.2.12
-
-    // Set the creds from the configure files
-    read_creds(&mut app);
-    set_creds(&mut app);
-
-    if app.client_id == "" {
-        instruction();
-        save_creds_to_yml(&mut app);
-    } else {
-        // Fetch user's playlists, new releases, set keybinds and themes before the main app starts
-        startup(&mut app, &mut key, &mut theme, &mut settings);
-
-        let mut terminal = tui::init()?;
-
-        let (tx1, rx1) = mpsc::channel();
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    };
+    if args.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    app.root_config_dir = config_dir(args.config_dir);
+    app.root_cache_dir = cache_dir(args.cache_dir);
+    // Every profile writes to the same log
+    app.log.set_file_dir(&app.root_cache_dir);
 
-        let mut player_info_app: App = app.clone();
-        let mut player_info_settings: Settings = settings.clone();
+    // Bring over the files of a release that kept them in a `spoify-<version>` folder
+    if let Some(legacy_dir) = import_legacy_config(&app.root_config_dir, &app.root_cache_dir)? {
+        let message = format!(
+            "Imported the configuration from {} into {}",
+            legacy_dir.display(),
+            app.root_config_dir.display()
+        );
+        println!("{}", message);
+        app.log.record(Severity::Info, message);
+    }
 
-        // Spawn a new thread to update player's current playback
-        let player_info_thread = thread::spawn(move || {
-            update_player_info(tx1, &mut player_info_app, &mut player_info_settings)
-        });
+    let profile = profile_name(args.profile);
+    match load_profile(&mut app, &profile) {
+        Ok(warnings) => {
+            for warning in warnings {
+                if args.command.is_some() {
+                    eprintln!("{}", warning);
+                }
+                app.notify(Severity::Warning, warning);
+            }
+        }
+        Err(e) => {
+            app.log.record(Severity::Error, e.to_string());
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
 
-        // Run the main app loop
-        app.run(&mut terminal, rx1, &mut key, &mut theme, &mut settings)?;
+    // The in-memory backend doesn't talk to Spotify, so it doesn't need any credentials
+    let needs_creds = app.client_id.is_empty() && !fake_backend_requested();
 
-        // Wait for the spawned threads to complete
-        if let Err(e) = player_info_thread.join() {
-            eprintln!("Error in player_info_thread: {:?}", e);
+    if let Some(command) = args.command {
+        // Commands are meant for scripts and hotkeys, so they never prompt for the credentials, logging out doesn't use them
+        if needs_creds && !matches!(command, Command::Logout) {
+            if profile == DEFAULT_PROFILE {
+                eprintln!(
+                    "No Spotify credentials yet, run spoify once without a command to set them up"
+                );
+            } else {
+                eprintln!(
+                    "No Spotify credentials for the '{}' profile yet, run 'spoify --profile {}' once without a command to set them up",
+                    profile, profile
+                );
+            }
+            process::exit(1);
         }
-
-        tui::restore()?;
+        if let Err(e) = run_command(&mut app, &mut settings, command.clone()) {
+            app.log
+                .record(Severity::Error, format!("{:?} failed: {}", command, e));
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    } else {
+        // Run the main app loop, it starts with the setup screen when there are no credentials yet
+        app.log.record(
+            Severity::Info,
+            format!("Started spoify {}", env!("CARGO_PKG_VERSION")),
+        );
+        app.run(&mut key, &mut theme, &mut settings)?;
     }
 
     Ok(())
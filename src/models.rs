@@ -0,0 +1,216 @@
+// The Spotify objects as the app uses them, every section and table works with these types
+use crate::spotify::util::spotify_url;
+use rspotify::model::{
+    FullAlbum, FullArtist, FullTrack, SimplifiedAlbum, SimplifiedArtist, SimplifiedPlaylist,
+    SimplifiedShow, SimplifiedTrack,
+};
+use rspotify::prelude::Id;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Artist {
+    pub id: String,
+    pub uri: String,
+    pub name: String,
+    pub link: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Album {
+    pub id: String,
+    pub uri: String,
+    pub name: String,
+    pub artists: Vec<Artist>,
+    pub release_date: String,
+    // Spotify only sends the number of tracks with the full album
+    pub total_tracks: u32,
+    pub link: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Track {
+    pub id: String,
+    pub uri: String,
+    pub name: String,
+    pub artists: Vec<Artist>,
+    // Tracks listed inside an album don't carry the album
+    pub album: Option<Album>,
+    pub duration_ms: i64,
+    pub explicit: bool,
+    // Spotify only says whether a track plays in the user's country when the request names a market
+    pub unplayable: bool,
+    pub link: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Playlist {
+    pub id: String,
+    pub uri: String,
+    pub name: String,
+    pub owner: String,
+    pub total_tracks: u32,
+    pub link: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Show {
+    pub id: String,
+    pub uri: String,
+    pub name: String,
+    pub publisher: String,
+    pub link: String,
+}
+
+impl Track {
+    /// Names of all the artists of the track, separated by commas
+    pub fn artist_names(&self) -> String {
+        artist_names(&self.artists)
+    }
+
+    /// Name of the album of the track, or an empty string if it isn't known
+    pub fn album_name(&self) -> &str {
+        self.album
+            .as_ref()
+            .map(|album| album.name.as_str())
+            .unwrap_or_default()
+    }
+}
+
+impl Album {
+    /// Names of all the artists of the album, separated by commas
+    pub fn artist_names(&self) -> String {
+        artist_names(&self.artists)
+    }
+}
+
+fn artist_names(artists: &[Artist]) -> String {
+    artists
+        .iter()
+        .map(|artist| artist.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Returns the bare id and the spotify: uri, objects Spotify doesn't know (local files) have neither
+fn id_and_uri<T: Id>(id: Option<&T>) -> (String, String) {
+    id.map(|id| (id.id().to_string(), id.uri()))
+        .unwrap_or_default()
+}
+
+impl From<&SimplifiedArtist> for Artist {
+    fn from(artist: &SimplifiedArtist) -> Self {
+        let (id, uri) = id_and_uri(artist.id.as_ref());
+        Self {
+            id,
+            uri,
+            name: artist.name.clone(),
+            link: spotify_url(&artist.external_urls),
+        }
+    }
+}
+
+impl From<&FullArtist> for Artist {
+    fn from(artist: &FullArtist) -> Self {
+        let (id, uri) = id_and_uri(Some(&artist.id));
+        Self {
+            id,
+            uri,
+            name: artist.name.clone(),
+            link: spotify_url(&artist.external_urls),
+        }
+    }
+}
+
+impl From<&SimplifiedAlbum> for Album {
+    fn from(album: &SimplifiedAlbum) -> Self {
+        let (id, uri) = id_and_uri(album.id.as_ref());
+        Self {
+            id,
+            uri,
+            name: album.name.clone(),
+            artists: album.artists.iter().map(Artist::from).collect(),
+            release_date: album.release_date.clone().unwrap_or_default(),
+            total_tracks: 0,
+            link: spotify_url(&album.external_urls),
+        }
+    }
+}
+
+impl From<&FullAlbum> for Album {
+    fn from(album: &FullAlbum) -> Self {
+        let (id, uri) = id_and_uri(Some(&album.id));
+        Self {
+            id,
+            uri,
+            name: album.name.clone(),
+            artists: album.artists.iter().map(Artist::from).collect(),
+            release_date: album.release_date.clone(),
+            total_tracks: album.tracks.total,
+            link: spotify_url(&album.external_urls),
+        }
+    }
+}
+
+impl From<&FullTrack> for Track {
+    fn from(track: &FullTrack) -> Self {
+        let (id, uri) = id_and_uri(track.id.as_ref());
+        Self {
+            id,
+            uri,
+            name: track.name.clone(),
+            artists: track.artists.iter().map(Artist::from).collect(),
+            album: Some(Album::from(&track.album)),
+            duration_ms: track.duration.num_milliseconds(),
+            explicit: track.explicit,
+            unplayable: track.is_playable == Some(false),
+            link: spotify_url(&track.external_urls),
+        }
+    }
+}
+
+impl From<&SimplifiedTrack> for Track {
+    fn from(track: &SimplifiedTrack) -> Self {
+        let (id, uri) = id_and_uri(track.id.as_ref());
+        Self {
+            id,
+            uri,
+            name: track.name.clone(),
+            artists: track.artists.iter().map(Artist::from).collect(),
+            album: track.album.as_ref().map(Album::from),
+            duration_ms: track.duration.num_milliseconds(),
+            explicit: track.explicit,
+            unplayable: track.is_playable == Some(false),
+            link: spotify_url(&track.external_urls),
+        }
+    }
+}
+
+impl From<&SimplifiedPlaylist> for Playlist {
+    fn from(playlist: &SimplifiedPlaylist) -> Self {
+        let (id, uri) = id_and_uri(Some(&playlist.id));
+        Self {
+            id,
+            uri,
+            name: playlist.name.clone(),
+            owner: playlist
+                .owner
+                .display_name
+                .clone()
+                .unwrap_or_else(|| playlist.owner.id.id().to_string()),
+            total_tracks: playlist.tracks.total,
+            link: spotify_url(&playlist.external_urls),
+        }
+    }
+}
+
+impl From<&SimplifiedShow> for Show {
+    fn from(show: &SimplifiedShow) -> Self {
+        let (id, uri) = id_and_uri(Some(&show.id));
+        Self {
+            id,
+            uri,
+            name: show.name.clone(),
+            publisher: show.publisher.clone(),
+            link: spotify_url(&show.external_urls),
+        }
+    }
+}
@@ -1,36 +1,108 @@
+use crossterm::terminal;
 use serde::Deserialize;
-use serde_yaml;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::env;
+use std::io;
+use std::process::{Command, Stdio};
 
 use crate::app::App;
+use crate::enums::AuthFlow;
+use crate::settings::paths::read_yaml;
+use crate::settings::permissions::write_private;
+
+/// Environment variable taking the place of the Client ID in `creds.yml`
+pub const CLIENT_ID_VAR: &str = "SPOIFY_CLIENT_ID";
+/// Environment variable taking the place of the Client Secret in `creds.yml`
+pub const CLIENT_SECRET_VAR: &str = "SPOIFY_CLIENT_SECRET";
 
 #[derive(Deserialize, Debug)]
 struct Creds(HashMap<String, String>);
 
-pub fn read_creds(app: &mut App) -> HashMap<String, String> {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(".."); // Move up to the root of the Git repository
-    path.push(app.file_name.clone());
-    path.push("configure");
-    path.push("creds.yml");
-
-    let file = File::open(&path).expect("Unable to open creds file");
-    let reader = BufReader::new(file);
-    let Creds(creds) = serde_yaml::from_reader(reader).expect("Unable to parse creds from YAML");
-    creds
+/// Reads the credentials file of the app's configuration directory
+pub fn read_creds(app: &App) -> io::Result<HashMap<String, String>> {
+    let Creds(creds) = read_yaml(&app.config_dir.join("creds.yml"))?;
+    Ok(creds)
 }
 
-pub fn set_creds(app: &mut App) {
-    let creds = read_creds(app);
+pub fn set_creds(app: &mut App) -> io::Result<()> {
+    let creds = read_creds(app)?;
+
+    app.client_id = credential(&creds, "Client ID", CLIENT_ID_VAR)?;
+    app.client_secret = credential(&creds, "Client Secret", CLIENT_SECRET_VAR)?;
+
+    // Without an `Auth Flow` the login that works with the stored creds is used
+    app.auth_flow = match creds.get("Auth Flow").map(|flow| flow.to_lowercase()) {
+        Some(flow) if flow == "pkce" => AuthFlow::Pkce,
+        Some(flow) if flow == "client secret" => AuthFlow::ClientSecret,
+        _ if app.client_secret.is_empty() => AuthFlow::Pkce,
+        _ => AuthFlow::ClientSecret,
+    };
+    Ok(())
+}
+
+/// Writes the app's credentials to `creds.yml`, a PKCE login keeps no secret on disk
+pub fn write_creds(app: &App) -> io::Result<()> {
+    let yaml_content = match app.auth_flow {
+        AuthFlow::Pkce => format!("Client ID: \"{}\"\nAuth Flow: \"pkce\"", app.client_id),
+        AuthFlow::ClientSecret => format!(
+            "Client ID: \"{}\"\nClient Secret: \"{}\"\nAuth Flow: \"client secret\"",
+            app.client_id, app.client_secret
+        ),
+    };
+
+    // Only the user may read the file, it can hold the secret
+    write_private(&app.config_dir.join("creds.yml"), &yaml_content)
+}
 
-    if let Some(value_str) = creds.get("Client ID") {
-        app.client_id = value_str.as_str().to_string();
+// The environment variable wins over `<key> Command`, which wins over the value written in the file
+fn credential(creds: &HashMap<String, String>, key: &str, var: &str) -> io::Result<String> {
+    if let Some(value) = env::var(var).ok().filter(|value| !value.is_empty()) {
+        return Ok(value);
     }
 
-    if let Some(value_str) = creds.get("Client Secret") {
-        app.client_secret = value_str.as_str().to_string();
+    let command = creds
+        .get(&format!("{} Command", key))
+        .filter(|command| !command.trim().is_empty());
+    if let Some(command) = command {
+        // The command may ask for a passphrase, which it can't do while the interface owns the terminal
+        if terminal::is_raw_mode_enabled().unwrap_or(false) {
+            return Err(io::Error::other(format!(
+                "{} Command only runs when spoify starts, start spoify with this profile instead",
+                key
+            )));
+        }
+        return run_command(command)
+            .map_err(|e| io::Error::new(e.kind(), format!("{} Command failed ({})", key, e)));
     }
+
+    Ok(creds.get(key).cloned().unwrap_or_default())
+}
+
+// Runs the command through the shell and keeps the first line it prints, so `pass show <entry>` works as it is
+fn run_command(command: &str) -> io::Result<String> {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    // A password manager may ask for its passphrase, so the command gets the terminal
+    let output = shell
+        .arg(command)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(output.status.to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout.lines().next().unwrap_or_default().trim().to_string())
 }
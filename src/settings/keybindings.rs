@@ -1,35 +1,34 @@
 use serde::Deserialize;
-use serde_yaml;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io;
 use yaml_rust::YamlLoader;
 
 use crate::app::App;
+use crate::enums::Severity;
+use crate::settings::paths::read_yaml;
 use crate::structs::Key;
 
 #[derive(Deserialize, Debug)]
 struct Keybindings(HashMap<String, String>);
 
 /// Reads the keybindings from the configuration file and returns them as a HashMap
-pub fn read_keybindings(app: &mut App) -> HashMap<String, String> {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(".."); // Move up to the root of the Git repository
-    path.push(app.file_name.clone());
-    path.push("configure");
-    path.push("keybindings.yml");
-
-    let file = File::open(&path).expect("Unable to open keybindings file");
-    let reader = BufReader::new(file);
-    let Keybindings(keybindings) =
-        serde_yaml::from_reader(reader).expect("Unable to parse keybindings from YAML");
-    keybindings
+pub fn read_keybindings(app: &App) -> io::Result<HashMap<String, String>> {
+    let Keybindings(keybindings) = read_yaml(&app.config_dir.join("keybindings.yml"))?;
+    Ok(keybindings)
 }
 
 /// Sets the keybindings in the App struct based on the loaded configuration
 pub fn set_keybindings(app: &mut App, key: &mut Key) {
-    let keybindings = read_keybindings(app);
+    let keybindings = match read_keybindings(app) {
+        Ok(keybindings) => keybindings,
+        Err(e) => {
+            app.notify(
+                Severity::Error,
+                format!("Couldn't load the keybindings: {}", e),
+            );
+            return;
+        }
+    };
 
     key.go_to_search_key = match keybindings.get("Go to Search") {
         Some(s) => s.chars().next().unwrap_or(' '),
@@ -78,7 +77,7 @@ pub fn set_keybindings(app: &mut App, key: &mut Key) {
         Some(s) => s.chars().next().unwrap_or(' '),
         None => ' ',
     };
-    key.error_key = match keybindings.get("Error Screen") {
+    key.error_key = match keybindings.get("Log Screen") {
         Some(s) => s.chars().next().unwrap_or(' '),
         None => ' ',
     };
@@ -98,29 +97,44 @@ pub fn set_keybindings(app: &mut App, key: &mut Key) {
         Some(s) => s.chars().next().unwrap_or(' '),
         None => ' ',
     };
+    key.profiles_key = match keybindings.get("Switch Profile") {
+        Some(s) => s.chars().next().unwrap_or(' '),
+        None => ' ',
+    };
+    key.account_key = match keybindings.get("Account") {
+        Some(s) => s.chars().next().unwrap_or(' '),
+        None => ' ',
+    };
+    key.saved_searches_key = match keybindings.get("Saved Searches") {
+        Some(s) => s.chars().next().unwrap_or(' '),
+        None => ' ',
+    };
 }
 
 /// Parses the keybindings from the configuration file and populates the tasks and first_keys fields in the App struct
 pub fn parse_keybindings(app: &mut App, keys: &mut Key) {
     // Construct the paths relative to the root directory
-    let mut yaml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    yaml_path.push("..");
-    yaml_path.push(app.file_name.clone());
-    yaml_path.push("configure");
+    let mut yaml_path = app.config_dir.clone();
     yaml_path.push("keybindings.yml");
 
-    // Load the YAML file
-    let yaml_data = std::fs::read_to_string(&yaml_path).expect("Failed to read YAML file");
-
-    let docs = YamlLoader::load_from_str(yaml_data.as_str()).expect("Failed to load YAML data");
-    let doc = &docs[0];
+    // Load the YAML file, set_keybindings already reported a file that can't be read
+    let Ok(yaml_data) = std::fs::read_to_string(&yaml_path) else {
+        return;
+    };
+    let Ok(docs) = YamlLoader::load_from_str(yaml_data.as_str()) else {
+        return;
+    };
+    let Some(doc) = docs.first().and_then(|doc| doc.as_hash()) else {
+        return;
+    };
 
     // Iterate over the YAML data
-    for (key, value) in doc.as_hash().unwrap() {
-        let task = key.as_str().unwrap().to_string();
-        let key_binding = value.as_str().unwrap().to_string();
+    for (key, value) in doc {
+        let (Some(task), Some(key_binding)) = (key.as_str(), value.as_str()) else {
+            continue;
+        };
 
-        keys.tasks.push(task);
-        keys.first_keys.push(key_binding);
+        keys.tasks.push(task.to_string());
+        keys.first_keys.push(key_binding.to_string());
     }
 }
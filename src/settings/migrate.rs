@@ -0,0 +1,394 @@
+// Upgrades configuration written by older releases to the current layout and schema
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::settings::paths::default_file;
+use crate::settings::permissions::restrict;
+
+/// Version of the configuration format written by this release
+pub const CONFIG_VERSION: u32 = 9;
+
+const VERSION_KEY: &str = "Config Version";
+
+// Files in the configuration directory that aren't themes
+const NON_THEME_FILES: [&str; 6] = [
+    "creds.yml",
+    "settings.yml",
+    "keybindings.yml",
+    "search_filters.yml",
+    "search_history.yml",
+    "saved_searches.yml",
+];
+
+// Keybindings renamed in version 2, as (old name, new name)
+const RENAMED_KEYBINDINGS_V2: [(&str, &str); 1] =
+    [("Exit Search menu or Help menu", "Go to main screen")];
+// Keybindings renamed in version 3
+const RENAMED_KEYBINDINGS_V3: [(&str, &str); 1] = [("Error Screen", "Log Screen")];
+
+// Each step upgrades the folder from version `index + 1` to the next one. Only the versions that
+// changed existing keys have a step, the ones that just added keys are handled by `add_new_keys`
+const STEPS: [fn(&Path) -> io::Result<()>; 2] = [upgrade_to_v2, upgrade_to_v3];
+
+/// Copies the configuration and token cache of the newest `spoify-<version>` folder when the configuration directory is still empty
+pub fn import_legacy_config(config_dir: &Path, cache_dir: &Path) -> io::Result<Option<PathBuf>> {
+    if config_dir.join("settings.yml").exists() {
+        return Ok(None);
+    }
+    let Some(legacy_dir) = find_legacy_dir() else {
+        return Ok(None);
+    };
+
+    fs::create_dir_all(config_dir)?;
+    for entry in fs::read_dir(legacy_dir.join("configure"))? {
+        let entry = entry?;
+        let target = config_dir.join(entry.file_name());
+        if entry.file_type()?.is_file() && !target.exists() {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    // The old releases left the secrets readable by everyone
+    restrict(&config_dir.join("creds.yml"))?;
+
+    let token_cache = legacy_dir
+        .join("spotify_cache")
+        .join(".spotify_token_cache.json");
+    let target = cache_dir.join(".spotify_token_cache.json");
+    if token_cache.is_file() && !target.exists() {
+        fs::create_dir_all(cache_dir)?;
+        fs::copy(token_cache, &target)?;
+        restrict(&target)?;
+    }
+
+    Ok(Some(legacy_dir))
+}
+
+/// Upgrades the files in the configuration directory to `CONFIG_VERSION`
+pub fn migrate_config(config_dir: &Path) -> io::Result<()> {
+    let version = read_version(config_dir)?;
+    if version >= CONFIG_VERSION {
+        return Ok(());
+    }
+
+    for step in STEPS.iter().skip(version.saturating_sub(1) as usize) {
+        step(config_dir)?;
+    }
+    // Every upgrade can bring new keys. They are only added once the renames are done, so a renamed key is never added twice
+    add_new_keys(config_dir)?;
+    write_version(config_dir)
+}
+
+// Releases up to 0.2.12 kept their files in `spoify-<version>/configure` next to the crate sources
+fn find_legacy_dir() -> Option<PathBuf> {
+    newest_release_dir(Path::new(env!("CARGO_MANIFEST_DIR")).parent()?)
+}
+
+// The `spoify-<version>` folder of `parent` with the highest release that has a `configure` folder
+fn newest_release_dir(parent: &Path) -> Option<PathBuf> {
+    fs::read_dir(parent)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let version = parse_release(name.strip_prefix("spoify-")?)?;
+            let path = entry.path();
+            path.join("configure").is_dir().then_some((version, path))
+        })
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, path)| path)
+}
+
+fn parse_release(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+    let release = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(release)
+}
+
+// Folders written before the version key existed are version 1
+fn read_version(config_dir: &Path) -> io::Result<u32> {
+    let contents = fs::read_to_string(config_dir.join("settings.yml"))?;
+    Ok(contents
+        .lines()
+        .find(|line| key_of(line) == Some(VERSION_KEY))
+        .and_then(|line| line.split_once(':'))
+        .and_then(|(_, value)| value.trim().trim_matches('"').parse().ok())
+        .unwrap_or(1))
+}
+
+fn write_version(config_dir: &Path) -> io::Result<()> {
+    let path = config_dir.join("settings.yml");
+    let contents = fs::read_to_string(&path)?;
+    let version_line = format!("{}: \"{}\"", VERSION_KEY, CONFIG_VERSION);
+
+    let mut lines: Vec<String> = contents
+        .lines()
+        .filter(|line| key_of(line) != Some(VERSION_KEY))
+        .map(str::to_string)
+        .collect();
+    lines.insert(0, version_line);
+    fs::write(path, lines.join("\n") + "\n")
+}
+
+fn upgrade_to_v2(config_dir: &Path) -> io::Result<()> {
+    update_file(config_dir, "keybindings.yml", |contents| {
+        rename_keys(contents, &RENAMED_KEYBINDINGS_V2)
+    })
+}
+
+// The error screen became the log screen
+fn upgrade_to_v3(config_dir: &Path) -> io::Result<()> {
+    update_file(config_dir, "keybindings.yml", |contents| {
+        rename_keys(contents, &RENAMED_KEYBINDINGS_V3)
+    })
+}
+
+// Appends the keybindings, settings and theme colors the older releases didn't have, with their default values
+fn add_new_keys(config_dir: &Path) -> io::Result<()> {
+    for name in ["keybindings.yml", "settings.yml"] {
+        if let Some(defaults) = default_file(name) {
+            update_file(config_dir, name, |contents| {
+                add_missing_keys(contents, defaults)
+            })?;
+        }
+    }
+
+    let Some(theme_defaults) = default_file("default.yml") else {
+        return Ok(());
+    };
+    for entry in fs::read_dir(config_dir)? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        if name.ends_with(".yml") && !NON_THEME_FILES.contains(&name.as_str()) {
+            update_file(config_dir, &name, |contents| {
+                add_missing_keys(contents, theme_defaults)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+// Rewrites a file of the configuration directory, a missing file is left alone
+fn update_file(config_dir: &Path, name: &str, edit: impl Fn(&str) -> String) -> io::Result<()> {
+    let path = config_dir.join(name);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    fs::write(path, edit(&contents))
+}
+
+fn rename_keys(contents: &str, renames: &[(&str, &str)]) -> String {
+    contents
+        .lines()
+        .map(|line| {
+            let old = key_of(line).and_then(|key| renames.iter().find(|(old, _)| *old == key));
+            match old {
+                Some((old, new)) => line.replacen(old, new, 1),
+                None => line.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+fn add_missing_keys(contents: &str, defaults: &str) -> String {
+    let present: Vec<&str> = contents.lines().filter_map(key_of).collect();
+    let missing: Vec<&str> = defaults
+        .lines()
+        .filter(|line| {
+            key_of(line).is_some_and(|key| key != VERSION_KEY && !present.contains(&key))
+        })
+        .collect();
+
+    let mut contents = contents.to_string();
+    if !missing.is_empty() {
+        if !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        contents.push_str(&missing.join("\n"));
+        contents.push('\n');
+    }
+    contents
+}
+
+fn key_of(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    line.split_once(':').map(|(key, _)| key.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A folder of the system temp directory holding the given files, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let dir = std::env::temp_dir().join(format!("spoify-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            for (file, contents) in files {
+                fs::write(dir.join(file), contents).unwrap();
+            }
+            TempDir(dir)
+        }
+
+        fn read(&self, file: &str) -> String {
+            fs::read_to_string(self.0.join(file)).unwrap()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    // The files a 0.2.x release wrote, before the version key existed
+    fn v1_layout(name: &str) -> TempDir {
+        TempDir::new(
+            name,
+            &[
+                (
+                    "settings.yml",
+                    "Volume Increament Value: \"10\"\nTheme: \"default\"\n",
+                ),
+                (
+                    "keybindings.yml",
+                    "Exit Search menu or Help menu: \"Esc\"\nError Screen: \"x\"\n",
+                ),
+                ("default.yml", "Border Color: \"Red\"\n"),
+            ],
+        )
+    }
+
+    fn count_keys(contents: &str, key: &str) -> usize {
+        contents
+            .lines()
+            .filter(|line| key_of(line) == Some(key))
+            .count()
+    }
+
+    #[test]
+    fn a_v1_folder_gets_the_renamed_keybindings() {
+        let dir = v1_layout("renames");
+        migrate_config(&dir.0).unwrap();
+
+        let keybindings = dir.read("keybindings.yml");
+        assert!(keybindings.contains("Go to main screen: \"Esc\""));
+        assert!(keybindings.contains("Log Screen: \"x\""));
+        assert_eq!(count_keys(&keybindings, "Exit Search menu or Help menu"), 0);
+        assert_eq!(count_keys(&keybindings, "Error Screen"), 0);
+    }
+
+    #[test]
+    fn keys_are_added_once_and_the_user_values_are_kept() {
+        let dir = v1_layout("missing-keys");
+        migrate_config(&dir.0).unwrap();
+
+        let keybindings = dir.read("keybindings.yml");
+        let defaults = default_file("keybindings.yml").unwrap();
+        for key in defaults.lines().filter_map(key_of) {
+            assert_eq!(
+                count_keys(&keybindings, key),
+                1,
+                "{} in keybindings.yml",
+                key
+            );
+        }
+        // The renamed keys keep the user's value instead of getting the default a second time
+        assert!(!keybindings.contains("Log Screen: \"e\""));
+
+        let settings = dir.read("settings.yml");
+        assert!(settings.contains("Volume Increament Value: \"10\""));
+        assert_eq!(count_keys(&settings, "Volume Increament Value"), 1);
+        assert_eq!(count_keys(&dir.read("default.yml"), "Border Color"), 1);
+    }
+
+    #[test]
+    fn a_version_without_renames_still_gets_the_new_keys() {
+        let dir = TempDir::new(
+            "v4",
+            &[
+                (
+                    "settings.yml",
+                    "Config Version: \"4\"\nTheme: \"default\"\n",
+                ),
+                ("keybindings.yml", "Log Screen: \"x\"\n"),
+            ],
+        );
+        migrate_config(&dir.0).unwrap();
+
+        let keybindings = dir.read("keybindings.yml");
+        assert_eq!(count_keys(&keybindings, "Saved Searches"), 1);
+        assert!(keybindings.contains("Log Screen: \"x\""));
+        assert_eq!(
+            count_keys(&dir.read("settings.yml"), "Search As You Type"),
+            1
+        );
+        assert_eq!(read_version(&dir.0).unwrap(), CONFIG_VERSION);
+    }
+
+    #[test]
+    fn add_missing_keys_leaves_a_complete_file_alone() {
+        let defaults = "# comment\nShuffle: \"Ctrl+S\"\nRepeat: \"Ctrl+R\"\n";
+        let contents = "Repeat: \"r\"\nShuffle: \"s\"\n";
+        assert_eq!(add_missing_keys(contents, defaults), contents);
+        assert_eq!(
+            add_missing_keys("Repeat: \"r\"", defaults),
+            "Repeat: \"r\"\nShuffle: \"Ctrl+S\"\n"
+        );
+    }
+
+    #[test]
+    fn the_version_line_is_written_once() {
+        let dir = v1_layout("version");
+        migrate_config(&dir.0).unwrap();
+        // A second run finds the folder up to date and leaves it as it is
+        let migrated = dir.read("settings.yml");
+        migrate_config(&dir.0).unwrap();
+        assert_eq!(dir.read("settings.yml"), migrated);
+
+        assert_eq!(count_keys(&migrated, VERSION_KEY), 1);
+        assert_eq!(
+            migrated.lines().next(),
+            Some(format!("{}: \"{}\"", VERSION_KEY, CONFIG_VERSION).as_str())
+        );
+        assert_eq!(read_version(&dir.0).unwrap(), CONFIG_VERSION);
+    }
+
+    #[test]
+    fn releases_are_compared_by_number() {
+        assert_eq!(parse_release("0.2.12"), Some((0, 2, 12)));
+        assert!(parse_release("0.2.12") > parse_release("0.2.9"));
+        assert!(parse_release("1.0.0") > parse_release("0.10.3"));
+        assert_eq!(parse_release("0.2"), None);
+        assert_eq!(parse_release("0.2.1.4"), None);
+        assert_eq!(parse_release("0.2.x"), None);
+    }
+
+    #[test]
+    fn the_newest_release_with_a_configure_folder_is_imported() {
+        let parent = TempDir::new("legacy", &[]);
+        for name in [
+            "spoify-0.2.9",
+            "spoify-0.2.12",
+            "spoify-0.10.0",
+            "spoify-beta",
+        ] {
+            fs::create_dir_all(parent.0.join(name).join("configure")).unwrap();
+        }
+        // The newest release has no configuration to import
+        fs::create_dir_all(parent.0.join("spoify-1.0.0")).unwrap();
+
+        assert_eq!(
+            newest_release_dir(&parent.0),
+            Some(parent.0.join("spoify-0.10.0"))
+        );
+    }
+}
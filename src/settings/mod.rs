@@ -1,7 +1,11 @@
 pub mod change_keybinding;
 pub mod creds;
 pub mod keybindings;
+pub mod migrate;
 pub mod open_configure;
+pub mod paths;
+pub mod permissions;
+pub mod profile;
 pub mod refresh;
 pub mod settings;
 pub mod theme;
@@ -1,14 +1,10 @@
 use crate::app::App;
+use crate::enums::Severity;
 use crate::structs::Key;
-use std::env;
-use std::path::PathBuf;
 use std::process::Command;
 
 pub fn open_configure(app: &mut App, key: &mut Key) {
-    let mut yaml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    yaml_path.push("..");
-    yaml_path.push(app.file_name.clone());
-    yaml_path.push("configure");
+    let yaml_path = app.config_dir.clone();
 
     let yaml_file = yaml_path.clone();
 
@@ -23,7 +19,13 @@ pub fn open_configure(app: &mut App, key: &mut Key) {
     let _temp = yaml_file;
 
     match spawn_command {
-        Ok(_) => println!("Press {} to refresh", key.refresh_key),
-        Err(e) => eprintln!("Failed to spawn terminal: {}", e),
+        Ok(_) => app.notify(
+            Severity::Info,
+            format!("Press {} to refresh after editing", key.refresh_key),
+        ),
+        Err(e) => app.notify(
+            Severity::Error,
+            format!("Couldn't open the configure folder: {}", e),
+        ),
     }
 }
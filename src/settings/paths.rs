@@ -0,0 +1,148 @@
+// Resolves where spoify keeps its configuration and its cached data
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::settings::permissions::write_private;
+
+/// Environment variable overriding the configuration directory
+pub const CONFIG_DIR_VAR: &str = "SPOIFY_CONFIG_DIR";
+/// Environment variable overriding the cache directory
+pub const CACHE_DIR_VAR: &str = "SPOIFY_CACHE_DIR";
+/// Environment variable choosing the profile when `--profile` isn't given
+pub const PROFILE_VAR: &str = "SPOIFY_PROFILE";
+
+/// The profile kept directly in the configuration and cache directories
+pub const DEFAULT_PROFILE: &str = "default";
+// Every other profile has a folder of this name in both directories
+const PROFILES_DIR: &str = "profiles";
+
+// Files written to the configuration directory when they are missing
+const DEFAULT_FILES: [(&str, &str); 7] = [
+    ("creds.yml", include_str!("../../configure/creds.yml")),
+    ("settings.yml", include_str!("../../configure/settings.yml")),
+    (
+        "keybindings.yml",
+        include_str!("../../configure/keybindings.yml"),
+    ),
+    ("default.yml", include_str!("../../configure/default.yml")),
+    ("dracula.yml", include_str!("../../configure/dracula.yml")),
+    (
+        "kanagawa-dark.yml",
+        include_str!("../../configure/kanagawa-dark.yml"),
+    ),
+    (
+        "kanagawa-light.yml",
+        include_str!("../../configure/kanagawa-light.yml"),
+    ),
+];
+
+// A command line flag wins over the environment variable, which wins over the platform directory
+fn resolve_dir(flag: Option<PathBuf>, var: &str, platform_dir: Option<PathBuf>) -> PathBuf {
+    flag.or_else(|| {
+        env::var_os(var)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    })
+    .or_else(|| platform_dir.map(|dir| dir.join("spoify")))
+    .unwrap_or_else(|| PathBuf::from(".spoify"))
+}
+
+/// Directory holding the keybindings, settings, themes and credentials (`~/.config/spoify` on Linux)
+pub fn config_dir(flag: Option<PathBuf>) -> PathBuf {
+    resolve_dir(flag, CONFIG_DIR_VAR, dirs::config_dir())
+}
+
+/// Directory holding the token cache and the cached Spotify data (`~/.cache/spoify` on Linux)
+pub fn cache_dir(flag: Option<PathBuf>) -> PathBuf {
+    resolve_dir(flag, CACHE_DIR_VAR, dirs::cache_dir())
+}
+
+/// The profile given by the flag, then by the environment variable, the default one otherwise
+pub fn profile_name(flag: Option<String>) -> String {
+    flag.or_else(|| env::var(PROFILE_VAR).ok())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Profile names become folder names, so they are kept to letters, digits, `-` and `_`
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Directory of a profile inside the configuration or the cache directory
+pub fn profile_dir(root: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        root.to_path_buf()
+    } else {
+        root.join(PROFILES_DIR).join(profile)
+    }
+}
+
+/// The default profile followed by every profile that has a configure folder, sorted by name
+pub fn list_profiles(root_config_dir: &Path) -> Vec<String> {
+    let mut profiles: Vec<String> = fs::read_dir(root_config_dir.join(PROFILES_DIR))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_valid_profile_name(name) && name != DEFAULT_PROFILE)
+        .collect();
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+    profiles
+}
+
+/// Contents of a configuration file as shipped with this release
+pub fn default_file(name: &str) -> Option<&'static str> {
+    DEFAULT_FILES
+        .iter()
+        .find(|(file_name, _)| *file_name == name)
+        .map(|(_, contents)| *contents)
+}
+
+/// Reads a YAML file of the configuration directory, the errors name the file so the user knows which one to fix
+pub fn read_yaml<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        io::Error::new(e.kind(), format!("Couldn't read {}: {}", path.display(), e))
+    })?;
+    serde_yaml::from_str(&contents).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} isn't valid YAML: {}", path.display(), e),
+        )
+    })
+}
+
+/// Writes `data` as YAML to a file of the configuration directory
+pub fn write_yaml<T: Serialize>(path: &Path, data: &T) -> io::Result<()> {
+    let contents =
+        serde_yaml::to_string(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, contents)
+}
+
+/// Creates the configuration directory and writes the default files it is missing
+pub fn write_default_config(config_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(config_dir)?;
+    for (name, contents) in DEFAULT_FILES {
+        let path = config_dir.join(name);
+        if path.exists() {
+            continue;
+        }
+        // The credentials are secrets, the other files can be shared like any config
+        if name == "creds.yml" {
+            write_private(&path, contents)?;
+        } else {
+            fs::write(path, contents)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+// Keeps the files holding secrets (the credentials and the token) readable by their owner only
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[cfg(unix)]
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+// Read and write for the owner, nothing for anyone else
+#[cfg(unix)]
+const PRIVATE_MODE: u32 = 0o600;
+
+/// Writes `contents` to a file only its owner can read, an existing file is restricted first
+pub fn write_private(path: &Path, contents: &str) -> io::Result<()> {
+    restrict(path)?;
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(PRIVATE_MODE);
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+/// Makes an existing file readable by its owner only, a missing file is left alone
+#[cfg(unix)]
+pub fn restrict(path: &Path) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    fs::set_permissions(path, fs::Permissions::from_mode(PRIVATE_MODE))
+}
+
+// Windows keeps the files of a user's profile private already
+#[cfg(not(unix))]
+pub fn restrict(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Returns true when users other than the owner can read the file
+#[cfg(unix)]
+pub fn is_world_readable(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o004 != 0)
+}
+
+#[cfg(not(unix))]
+pub fn is_world_readable(_path: &Path) -> bool {
+    false
+}
@@ -0,0 +1,44 @@
+// Loads the configure folder and credentials of a profile, at startup and when switching from the interface
+use std::io;
+
+use crate::app::App;
+use crate::settings::creds::set_creds;
+use crate::settings::keybindings::read_keybindings;
+use crate::settings::migrate::migrate_config;
+use crate::settings::paths::{profile_dir, write_default_config};
+use crate::settings::permissions::is_world_readable;
+use crate::settings::settings::read_settings;
+use crate::spotify::auth::token_cache_path;
+
+/// Points the app at the folders of `profile`, writes or upgrades its configure folder and reads its credentials
+///
+/// Returns a warning for every secret file of the profile that other users can read.
+pub fn load_profile(app: &mut App, profile: &str) -> io::Result<Vec<String>> {
+    app.profile = profile.to_string();
+    app.config_dir = profile_dir(&app.root_config_dir, profile);
+    app.cache_dir = profile_dir(&app.root_cache_dir, profile);
+
+    // Write the default configure files on the first run, then upgrade older ones
+    write_default_config(&app.config_dir)?;
+    migrate_config(&app.config_dir)?;
+
+    // A file that can't be read is reported here, before the app switches to the profile
+    read_keybindings(app)?;
+    read_settings(app)?;
+
+    // Set the creds from the configure files
+    set_creds(app)?;
+
+    // Anyone who can read these files can use the Spotify account
+    Ok([app.config_dir.join("creds.yml"), token_cache_path(app)]
+        .into_iter()
+        .filter(|file| is_world_readable(file))
+        .map(|file| {
+            format!(
+                "{} can be read by other users, run 'chmod 600 {}'",
+                file.display(),
+                file.display()
+            )
+        })
+        .collect())
+}
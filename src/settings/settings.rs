@@ -1,35 +1,35 @@
+use rspotify::model::{Country, Market};
 use serde::Deserialize;
-use serde_yaml;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io;
+use std::time::Duration;
 
 use crate::app::App;
+use crate::enums::Severity;
+use crate::settings::paths::read_yaml;
 use crate::structs::Settings;
 
 #[derive(Deserialize, Debug)]
 struct SettingsValues(HashMap<String, String>);
 
 /// Reads the settings from the configuration file and returns them as a HashMap
-pub fn read_settings(app: &mut App) -> HashMap<String, String> {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(".."); // Move up to the root of the Git repository
-    path.push(app.file_name.clone());
-    path.push("configure");
-    path.push("settings.yml");
-
-    let file = File::open(&path).expect("Unable to open settings_values file");
-    let reader = BufReader::new(file);
-    let SettingsValues(settings_values) =
-        serde_yaml::from_reader(reader).expect("Unable to parse settings_values from YAML");
-
-    settings_values
+pub fn read_settings(app: &App) -> io::Result<HashMap<String, String>> {
+    let SettingsValues(settings_values) = read_yaml(&app.config_dir.join("settings.yml"))?;
+    Ok(settings_values)
 }
 
 /// Sets the settings in the App struct based on the loaded configuration
 pub fn set_settings_values(app: &mut App, settings: &mut Settings) {
-    let settings_values = read_settings(app);
+    let settings_values = match read_settings(app) {
+        Ok(settings_values) => settings_values,
+        Err(e) => {
+            app.notify(
+                Severity::Error,
+                format!("Couldn't load the settings: {}", e),
+            );
+            return;
+        }
+    };
 
     if let Some(value_str) = settings_values.get("Volume Increament Value") {
         settings.volume_increment_value = value_str.parse::<u8>().unwrap_or(0);
@@ -42,4 +42,47 @@ pub fn set_settings_values(app: &mut App, settings: &mut Settings) {
     if let Some(value_str) = settings_values.get("Theme") {
         settings.theme_name = value_str.to_string();
     }
+
+    if let Some(value_str) = settings_values.get("Disk Cache") {
+        app.disk_cache = value_str.parse::<bool>().unwrap_or(false);
+    }
+
+    if let Some(value_str) = settings_values.get("Market") {
+        app.market = match parse_market(value_str) {
+            Some(market) => market,
+            None => {
+                app.log.record(
+                    Severity::Warning,
+                    format!(
+                        "Market '{}' isn't a country code Spotify knows, searching in the account's country",
+                        value_str
+                    ),
+                );
+                Market::FromToken
+            }
+        };
+    }
+
+    if let Some(value_str) = settings_values.get("Hide Unplayable") {
+        app.hide_unplayable = value_str.parse::<bool>().unwrap_or(false);
+    }
+
+    if let Some(value_str) = settings_values.get("Search As You Type") {
+        app.live_search = value_str.parse::<bool>().unwrap_or(false);
+    }
+
+    if let Some(value_str) = settings_values.get("Search Delay") {
+        app.live_search_delay = Duration::from_millis(value_str.parse::<u64>().unwrap_or(300));
+    }
+}
+
+// "from_token" (or nothing) is the country of the account, anything else a code such as "DE"
+fn parse_market(value: &str) -> Option<Market> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("from_token") {
+        return Some(Market::FromToken);
+    }
+    serde_json::from_value::<Country>(serde_json::Value::String(value.to_uppercase()))
+        .ok()
+        .map(Market::Country)
 }
@@ -1,39 +1,33 @@
 use ratatui::style::Color;
 use serde::Deserialize;
 use serde_json::Value;
-use serde_yaml;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io;
 
 use crate::app::App;
+use crate::enums::Severity;
+use crate::settings::paths::read_yaml;
 use crate::structs::{Settings, Themes};
 
 #[derive(Deserialize, Debug)]
 struct Theme(HashMap<String, Value>);
 
 /// Reads the theme configuration file and returns the parsed theme data as a HashMap
-pub fn read_theme(app: &mut App, settings: &mut Settings) -> HashMap<String, Value> {
-    let file_name = format!("{}.yml", settings.theme_name.clone());
-
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push("..");
-    path.push(app.file_name.clone());
-    path.push("configure");
-    path.push(file_name);
-
-    let file = File::open(&path).expect("Unable to open theme file");
-    let reader = BufReader::new(file);
-
-    let theme: Theme = serde_yaml::from_reader(reader).expect("Unable to parse theme from YAML");
-
-    theme.0
+pub fn read_theme(app: &App, settings: &Settings) -> io::Result<HashMap<String, Value>> {
+    let file_name = format!("{}.yml", settings.theme_name);
+    let Theme(theme) = read_yaml(&app.config_dir.join(file_name))?;
+    Ok(theme)
 }
 
 /// Sets the application theme based on the configuration loaded from the theme file
 pub fn set_theme(app: &mut App, themes: &mut Themes, settings: &mut Settings) {
-    let theme = read_theme(app, settings);
+    let theme = match read_theme(app, settings) {
+        Ok(theme) => theme,
+        Err(e) => {
+            app.notify(Severity::Error, format!("Couldn't load the theme: {}", e));
+            return;
+        }
+    };
 
     // Iterate over all entries in the theme HashMap
     for (key, value) in theme.iter() {
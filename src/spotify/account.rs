@@ -0,0 +1,24 @@
+// This section fetches what the account screen shows about the login
+use rspotify::model::{PrivateUser, Token};
+use rspotify::ClientError;
+use serde::{Deserialize, Serialize};
+
+use crate::app::App;
+use crate::spotify::backend::get_backend;
+
+/// The user spoify is logged in as and the token it uses for them
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub user: PrivateUser,
+    pub token: Option<Token>,
+}
+
+/// Fetches the current user and reads the token of the backend
+pub async fn account(app: &App) -> Result<Account, ClientError> {
+    let spotify = get_backend(app).await?;
+
+    // Asking for the user refreshes a token that ran out, so the token read after it is the one in use
+    let user = spotify.current_user().await?;
+    let token = spotify.token().await?;
+    Ok(Account { user, token })
+}
@@ -1,23 +1,32 @@
 extern crate rspotify;
 
+use async_trait::async_trait;
+use rspotify::http::HttpError;
 use rspotify::prelude::OAuthClient;
-use rspotify::{scopes, AuthCodeSpotify, ClientError, Credentials, OAuth};
-use std::env;
+use rspotify::{
+    scopes, AuthCodePkceSpotify, AuthCodeSpotify, ClientCredsSpotify, ClientError, Credentials,
+    OAuth,
+};
+use std::collections::HashSet;
 use std::fs;
-use std::io::stdin;
-use std::path::PathBuf;
+use std::io::{self, stdin};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use url::Url;
 use webbrowser;
 
 use crate::app::App;
+use crate::enums::AuthFlow;
+use crate::settings::permissions;
+use crate::spotify::backend::SpotifyBackend;
+use crate::spotify::login_server;
 
-// Function to get the Spotify client, either from a cached token or through the authorization flow
-pub async fn get_spotify_client(app: &mut App) -> Result<AuthCodeSpotify, ClientError> {
-    let client_id = &app.client_id;
-    let client_secret_id = &app.client_secret;
+/// Where Spotify sends the browser after the login, it has to be added to the app on the dashboard
+pub const REDIRECT_URI: &str = "http://localhost:8888/callback";
 
-    // Defining the scopes (permissions) required for the application
-    let scopes = scopes!(
+/// The scopes (permissions) spoify asks for when the user logs in
+pub fn requested_scopes() -> HashSet<String> {
+    scopes!(
         "user-read-email",
         "user-read-private",
         "user-top-read",
@@ -37,82 +46,193 @@ pub async fn get_spotify_client(app: &mut App) -> Result<AuthCodeSpotify, Client
         "ugc-image-upload",
         "app-remote-control",
         "streaming"
-    );
+    )
+}
+
+/// How the authorization flow talks to the user, on the terminal or on the setup screen
+#[async_trait]
+pub trait LoginPrompt: Send + Sync {
+    /// Tells the user to log in at `auth_url`, `paste_reason` says why the redirected URL has to be pasted when it can't be caught
+    fn show_login(&self, auth_url: &str, browser_opened: bool, paste_reason: Option<String>);
+
+    /// Waits for the user to paste the URL the browser was redirected to
+    async fn redirected_url(&self) -> Result<String, ClientError>;
+}
 
-    let mut oauth = OAuth::default();
-    oauth.scopes = scopes;
-    oauth.redirect_uri = "http://localhost:8888/callback".to_owned();
+/// Logs in on the terminal, before the interface takes it over
+pub struct TerminalPrompt;
+
+#[async_trait]
+impl LoginPrompt for TerminalPrompt {
+    fn show_login(&self, auth_url: &str, browser_opened: bool, paste_reason: Option<String>) {
+        if !browser_opened {
+            println!(
+                "Failed to open the authorization URL. Please visit the URL manually: {}",
+                auth_url
+            );
+        }
+        match paste_reason {
+            Some(reason) => println!("{}", reason),
+            None => println!("Waiting for the login to finish in the browser..."),
+        }
+    }
+
+    // Prompting the user to enter the redirected URL after authorization
+    async fn redirected_url(&self) -> Result<String, ClientError> {
+        println!("Enter redirected url:");
+        let mut url_input = String::new();
+        stdin().read_line(&mut url_input).map_err(ClientError::Io)?;
+        Ok(url_input)
+    }
+}
+
+/// Asks Spotify for a token with the Client ID and Secret alone, so wrong ones are found before the browser opens
+///
+/// A PKCE login has no secret to check, a wrong Client ID shows up on Spotify's login page instead.
+pub async fn check_credentials(app: &App) -> Result<(), ClientError> {
+    if app.auth_flow == AuthFlow::Pkce {
+        return Ok(());
+    }
+
+    let spotify = ClientCredsSpotify::new(Credentials::new(&app.client_id, &app.client_secret));
+    match spotify.request_token().await {
+        Err(ClientError::Http(err))
+            if matches!(&*err, HttpError::StatusCode(response) if response.status().as_u16() == 400 || response.status().as_u16() == 401) =>
+        {
+            Err(ClientError::Cli(
+                "Spotify doesn't accept this Client ID and Client Secret, check them on the dashboard"
+                    .to_string(),
+            ))
+        }
+        result => result,
+    }
+}
+
+/// Removes the cached tokens of both logins, the next login goes through the browser again
+pub fn forget_token(app: &App) -> io::Result<()> {
+    for flow in [AuthFlow::ClientSecret, AuthFlow::Pkce] {
+        match fs::remove_file(token_cache_file(&app.cache_dir, flow)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
 
-    let creds = Credentials::new(client_id, client_secret_id);
+// Function to build the Spotify client, either from a cached token or through the authorization flow
+pub async fn build_spotify_client(
+    app: &App,
+    prompt: &dyn LoginPrompt,
+) -> Result<Arc<dyn SpotifyBackend>, ClientError> {
+    let client_id = &app.client_id;
+    let client_secret_id = &app.client_secret;
 
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(".."); // Move up to the root of the Git repository
-    path.push(app.file_name.clone());
-    path.push("spotify_cache");
+    let oauth = OAuth {
+        scopes: requested_scopes(),
+        redirect_uri: REDIRECT_URI.to_owned(),
+        ..Default::default()
+    };
 
-    fs::create_dir_all(&path).unwrap();
+    let path = app.cache_dir.clone();
+    fs::create_dir_all(&path).map_err(ClientError::Io)?;
 
     let config = rspotify::Config {
         token_cached: true,
         token_refreshing: true,
-        cache_path: path.join(".spotify_token_cache.json"),
+        cache_path: token_cache_path(app),
         ..Default::default()
     };
 
-    let mut spotify = AuthCodeSpotify::with_config(creds, oauth, config);
+    match app.auth_flow {
+        AuthFlow::ClientSecret => {
+            let creds = Credentials::new(client_id, client_secret_id);
+            let spotify = AuthCodeSpotify::with_config(creds, oauth, config);
+            let auth_url = spotify.get_authorize_url(true)?;
+            log_in(&spotify, &auth_url, prompt).await?;
+            Ok(Arc::new(spotify))
+        }
+        AuthFlow::Pkce => {
+            let creds = Credentials::new_pkce(client_id);
+            let mut spotify = AuthCodePkceSpotify::with_config(creds, oauth, config);
+            // The verifier for this login is kept by the client until the code is exchanged
+            let auth_url = spotify.get_authorize_url(None)?;
+            log_in(&spotify, &auth_url, prompt).await?;
+            Ok(Arc::new(spotify))
+        }
+    }
+}
+
+/// Where the token of the app's login is cached
+///
+/// A PKCE token can't be refreshed with the Client Secret and the other way around, so each flow has its own file.
+pub fn token_cache_path(app: &App) -> PathBuf {
+    token_cache_file(&app.cache_dir, app.auth_flow)
+}
 
+fn token_cache_file(cache_dir: &Path, flow: AuthFlow) -> PathBuf {
+    match flow {
+        AuthFlow::ClientSecret => cache_dir.join(".spotify_token_cache.json"),
+        AuthFlow::Pkce => cache_dir.join(".spotify_pkce_token_cache.json"),
+    }
+}
+
+// Uses the cached token, or asks the user to log in when there isn't one
+async fn log_in(
+    spotify: &impl OAuthClient,
+    auth_url: &str,
+    prompt: &dyn LoginPrompt,
+) -> Result<(), ClientError> {
     match spotify.read_token_cache(true).await {
         Ok(Some(token)) => {
-            *spotify.token.lock().await.unwrap() = Some(token);
-        }
-        Ok(None) => {
-            // No cached token found, enter the authorization flow
-            handle_authorization_flow(&mut spotify).await?;
+            *spotify.get_token().lock().await.unwrap() = Some(token);
         }
-        Err(e) => {
-            println!("Failed to read token cache: {}", e);
-            // Handle the error, e.g., by entering the authorization flow
-            handle_authorization_flow(&mut spotify).await?;
+        // No cached token found, or one that can't be read (rspotify reports a missing file this way too), enter the authorization flow
+        Ok(None) | Err(_) => {
+            handle_authorization_flow(spotify, auth_url, prompt).await?;
         }
     }
 
-    Ok(spotify)
+    // rspotify writes the token with the default permissions, anyone reading it could use the account
+    permissions::restrict(&spotify.get_config().cache_path).map_err(ClientError::Io)?;
+    Ok(())
 }
 
 // Function to handle the authorization flow with Spotify
-async fn handle_authorization_flow(spotify: &mut AuthCodeSpotify) -> Result<(), ClientError> {
-    let auth_url = spotify.get_authorize_url(true).unwrap(); // Getting the authorization URL
-
-    if webbrowser::open(&auth_url).is_err() {
-        // Attempting to open the authorization URL in the default browser
-        println!(
-            "Failed to open the authorization URL. Please visit the URL manually: {}",
-            auth_url
-        );
-    }
-
-    // Prompting the user to enter the redirected URL after authorization
-    println!("Enter redirected url:");
-    let mut url_input = String::new();
-    stdin().read_line(&mut url_input).unwrap();
-    let url_string = &url_input.as_str();
-
-    // Parsing the redirected URL
-    let url = Url::parse(url_string).expect("Failed to parse URL");
-    let query_pairs = url.query_pairs();
-
-    let mut code = String::new();
-    let mut _state = String::new();
-    for (key, value) in query_pairs {
-        if key == "code" {
-            code = value.to_string();
-        } else if key == "state" {
-            _state = value.to_string();
+async fn handle_authorization_flow(
+    spotify: &impl OAuthClient,
+    auth_url: &str,
+    prompt: &dyn LoginPrompt,
+) -> Result<(), ClientError> {
+    let redirect_uri = spotify.get_oauth().redirect_uri.clone();
+    let state = spotify.get_oauth().state.clone();
+
+    // Listen before the browser opens, so the redirect can't arrive before spoify is ready for it
+    let listeners = login_server::bind(&redirect_uri).await;
+
+    // Attempting to open the authorization URL in the default browser
+    let browser_opened = webbrowser::open(auth_url).is_ok();
+
+    let code = match listeners {
+        Ok(listeners) => {
+            prompt.show_login(auth_url, browser_opened, None);
+            login_server::wait_for_code(listeners, &redirect_uri, &state).await
         }
-    }
+        // Something else is using the port, so the redirected URL has to be pasted in
+        Err(e) => {
+            let reason = format!("Couldn't listen on {} ({})", redirect_uri, e);
+            prompt.show_login(auth_url, browser_opened, Some(reason));
+            let url_input = prompt.redirected_url().await?;
+
+            // Parsing the redirected URL
+            let url = Url::parse(url_input.trim()).map_err(|_| {
+                ClientError::Cli("The redirected url isn't a valid URL".to_string())
+            })?;
+            login_server::code_from_url(&url, &state)
+        }
+    }?;
 
     // Requesting the access token using the authorization code
-    spotify.request_token(code.trim()).await?;
+    spotify.request_token(&code).await?;
 
     Ok(())
 }
@@ -0,0 +1,789 @@
+// This section is an in-memory Spotify used to run the app without a network or an account
+use super::SpotifyBackend;
+use crate::spotify::auth::requested_scopes;
+use crate::spotify::search::query::free_text;
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use rspotify::model::{
+    Actions, AlbumId, AlbumType, ArtistId, Country, CurrentPlaybackContext, CurrentlyPlayingType,
+    DatePrecision, Device, DeviceType, Followers, FullAlbum, FullArtist, FullTrack, Market, Page,
+    PlayHistory, PlayableItem, PlaylistId, PlaylistItem, PlaylistTracksRef, PrivateUser,
+    PublicUser, RepeatState, SavedAlbum, SavedTrack, SearchResult, SearchType, Show, ShowId,
+    SimplifiedAlbum, SimplifiedArtist, SimplifiedPlaylist, SimplifiedShow, SimplifiedTrack,
+    SubscriptionLevel, TimeRange, Token, TrackId, UserId,
+};
+use rspotify::ClientError;
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// Every timestamp is derived from this one so the data is the same on every run (2024-01-01)
+const BASE_TIMESTAMP: i64 = 1_704_067_200;
+
+// (id, name, genre)
+const ARTISTS: &[(&str, &str, &str)] = &[
+    ("fakeartist0001", "Aurora Lane", "indie pop"),
+    ("fakeartist0002", "The Night Owls", "synthwave"),
+    ("fakeartist0003", "Marco Vell", "jazz"),
+    ("fakeartist0004", "Kite Theory", "alternative rock"),
+];
+
+// (id, name, index of the artist, release date)
+const ALBUMS: &[(&str, &str, usize, &str)] = &[
+    ("fakealbum00001", "Northern Lights", 0, "2023-03-17"),
+    ("fakealbum00002", "Neon Drive", 1, "2023-08-04"),
+    ("fakealbum00003", "Late Set", 2, "2023-11-24"),
+    ("fakealbum00004", "Paper Skies", 3, "2024-01-12"),
+];
+
+// (id, name, index of the album, duration in seconds)
+const TRACKS: &[(&str, &str, usize, i64)] = &[
+    ("faketrack00001", "First Light", 0, 214),
+    ("faketrack00002", "Glacier", 0, 187),
+    ("faketrack00003", "Polar Night", 0, 243),
+    ("faketrack00004", "Midnight Run", 1, 201),
+    ("faketrack00005", "Chrome Hearts", 1, 226),
+    ("faketrack00006", "Outrun", 1, 259),
+    ("faketrack00007", "Blue Smoke", 2, 312),
+    ("faketrack00008", "After Hours", 2, 275),
+    ("faketrack00009", "Last Call", 2, 198),
+    ("faketrack00010", "Paper Skies", 3, 232),
+    ("faketrack00011", "Crosswind", 3, 204),
+    ("faketrack00012", "Tailspin", 3, 189),
+];
+
+// A track that isn't available in the account's country, like a release that is region locked
+const UNPLAYABLE_TRACK: usize = 10;
+
+// (id, name, publisher)
+const SHOWS: &[(&str, &str, &str)] = &[
+    ("fakeshow000001", "Night Shift Radio", "Spoify"),
+    ("fakeshow000002", "The Mixing Desk", "Spoify"),
+];
+
+// Id of the made for you category the app asks for
+const MADE_FY_CATEGORY: &str = "0JQ5DAt0tbjZptfcdMSKl3";
+
+#[derive(Clone, Debug)]
+struct FakePlaylist {
+    id: String,
+    name: String,
+    tracks: Vec<usize>,
+    followed: bool,
+    made_fy: bool,
+}
+
+#[derive(Debug)]
+struct FakeState {
+    playlists: Vec<FakePlaylist>,
+    liked_tracks: Vec<usize>,
+    saved_albums: Vec<usize>,
+    followed_artists: Vec<usize>,
+    saved_shows: Vec<usize>,
+    // Most recent first, the i64 is the play time in seconds
+    recently_played: Vec<(usize, i64)>,
+    current_track: Option<usize>,
+    is_playing: bool,
+    progress_ms: i64,
+    shuffle: bool,
+    repeat: RepeatState,
+    volume: u8,
+}
+
+/// A deterministic Spotify backend that keeps everything in memory
+///
+/// Clones share the same state, so a change made by one request is seen by the next one.
+#[derive(Clone, Debug)]
+pub struct FakeBackend {
+    state: Arc<Mutex<FakeState>>,
+}
+
+impl Default for FakeBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeBackend {
+    /// Creates a backend seeded with a small catalogue of artists, albums, tracks, playlists and shows
+    pub fn new() -> Self {
+        let playlist =
+            |id: &str, name: &str, tracks: &[usize], followed: bool, made_fy: bool| FakePlaylist {
+                id: id.to_string(),
+                name: name.to_string(),
+                tracks: tracks.to_vec(),
+                followed,
+                made_fy,
+            };
+
+        let state = FakeState {
+            playlists: vec![
+                playlist("fakeplaylist01", "Morning Coffee", &[0, 6, 9], true, false),
+                playlist(
+                    "fakeplaylist02",
+                    "Late Night Drive",
+                    &[3, 4, 5, 7],
+                    true,
+                    false,
+                ),
+                playlist("fakeplaylist03", "Focus", &[1, 2, 10], true, false),
+                playlist("fakeplaylist04", "Daily Mix 1", &[0, 3, 6, 9], false, true),
+                playlist(
+                    "fakeplaylist05",
+                    "Discover Weekly",
+                    &[2, 5, 8, 11],
+                    false,
+                    true,
+                ),
+            ],
+            liked_tracks: vec![0, 4, 7, 10],
+            saved_albums: vec![0, 2],
+            followed_artists: vec![0, 1, 3],
+            saved_shows: vec![0, 1],
+            recently_played: vec![
+                (5, BASE_TIMESTAMP - 600),
+                (2, BASE_TIMESTAMP - 1200),
+                (9, BASE_TIMESTAMP - 1800),
+            ],
+            current_track: Some(0),
+            is_playing: false,
+            progress_ms: 42_000,
+            shuffle: false,
+            repeat: RepeatState::Off,
+            volume: 50,
+        };
+
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    // A panicked request must not take the backend down with it, so a poisoned lock is reused
+    fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn not_found(kind: &str, id: &str) -> ClientError {
+    ClientError::Io(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no {} with id {}", kind, id),
+    ))
+}
+
+fn timestamp(secs: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(secs, 0).unwrap_or_default()
+}
+
+fn urls(kind: &str, id: &str) -> HashMap<String, String> {
+    HashMap::from([(
+        "spotify".to_string(),
+        format!("https://open.spotify.com/{}/{}", kind, id),
+    )])
+}
+
+fn href(kind: &str, id: &str) -> String {
+    format!("https://api.spotify.com/v1/{}s/{}", kind, id)
+}
+
+// Lowercased query without the quotes the app adds around playlist searches
+fn normalize(query: &str) -> String {
+    query.replace('"', "").trim().to_lowercase()
+}
+
+fn page<T>(items: Vec<T>, limit: u32) -> Page<T> {
+    page_at(items, limit, 0)
+}
+
+// The page of `items` starting at `offset`, `next` is only set to tell that there are more
+fn page_at<T>(items: Vec<T>, limit: u32, offset: u32) -> Page<T> {
+    let total = items.len() as u32;
+    Page {
+        href: String::new(),
+        items: items
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect(),
+        limit,
+        next: (offset + limit < total).then(String::new),
+        offset,
+        previous: None,
+        total,
+    }
+}
+
+fn track_index(id: &str) -> Result<usize, ClientError> {
+    TRACKS
+        .iter()
+        .position(|track| track.0 == id)
+        .ok_or_else(|| not_found("track", id))
+}
+
+fn simplified_artist(index: usize) -> SimplifiedArtist {
+    let (id, name, _) = ARTISTS[index];
+    SimplifiedArtist {
+        external_urls: urls("artist", id),
+        href: Some(href("artist", id)),
+        id: ArtistId::from_id(id.to_string()).ok(),
+        name: name.to_string(),
+    }
+}
+
+fn full_artist(index: usize) -> FullArtist {
+    let (id, name, genre) = ARTISTS[index];
+    FullArtist {
+        external_urls: urls("artist", id),
+        followers: Followers {
+            total: 1000 * (index as u32 + 1),
+        },
+        genres: vec![genre.to_string()],
+        href: href("artist", id),
+        id: ArtistId::from_id(id.to_string()).expect("fake ids are alphanumeric"),
+        images: Vec::new(),
+        name: name.to_string(),
+        popularity: 50 + index as u32,
+    }
+}
+
+fn simplified_album(index: usize) -> SimplifiedAlbum {
+    let (id, name, artist, release_date) = ALBUMS[index];
+    SimplifiedAlbum {
+        album_group: None,
+        album_type: Some("album".to_string()),
+        artists: vec![simplified_artist(artist)],
+        available_markets: Vec::new(),
+        external_urls: urls("album", id),
+        href: Some(href("album", id)),
+        id: AlbumId::from_id(id.to_string()).ok(),
+        images: Vec::new(),
+        name: name.to_string(),
+        release_date: Some(release_date.to_string()),
+        release_date_precision: Some("day".to_string()),
+        restrictions: None,
+    }
+}
+
+fn album_track_indexes(album: usize) -> Vec<usize> {
+    (0..TRACKS.len())
+        .filter(|&track| TRACKS[track].2 == album)
+        .collect()
+}
+
+fn full_album(index: usize) -> FullAlbum {
+    let (id, name, artist, release_date) = ALBUMS[index];
+    let tracks = album_track_indexes(index)
+        .into_iter()
+        .map(simplified_track)
+        .collect();
+    FullAlbum {
+        artists: vec![simplified_artist(artist)],
+        album_type: AlbumType::Album,
+        available_markets: None,
+        copyrights: Vec::new(),
+        external_ids: HashMap::new(),
+        external_urls: urls("album", id),
+        genres: Vec::new(),
+        href: href("album", id),
+        id: AlbumId::from_id(id.to_string()).expect("fake ids are alphanumeric"),
+        images: Vec::new(),
+        name: name.to_string(),
+        popularity: 50,
+        release_date: release_date.to_string(),
+        release_date_precision: DatePrecision::Day,
+        tracks: page(tracks, 50),
+        label: None,
+    }
+}
+
+fn full_track(index: usize) -> FullTrack {
+    let (id, name, album, duration) = TRACKS[index];
+    FullTrack {
+        album: simplified_album(album),
+        artists: vec![simplified_artist(ALBUMS[album].2)],
+        available_markets: Vec::new(),
+        disc_number: 1,
+        duration: TimeDelta::seconds(duration),
+        explicit: false,
+        external_ids: HashMap::new(),
+        external_urls: urls("track", id),
+        href: Some(href("track", id)),
+        id: TrackId::from_id(id.to_string()).ok(),
+        is_local: false,
+        is_playable: Some(index != UNPLAYABLE_TRACK),
+        linked_from: None,
+        restrictions: None,
+        name: name.to_string(),
+        popularity: 50,
+        preview_url: None,
+        track_number: index as u32 % 3 + 1,
+    }
+}
+
+fn simplified_track(index: usize) -> SimplifiedTrack {
+    let track = full_track(index);
+    SimplifiedTrack {
+        album: Some(track.album),
+        artists: track.artists,
+        available_markets: None,
+        disc_number: track.disc_number,
+        duration: track.duration,
+        explicit: track.explicit,
+        external_urls: track.external_urls,
+        href: track.href,
+        id: track.id,
+        is_local: track.is_local,
+        is_playable: track.is_playable,
+        linked_from: None,
+        restrictions: None,
+        name: track.name,
+        preview_url: None,
+        track_number: track.track_number,
+    }
+}
+
+fn owner() -> PublicUser {
+    PublicUser {
+        display_name: Some("Spoify".to_string()),
+        external_urls: urls("user", "spoify"),
+        followers: None,
+        href: href("user", "spoify"),
+        id: UserId::from_id("spoify").expect("fake ids are alphanumeric"),
+        images: Vec::new(),
+    }
+}
+
+fn simplified_playlist(playlist: &FakePlaylist) -> SimplifiedPlaylist {
+    SimplifiedPlaylist {
+        collaborative: false,
+        external_urls: urls("playlist", &playlist.id),
+        href: href("playlist", &playlist.id),
+        id: PlaylistId::from_id(playlist.id.clone()).expect("fake ids are alphanumeric"),
+        images: Vec::new(),
+        name: playlist.name.clone(),
+        owner: owner(),
+        public: Some(true),
+        snapshot_id: playlist.tracks.len().to_string(),
+        tracks: PlaylistTracksRef {
+            href: format!("{}/tracks", href("playlist", &playlist.id)),
+            total: playlist.tracks.len() as u32,
+        },
+    }
+}
+
+fn simplified_show(index: usize) -> SimplifiedShow {
+    let (id, name, publisher) = SHOWS[index];
+    SimplifiedShow {
+        available_markets: Vec::new(),
+        copyrights: Vec::new(),
+        description: format!("{} by {}", name, publisher),
+        explicit: false,
+        external_urls: urls("show", id),
+        href: href("show", id),
+        id: ShowId::from_id(id.to_string()).expect("fake ids are alphanumeric"),
+        images: Vec::new(),
+        is_externally_hosted: Some(false),
+        languages: vec!["en".to_string()],
+        media_type: "audio".to_string(),
+        name: name.to_string(),
+        publisher: publisher.to_string(),
+    }
+}
+
+#[async_trait]
+impl SpotifyBackend for FakeBackend {
+    async fn current_playback(&self) -> Result<Option<CurrentPlaybackContext>, ClientError> {
+        let state = self.state();
+        let Some(track) = state.current_track else {
+            return Ok(None);
+        };
+
+        Ok(Some(CurrentPlaybackContext {
+            device: Device {
+                id: Some("fakedevice".to_string()),
+                is_active: true,
+                is_private_session: false,
+                is_restricted: false,
+                name: "Spoify Fake Device".to_string(),
+                _type: DeviceType::Computer,
+                volume_percent: Some(state.volume as u32),
+            },
+            repeat_state: state.repeat,
+            shuffle_state: state.shuffle,
+            context: None,
+            timestamp: timestamp(BASE_TIMESTAMP),
+            progress: Some(TimeDelta::milliseconds(state.progress_ms)),
+            is_playing: state.is_playing,
+            item: Some(PlayableItem::Track(full_track(track))),
+            currently_playing_type: CurrentlyPlayingType::Track,
+            actions: Actions::default(),
+        }))
+    }
+
+    async fn start_track(
+        &self,
+        track_id: &str,
+        _device_id: Option<&str>,
+    ) -> Result<(), ClientError> {
+        let track = track_index(track_id)?;
+        let mut state = self.state();
+        let played_at = state
+            .recently_played
+            .first()
+            .map_or(BASE_TIMESTAMP, |(_, played_at)| played_at + 60);
+        state.recently_played.insert(0, (track, played_at));
+        state.current_track = Some(track);
+        state.progress_ms = 0;
+        state.is_playing = true;
+        Ok(())
+    }
+
+    async fn resume(
+        &self,
+        _device_id: Option<&str>,
+        position: Option<TimeDelta>,
+    ) -> Result<(), ClientError> {
+        let mut state = self.state();
+        if let Some(position) = position {
+            state.progress_ms = position.num_milliseconds();
+        }
+        state.is_playing = true;
+        Ok(())
+    }
+
+    async fn pause(&self, _device_id: Option<&str>) -> Result<(), ClientError> {
+        self.state().is_playing = false;
+        Ok(())
+    }
+
+    async fn next_track(&self, _device_id: Option<&str>) -> Result<(), ClientError> {
+        let mut state = self.state();
+        state.current_track = Some(state.current_track.map_or(0, |t| (t + 1) % TRACKS.len()));
+        state.progress_ms = 0;
+        Ok(())
+    }
+
+    async fn previous_track(&self, _device_id: Option<&str>) -> Result<(), ClientError> {
+        let mut state = self.state();
+        state.current_track = Some(
+            state
+                .current_track
+                .map_or(0, |t| (t + TRACKS.len() - 1) % TRACKS.len()),
+        );
+        state.progress_ms = 0;
+        Ok(())
+    }
+
+    async fn repeat(
+        &self,
+        state: RepeatState,
+        _device_id: Option<&str>,
+    ) -> Result<(), ClientError> {
+        self.state().repeat = state;
+        Ok(())
+    }
+
+    async fn shuffle(&self, state: bool, _device_id: Option<&str>) -> Result<(), ClientError> {
+        self.state().shuffle = state;
+        Ok(())
+    }
+
+    async fn volume(
+        &self,
+        volume_percent: u8,
+        _device_id: Option<&str>,
+    ) -> Result<(), ClientError> {
+        self.state().volume = volume_percent.min(100);
+        Ok(())
+    }
+
+    async fn saved_tracks(&self) -> Result<Vec<SavedTrack>, ClientError> {
+        let state = self.state();
+        Ok(state
+            .liked_tracks
+            .iter()
+            .enumerate()
+            .map(|(i, &track)| SavedTrack {
+                added_at: timestamp(BASE_TIMESTAMP - 86_400 * i as i64),
+                track: full_track(track),
+            })
+            .collect())
+    }
+
+    async fn save_track(&self, track_id: &str) -> Result<(), ClientError> {
+        let track = track_index(track_id)?;
+        let mut state = self.state();
+        if !state.liked_tracks.contains(&track) {
+            state.liked_tracks.insert(0, track);
+        }
+        Ok(())
+    }
+
+    async fn recently_played(&self, limit: u32) -> Result<Vec<PlayHistory>, ClientError> {
+        let state = self.state();
+        Ok(state
+            .recently_played
+            .iter()
+            .take(limit as usize)
+            .map(|&(track, played_at)| PlayHistory {
+                track: full_track(track),
+                played_at: timestamp(played_at),
+                context: None,
+            })
+            .collect())
+    }
+
+    async fn saved_albums(&self) -> Result<Vec<SavedAlbum>, ClientError> {
+        let state = self.state();
+        Ok(state
+            .saved_albums
+            .iter()
+            .enumerate()
+            .map(|(i, &album)| SavedAlbum {
+                added_at: timestamp(BASE_TIMESTAMP - 86_400 * i as i64),
+                album: full_album(album),
+            })
+            .collect())
+    }
+
+    async fn followed_artists(&self, limit: u32) -> Result<Vec<FullArtist>, ClientError> {
+        let state = self.state();
+        Ok(state
+            .followed_artists
+            .iter()
+            .take(limit as usize)
+            .map(|&artist| full_artist(artist))
+            .collect())
+    }
+
+    async fn saved_shows(&self) -> Result<Vec<Show>, ClientError> {
+        let state = self.state();
+        Ok(state
+            .saved_shows
+            .iter()
+            .map(|&show| Show {
+                added_at: timestamp(BASE_TIMESTAMP).to_rfc3339(),
+                show: simplified_show(show),
+            })
+            .collect())
+    }
+
+    async fn category_playlists(
+        &self,
+        category_id: &str,
+    ) -> Result<Vec<SimplifiedPlaylist>, ClientError> {
+        if category_id != MADE_FY_CATEGORY {
+            return Err(not_found("category", category_id));
+        }
+        let state = self.state();
+        Ok(state
+            .playlists
+            .iter()
+            .filter(|playlist| playlist.made_fy)
+            .map(simplified_playlist)
+            .collect())
+    }
+
+    async fn top_tracks(
+        &self,
+        time_range: TimeRange,
+        limit: usize,
+    ) -> Result<Vec<FullTrack>, ClientError> {
+        // Each time range starts at a different track so the three lists can be told apart
+        let offset = match time_range {
+            TimeRange::LongTerm => 0,
+            TimeRange::MediumTerm => 4,
+            TimeRange::ShortTerm => 8,
+        };
+        Ok((0..TRACKS.len())
+            .map(|i| full_track((i + offset) % TRACKS.len()))
+            .take(limit)
+            .collect())
+    }
+
+    async fn new_releases(&self) -> Result<Vec<SimplifiedAlbum>, ClientError> {
+        Ok((0..ALBUMS.len()).rev().map(simplified_album).collect())
+    }
+
+    async fn album_tracks(
+        &self,
+        album_id: &str,
+        _market: Option<Market>,
+    ) -> Result<Vec<SimplifiedTrack>, ClientError> {
+        let album = ALBUMS
+            .iter()
+            .position(|album| album.0 == album_id)
+            .ok_or_else(|| not_found("album", album_id))?;
+        Ok(album_track_indexes(album)
+            .into_iter()
+            .map(simplified_track)
+            .collect())
+    }
+
+    async fn artist_top_tracks(
+        &self,
+        artist_id: &str,
+        _market: Option<Market>,
+    ) -> Result<Vec<FullTrack>, ClientError> {
+        let artist = ARTISTS
+            .iter()
+            .position(|artist| artist.0 == artist_id)
+            .ok_or_else(|| not_found("artist", artist_id))?;
+        Ok((0..TRACKS.len())
+            .filter(|&track| ALBUMS[TRACKS[track].2].2 == artist)
+            .map(full_track)
+            .collect())
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        search_type: SearchType,
+        _market: Option<Market>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<SearchResult, ClientError> {
+        // The field filters aren't matched, only the text
+        let query = normalize(&free_text(query));
+        let matches = |name: &str| name.to_lowercase().contains(&query);
+
+        Ok(match search_type {
+            SearchType::Album => SearchResult::Albums(page_at(
+                (0..ALBUMS.len())
+                    .filter(|&album| matches(ALBUMS[album].1))
+                    .map(simplified_album)
+                    .collect(),
+                limit,
+                offset,
+            )),
+            SearchType::Artist => SearchResult::Artists(page_at(
+                (0..ARTISTS.len())
+                    .filter(|&artist| matches(ARTISTS[artist].1))
+                    .map(full_artist)
+                    .collect(),
+                limit,
+                offset,
+            )),
+            SearchType::Track => SearchResult::Tracks(page_at(
+                (0..TRACKS.len())
+                    .filter(|&track| matches(TRACKS[track].1))
+                    .map(full_track)
+                    .collect(),
+                limit,
+                offset,
+            )),
+            SearchType::Playlist => SearchResult::Playlists(page_at(
+                self.state()
+                    .playlists
+                    .iter()
+                    .filter(|playlist| matches(&playlist.name))
+                    .map(simplified_playlist)
+                    .collect(),
+                limit,
+                offset,
+            )),
+            SearchType::Show => SearchResult::Shows(page_at(
+                (0..SHOWS.len())
+                    .filter(|&show| matches(SHOWS[show].1))
+                    .map(simplified_show)
+                    .collect(),
+                limit,
+                offset,
+            )),
+            SearchType::Episode => SearchResult::Episodes(page_at(Vec::new(), limit, offset)),
+        })
+    }
+
+    async fn user_playlists(&self) -> Result<Vec<SimplifiedPlaylist>, ClientError> {
+        let state = self.state();
+        Ok(state
+            .playlists
+            .iter()
+            .filter(|playlist| playlist.followed)
+            .map(simplified_playlist)
+            .collect())
+    }
+
+    async fn playlist_items(&self, playlist_id: &str) -> Result<Vec<PlaylistItem>, ClientError> {
+        let state = self.state();
+        let playlist = state
+            .playlists
+            .iter()
+            .find(|playlist| playlist.id == playlist_id)
+            .ok_or_else(|| not_found("playlist", playlist_id))?;
+        Ok(playlist
+            .tracks
+            .iter()
+            .map(|&track| PlaylistItem {
+                added_at: Some(timestamp(BASE_TIMESTAMP)),
+                added_by: Some(owner()),
+                is_local: false,
+                track: Some(PlayableItem::Track(full_track(track))),
+            })
+            .collect())
+    }
+
+    async fn add_track_to_playlist(
+        &self,
+        playlist_id: &str,
+        track_id: &str,
+    ) -> Result<(), ClientError> {
+        let track = track_index(track_id)?;
+        let mut state = self.state();
+        let playlist = state
+            .playlists
+            .iter_mut()
+            .find(|playlist| playlist.id == playlist_id)
+            .ok_or_else(|| not_found("playlist", playlist_id))?;
+        playlist.tracks.insert(0, track);
+        Ok(())
+    }
+
+    async fn follow_playlist(&self, playlist_id: &str) -> Result<(), ClientError> {
+        self.set_followed(playlist_id, true)
+    }
+
+    async fn unfollow_playlist(&self, playlist_id: &str) -> Result<(), ClientError> {
+        self.set_followed(playlist_id, false)
+    }
+
+    async fn current_user(&self) -> Result<PrivateUser, ClientError> {
+        let owner = owner();
+        Ok(PrivateUser {
+            country: Some(Country::Germany),
+            display_name: owner.display_name,
+            email: Some("listener@spoify.invalid".to_string()),
+            external_urls: owner.external_urls,
+            explicit_content: None,
+            followers: None,
+            href: owner.href,
+            id: owner.id,
+            images: None,
+            product: Some(SubscriptionLevel::Premium),
+        })
+    }
+
+    // A token that never runs out, holding every scope spoify asks for
+    async fn token(&self) -> Result<Option<Token>, ClientError> {
+        Ok(Some(Token {
+            access_token: "fake-access-token".to_string(),
+            expires_in: TimeDelta::hours(1),
+            expires_at: Some(Utc::now() + TimeDelta::hours(1)),
+            refresh_token: Some("fake-refresh-token".to_string()),
+            scopes: requested_scopes(),
+        }))
+    }
+}
+
+impl FakeBackend {
+    fn set_followed(&self, playlist_id: &str, followed: bool) -> Result<(), ClientError> {
+        let mut state = self.state();
+        let playlist = state
+            .playlists
+            .iter_mut()
+            .find(|playlist| playlist.id == playlist_id)
+            .ok_or_else(|| not_found("playlist", playlist_id))?;
+        playlist.followed = followed;
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+// This section defines every Spotify operation the app needs, so the data source can be swapped
+pub mod fake;
+pub mod request;
+pub mod rspotify_backend;
+
+use crate::app::App;
+use crate::spotify::auth::{build_spotify_client, LoginPrompt, TerminalPrompt};
+use async_trait::async_trait;
+use chrono::TimeDelta;
+use rspotify::model::{
+    CurrentPlaybackContext, FullArtist, FullTrack, IdError, Market, PlayHistory, PlaylistItem,
+    PrivateUser, RepeatState, SavedAlbum, SavedTrack, SearchResult, SearchType, Show,
+    SimplifiedAlbum, SimplifiedPlaylist, SimplifiedTrack, TimeRange, Token,
+};
+use rspotify::ClientError;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::{env, io};
+
+use self::fake::FakeBackend;
+use self::request::RequestLayer;
+
+/// Operations for playback, the user's library, search and playlists
+///
+/// Ids are the bare Spotify ids (not URIs or URLs).
+#[async_trait]
+pub trait SpotifyBackend: Debug + Send + Sync {
+    // Playback
+    async fn current_playback(&self) -> Result<Option<CurrentPlaybackContext>, ClientError>;
+    async fn start_track(&self, track_id: &str, device_id: Option<&str>)
+        -> Result<(), ClientError>;
+    async fn resume(
+        &self,
+        device_id: Option<&str>,
+        position: Option<TimeDelta>,
+    ) -> Result<(), ClientError>;
+    async fn pause(&self, device_id: Option<&str>) -> Result<(), ClientError>;
+    async fn next_track(&self, device_id: Option<&str>) -> Result<(), ClientError>;
+    async fn previous_track(&self, device_id: Option<&str>) -> Result<(), ClientError>;
+    async fn repeat(&self, state: RepeatState, device_id: Option<&str>) -> Result<(), ClientError>;
+    async fn shuffle(&self, state: bool, device_id: Option<&str>) -> Result<(), ClientError>;
+    async fn volume(&self, volume_percent: u8, device_id: Option<&str>) -> Result<(), ClientError>;
+
+    // Library
+    async fn saved_tracks(&self) -> Result<Vec<SavedTrack>, ClientError>;
+    async fn save_track(&self, track_id: &str) -> Result<(), ClientError>;
+    async fn recently_played(&self, limit: u32) -> Result<Vec<PlayHistory>, ClientError>;
+    async fn saved_albums(&self) -> Result<Vec<SavedAlbum>, ClientError>;
+    async fn followed_artists(&self, limit: u32) -> Result<Vec<FullArtist>, ClientError>;
+    async fn saved_shows(&self) -> Result<Vec<Show>, ClientError>;
+    async fn category_playlists(
+        &self,
+        category_id: &str,
+    ) -> Result<Vec<SimplifiedPlaylist>, ClientError>;
+    async fn top_tracks(
+        &self,
+        time_range: TimeRange,
+        limit: usize,
+    ) -> Result<Vec<FullTrack>, ClientError>;
+    async fn new_releases(&self) -> Result<Vec<SimplifiedAlbum>, ClientError>;
+    // With a market Spotify marks the tracks that can't be played there
+    async fn album_tracks(
+        &self,
+        album_id: &str,
+        market: Option<Market>,
+    ) -> Result<Vec<SimplifiedTrack>, ClientError>;
+    async fn artist_top_tracks(
+        &self,
+        artist_id: &str,
+        market: Option<Market>,
+    ) -> Result<Vec<FullTrack>, ClientError>;
+
+    // Search
+    async fn search(
+        &self,
+        query: &str,
+        search_type: SearchType,
+        market: Option<Market>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<SearchResult, ClientError>;
+
+    // Playlists
+    async fn user_playlists(&self) -> Result<Vec<SimplifiedPlaylist>, ClientError>;
+    async fn playlist_items(&self, playlist_id: &str) -> Result<Vec<PlaylistItem>, ClientError>;
+    async fn add_track_to_playlist(
+        &self,
+        playlist_id: &str,
+        track_id: &str,
+    ) -> Result<(), ClientError>;
+    async fn follow_playlist(&self, playlist_id: &str) -> Result<(), ClientError>;
+    async fn unfollow_playlist(&self, playlist_id: &str) -> Result<(), ClientError>;
+
+    // Account
+    async fn current_user(&self) -> Result<PrivateUser, ClientError>;
+    /// The token the requests are sent with, None before the login
+    async fn token(&self) -> Result<Option<Token>, ClientError>;
+}
+
+/// Returns true when `SPOIFY_BACKEND=fake` asks for the in-memory backend
+pub fn fake_backend_requested() -> bool {
+    env::var("SPOIFY_BACKEND").is_ok_and(|backend| backend == "fake")
+}
+
+/// Creates the backend for this session, either the in-memory one or an authenticated Spotify client
+///
+/// Either way its requests go through the request layer, which reports rate limits to `app.requests`.
+pub async fn build_backend(app: &App) -> Result<Arc<dyn SpotifyBackend>, ClientError> {
+    build_backend_with(app, &TerminalPrompt).await
+}
+
+/// Creates the backend like `build_backend`, a login it needs asks the user through `prompt`
+pub async fn build_backend_with(
+    app: &App,
+    prompt: &dyn LoginPrompt,
+) -> Result<Arc<dyn SpotifyBackend>, ClientError> {
+    let inner: Arc<dyn SpotifyBackend> = if fake_backend_requested() {
+        Arc::new(FakeBackend::new())
+    } else {
+        build_spotify_client(app, prompt).await?
+    };
+    Ok(Arc::new(RequestLayer::new(
+        inner,
+        app.requests.clone(),
+        app.log.clone(),
+    )))
+}
+
+/// Gets the backend shared by the app
+///
+/// The interface and the commands set it up once logged in, so a request made without it fails instead of
+/// starting a login of its own.
+pub async fn get_backend(app: &App) -> Result<Arc<dyn SpotifyBackend>, ClientError> {
+    app.backend.clone().ok_or_else(|| {
+        ClientError::Cli("Not logged in to Spotify, log in from the account screen".to_string())
+    })
+}
+
+// Reports an id that Spotify would reject as an error instead of panicking
+pub(crate) fn invalid_id(err: IdError) -> ClientError {
+    ClientError::Io(io::Error::new(io::ErrorKind::InvalidInput, err))
+}
@@ -0,0 +1,710 @@
+// Every Spotify request goes through this layer, it waits out rate limits and retries reads that failed on the way
+use async_trait::async_trait;
+use chrono::TimeDelta;
+use reqwest::header::RETRY_AFTER;
+use rspotify::http::HttpError;
+use rspotify::model::{
+    CurrentPlaybackContext, FullArtist, FullTrack, Market, PlayHistory, PlaylistItem, PrivateUser,
+    RepeatState, SavedAlbum, SavedTrack, SearchResult, SearchType, Show, SimplifiedAlbum,
+    SimplifiedPlaylist, SimplifiedTrack, TimeRange, Token,
+};
+use rspotify::ClientError;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::SpotifyBackend;
+use crate::enums::Severity;
+use crate::event_log::EventLog;
+
+// Attempts made for a read before its error is reported, the first one included
+const MAX_ATTEMPTS: u32 = 4;
+// Wait before the first retry of a read, doubled for every retry after it
+const FIRST_BACKOFF: Duration = Duration::from_millis(500);
+// Used when a rate limited response doesn't say how long to wait
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+// Longer rate limits fail the request right away instead of keeping the task waiting
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(30);
+// Path of the accounts service endpoint that hands out and refreshes tokens
+const TOKEN_PATH: &str = "/api/token";
+
+/// A failed request, told apart so the user sees what went wrong instead of a status code
+#[derive(Clone, Debug, PartialEq)]
+pub enum RequestError {
+    /// Spotify couldn't be reached
+    Offline,
+    /// Spotify kept failing on its side
+    Unavailable,
+    /// Spotify asked to wait this long before sending more requests
+    RateLimited(Duration),
+    /// A player command was sent while no device is playing
+    NoActiveDevice,
+    /// The token was revoked or couldn't be refreshed, only a new login helps
+    LoginExpired,
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::Offline => write!(f, "Spotify can't be reached, check the connection"),
+            RequestError::Unavailable => {
+                write!(f, "Spotify is having problems right now, try again later")
+            }
+            RequestError::RateLimited(wait) => write!(
+                f,
+                "Spotify is limiting the requests, try again in {}s",
+                wait.as_secs().max(1)
+            ),
+            RequestError::NoActiveDevice => {
+                write!(
+                    f,
+                    "No active device, start playing on a Spotify device first"
+                )
+            }
+            RequestError::LoginExpired => {
+                write!(f, "Spotify no longer accepts the login, log in again")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+/// Returns the request error behind `err`, if the request layer reported one
+pub fn request_error(err: &ClientError) -> Option<&RequestError> {
+    match err {
+        ClientError::Io(err) => err.get_ref()?.downcast_ref(),
+        _ => None,
+    }
+}
+
+/// The message shown for `err`, without the wrapping needed to pass it as a `ClientError`
+pub fn describe(err: &ClientError) -> String {
+    if let ClientError::Cli(message) = err {
+        return message.clone();
+    }
+    match request_error(err) {
+        Some(err) => err.to_string(),
+        // The login doesn't go through the layer, so its connection errors are told apart here
+        None if matches!(classify(err, Kind::Read), Failure::Offline) => {
+            RequestError::Offline.to_string()
+        }
+        None => err.to_string(),
+    }
+}
+
+/// Whether Spotify is rate limiting the requests right now
+///
+/// Clones share the same state, so the UI sees the limits hit by the background tasks.
+#[derive(Clone, Debug, Default)]
+pub struct RequestStatus {
+    rate_limited_until: Arc<Mutex<Option<Instant>>>,
+}
+
+impl RequestStatus {
+    fn until(&self) -> MutexGuard<'_, Option<Instant>> {
+        self.rate_limited_until
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// How long the requests are still held back, None once they can be sent again
+    pub fn rate_limited_for(&self) -> Option<Duration> {
+        let until = (*self.until())?;
+        let left = until.saturating_duration_since(Instant::now());
+        (!left.is_zero()).then_some(left)
+    }
+
+    fn rate_limit(&self, wait: Duration) {
+        let until = Instant::now() + wait;
+        let mut current = self.until();
+        if current.is_none_or(|current| current < until) {
+            *current = Some(until);
+        }
+    }
+}
+
+// What a request does, which decides what is safe to retry
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    // Reads don't change anything, so sending them twice is harmless
+    Read,
+    // Player commands need a device, and skipping a track twice isn't the same as once
+    Player,
+    Write,
+}
+
+// What went wrong with a single attempt
+enum Failure {
+    Offline,
+    Server,
+    RateLimited(Duration),
+    NoActiveDevice,
+    LoginExpired,
+    Other,
+}
+
+fn classify(err: &ClientError, kind: Kind) -> Failure {
+    let ClientError::Http(err) = err else {
+        return Failure::Other;
+    };
+    match err.as_ref() {
+        HttpError::Client(err) if err.is_connect() || err.is_timeout() || err.is_request() => {
+            Failure::Offline
+        }
+        // rspotify refreshes an expired token before the request, a refresh Spotify turns down ends up here
+        HttpError::StatusCode(response)
+            if response.url().path() == TOKEN_PATH
+                && matches!(response.status().as_u16(), 400 | 401) =>
+        {
+            Failure::LoginExpired
+        }
+        HttpError::StatusCode(response) => match response.status().as_u16() {
+            401 => Failure::LoginExpired,
+            429 => Failure::RateLimited(
+                response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.trim().parse().ok())
+                    .map(Duration::from_secs)
+                    .unwrap_or(DEFAULT_RETRY_AFTER),
+            ),
+            404 if kind == Kind::Player => Failure::NoActiveDevice,
+            500.. => Failure::Server,
+            _ => Failure::Other,
+        },
+        _ => Failure::Other,
+    }
+}
+
+fn request_failed(err: RequestError) -> ClientError {
+    ClientError::Io(io::Error::other(err))
+}
+
+/// Wraps a backend so its requests honour `Retry-After` and reads are retried with exponential backoff
+#[derive(Debug)]
+pub struct RequestLayer {
+    inner: Arc<dyn SpotifyBackend>,
+    status: RequestStatus,
+    log: EventLog,
+}
+
+impl RequestLayer {
+    pub fn new(inner: Arc<dyn SpotifyBackend>, status: RequestStatus, log: EventLog) -> Self {
+        Self { inner, status, log }
+    }
+
+    async fn send<T, F, Fut>(&self, kind: Kind, request: F) -> Result<T, ClientError>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T, ClientError>> + Send,
+        T: Send,
+    {
+        let mut backoff = FIRST_BACKOFF;
+        let mut attempt = 1;
+
+        loop {
+            // A rate limit hit by another request holds this one back too
+            if let Some(wait) = self.status.rate_limited_for() {
+                if wait > MAX_RATE_LIMIT_WAIT {
+                    return Err(request_failed(RequestError::RateLimited(wait)));
+                }
+                tokio::time::sleep(wait).await;
+            }
+
+            let err = match request().await {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+
+            let last_attempt = attempt >= MAX_ATTEMPTS;
+            match classify(&err, kind) {
+                // A rate limited request was never carried out, so any kind of request can be sent again
+                Failure::RateLimited(wait) => {
+                    self.status.rate_limit(wait);
+                    self.log.record(
+                        Severity::Warning,
+                        format!(
+                            "Spotify is limiting the requests, waiting {}s",
+                            wait.as_secs()
+                        ),
+                    );
+                    if last_attempt || wait > MAX_RATE_LIMIT_WAIT {
+                        return Err(request_failed(RequestError::RateLimited(wait)));
+                    }
+                }
+                Failure::Offline | Failure::Server if kind == Kind::Read && !last_attempt => {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Failure::Offline => return Err(request_failed(RequestError::Offline)),
+                Failure::Server => return Err(request_failed(RequestError::Unavailable)),
+                Failure::NoActiveDevice => {
+                    return Err(request_failed(RequestError::NoActiveDevice))
+                }
+                Failure::LoginExpired => return Err(request_failed(RequestError::LoginExpired)),
+                Failure::Other => return Err(err),
+            }
+            attempt += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl SpotifyBackend for RequestLayer {
+    async fn current_playback(&self) -> Result<Option<CurrentPlaybackContext>, ClientError> {
+        self.send(Kind::Read, || self.inner.current_playback())
+            .await
+    }
+
+    async fn start_track(
+        &self,
+        track_id: &str,
+        device_id: Option<&str>,
+    ) -> Result<(), ClientError> {
+        self.send(Kind::Player, || self.inner.start_track(track_id, device_id))
+            .await
+    }
+
+    async fn resume(
+        &self,
+        device_id: Option<&str>,
+        position: Option<TimeDelta>,
+    ) -> Result<(), ClientError> {
+        self.send(Kind::Player, || self.inner.resume(device_id, position))
+            .await
+    }
+
+    async fn pause(&self, device_id: Option<&str>) -> Result<(), ClientError> {
+        self.send(Kind::Player, || self.inner.pause(device_id))
+            .await
+    }
+
+    async fn next_track(&self, device_id: Option<&str>) -> Result<(), ClientError> {
+        self.send(Kind::Player, || self.inner.next_track(device_id))
+            .await
+    }
+
+    async fn previous_track(&self, device_id: Option<&str>) -> Result<(), ClientError> {
+        self.send(Kind::Player, || self.inner.previous_track(device_id))
+            .await
+    }
+
+    async fn repeat(&self, state: RepeatState, device_id: Option<&str>) -> Result<(), ClientError> {
+        self.send(Kind::Player, || self.inner.repeat(state, device_id))
+            .await
+    }
+
+    async fn shuffle(&self, state: bool, device_id: Option<&str>) -> Result<(), ClientError> {
+        self.send(Kind::Player, || self.inner.shuffle(state, device_id))
+            .await
+    }
+
+    async fn volume(&self, volume_percent: u8, device_id: Option<&str>) -> Result<(), ClientError> {
+        self.send(Kind::Player, || {
+            self.inner.volume(volume_percent, device_id)
+        })
+        .await
+    }
+
+    async fn saved_tracks(&self) -> Result<Vec<SavedTrack>, ClientError> {
+        self.send(Kind::Read, || self.inner.saved_tracks()).await
+    }
+
+    async fn save_track(&self, track_id: &str) -> Result<(), ClientError> {
+        self.send(Kind::Write, || self.inner.save_track(track_id))
+            .await
+    }
+
+    async fn recently_played(&self, limit: u32) -> Result<Vec<PlayHistory>, ClientError> {
+        self.send(Kind::Read, || self.inner.recently_played(limit))
+            .await
+    }
+
+    async fn saved_albums(&self) -> Result<Vec<SavedAlbum>, ClientError> {
+        self.send(Kind::Read, || self.inner.saved_albums()).await
+    }
+
+    async fn followed_artists(&self, limit: u32) -> Result<Vec<FullArtist>, ClientError> {
+        self.send(Kind::Read, || self.inner.followed_artists(limit))
+            .await
+    }
+
+    async fn saved_shows(&self) -> Result<Vec<Show>, ClientError> {
+        self.send(Kind::Read, || self.inner.saved_shows()).await
+    }
+
+    async fn category_playlists(
+        &self,
+        category_id: &str,
+    ) -> Result<Vec<SimplifiedPlaylist>, ClientError> {
+        self.send(Kind::Read, || self.inner.category_playlists(category_id))
+            .await
+    }
+
+    async fn top_tracks(
+        &self,
+        time_range: TimeRange,
+        limit: usize,
+    ) -> Result<Vec<FullTrack>, ClientError> {
+        self.send(Kind::Read, || self.inner.top_tracks(time_range, limit))
+            .await
+    }
+
+    async fn new_releases(&self) -> Result<Vec<SimplifiedAlbum>, ClientError> {
+        self.send(Kind::Read, || self.inner.new_releases()).await
+    }
+
+    async fn album_tracks(
+        &self,
+        album_id: &str,
+        market: Option<Market>,
+    ) -> Result<Vec<SimplifiedTrack>, ClientError> {
+        self.send(Kind::Read, || self.inner.album_tracks(album_id, market))
+            .await
+    }
+
+    async fn artist_top_tracks(
+        &self,
+        artist_id: &str,
+        market: Option<Market>,
+    ) -> Result<Vec<FullTrack>, ClientError> {
+        self.send(Kind::Read, || {
+            self.inner.artist_top_tracks(artist_id, market)
+        })
+        .await
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        search_type: SearchType,
+        market: Option<Market>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<SearchResult, ClientError> {
+        self.send(Kind::Read, || {
+            self.inner.search(query, search_type, market, limit, offset)
+        })
+        .await
+    }
+
+    async fn user_playlists(&self) -> Result<Vec<SimplifiedPlaylist>, ClientError> {
+        self.send(Kind::Read, || self.inner.user_playlists()).await
+    }
+
+    async fn playlist_items(&self, playlist_id: &str) -> Result<Vec<PlaylistItem>, ClientError> {
+        self.send(Kind::Read, || self.inner.playlist_items(playlist_id))
+            .await
+    }
+
+    async fn add_track_to_playlist(
+        &self,
+        playlist_id: &str,
+        track_id: &str,
+    ) -> Result<(), ClientError> {
+        self.send(Kind::Write, || {
+            self.inner.add_track_to_playlist(playlist_id, track_id)
+        })
+        .await
+    }
+
+    async fn follow_playlist(&self, playlist_id: &str) -> Result<(), ClientError> {
+        self.send(Kind::Write, || self.inner.follow_playlist(playlist_id))
+            .await
+    }
+
+    async fn unfollow_playlist(&self, playlist_id: &str) -> Result<(), ClientError> {
+        self.send(Kind::Write, || self.inner.unfollow_playlist(playlist_id))
+            .await
+    }
+
+    async fn current_user(&self) -> Result<PrivateUser, ClientError> {
+        self.send(Kind::Read, || self.inner.current_user()).await
+    }
+
+    // Reading the token doesn't send a request
+    async fn token(&self) -> Result<Option<Token>, ClientError> {
+        self.inner.token().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::ResponseBuilderExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use url::Url;
+
+    // Answers the first `failures` requests with the same error response, the ones after it succeed
+    #[derive(Debug)]
+    struct FailingBackend {
+        status: u16,
+        url: &'static str,
+        retry_after: Option<&'static str>,
+        failures: u32,
+        calls: AtomicU32,
+    }
+
+    impl FailingBackend {
+        fn new(status: u16, failures: u32) -> Self {
+            Self {
+                status,
+                url: "https://api.spotify.com/v1/me/player",
+                retry_after: None,
+                failures,
+                calls: AtomicU32::new(0),
+            }
+        }
+
+        fn respond(&self) -> Result<(), ClientError> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) >= self.failures {
+                return Ok(());
+            }
+            Err(self.failure())
+        }
+
+        // The error response the backend answers with
+        fn failure(&self) -> ClientError {
+            let mut response = http::Response::builder()
+                .status(self.status)
+                .url(Url::parse(self.url).unwrap());
+            if let Some(retry_after) = self.retry_after {
+                response = response.header(RETRY_AFTER, retry_after);
+            }
+            let response = reqwest::Response::from(response.body("").unwrap());
+            ClientError::Http(Box::new(HttpError::StatusCode(response)))
+        }
+    }
+
+    #[async_trait]
+    impl SpotifyBackend for FailingBackend {
+        async fn current_playback(&self) -> Result<Option<CurrentPlaybackContext>, ClientError> {
+            self.respond().map(|_| None)
+        }
+        async fn start_track(&self, _: &str, _: Option<&str>) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn resume(&self, _: Option<&str>, _: Option<TimeDelta>) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn pause(&self, _: Option<&str>) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn next_track(&self, _: Option<&str>) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn previous_track(&self, _: Option<&str>) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn repeat(&self, _: RepeatState, _: Option<&str>) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn shuffle(&self, _: bool, _: Option<&str>) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn volume(&self, _: u8, _: Option<&str>) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn saved_tracks(&self) -> Result<Vec<SavedTrack>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn save_track(&self, _: &str) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn recently_played(&self, _: u32) -> Result<Vec<PlayHistory>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn saved_albums(&self) -> Result<Vec<SavedAlbum>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn followed_artists(&self, _: u32) -> Result<Vec<FullArtist>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn saved_shows(&self) -> Result<Vec<Show>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn category_playlists(
+            &self,
+            _: &str,
+        ) -> Result<Vec<SimplifiedPlaylist>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn top_tracks(&self, _: TimeRange, _: usize) -> Result<Vec<FullTrack>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn new_releases(&self) -> Result<Vec<SimplifiedAlbum>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn album_tracks(
+            &self,
+            _: &str,
+            _: Option<Market>,
+        ) -> Result<Vec<SimplifiedTrack>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn artist_top_tracks(
+            &self,
+            _: &str,
+            _: Option<Market>,
+        ) -> Result<Vec<FullTrack>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn search(
+            &self,
+            _: &str,
+            _: SearchType,
+            _: Option<Market>,
+            _: u32,
+            _: u32,
+        ) -> Result<SearchResult, ClientError> {
+            // There are no results to make up, so the search always fails
+            Err(self.failure())
+        }
+        async fn user_playlists(&self) -> Result<Vec<SimplifiedPlaylist>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn playlist_items(&self, _: &str) -> Result<Vec<PlaylistItem>, ClientError> {
+            self.respond().map(|_| Vec::new())
+        }
+        async fn add_track_to_playlist(&self, _: &str, _: &str) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn follow_playlist(&self, _: &str) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn unfollow_playlist(&self, _: &str) -> Result<(), ClientError> {
+            self.respond()
+        }
+        async fn current_user(&self) -> Result<PrivateUser, ClientError> {
+            // Nor is there a user
+            Err(self.failure())
+        }
+        async fn token(&self) -> Result<Option<Token>, ClientError> {
+            Ok(None)
+        }
+    }
+
+    fn layer_over(backend: &Arc<FailingBackend>) -> (RequestLayer, RequestStatus) {
+        let status = RequestStatus::default();
+        let layer = RequestLayer::new(backend.clone(), status.clone(), EventLog::default());
+        (layer, status)
+    }
+
+    fn calls(backend: &FailingBackend) -> u32 {
+        backend.calls.load(Ordering::SeqCst)
+    }
+
+    fn failure(result: Result<(), ClientError>) -> Option<RequestError> {
+        request_error(&result.unwrap_err()).cloned()
+    }
+
+    // The clock is paused, so the waits pass right away and are measured on tokio's clock
+    #[tokio::test(start_paused = true)]
+    async fn retry_after_is_waited_out_and_shared() {
+        let backend = Arc::new(FailingBackend {
+            retry_after: Some("3"),
+            ..FailingBackend::new(429, 1)
+        });
+        let (layer, status) = layer_over(&backend);
+
+        let started = tokio::time::Instant::now();
+        layer.pause(None).await.unwrap();
+
+        assert_eq!(calls(&backend), 2);
+        assert!(started.elapsed() >= Duration::from_secs(3));
+        // The other requests see the limit that was hit
+        let left = status.rate_limited_for().unwrap();
+        assert!(left > Duration::from_secs(2) && left <= Duration::from_secs(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_long_rate_limit_fails_right_away() {
+        let backend = Arc::new(FailingBackend {
+            retry_after: Some("120"),
+            ..FailingBackend::new(429, MAX_ATTEMPTS)
+        });
+        let (layer, status) = layer_over(&backend);
+
+        assert_eq!(
+            failure(layer.save_track("id").await),
+            Some(RequestError::RateLimited(Duration::from_secs(120)))
+        );
+        assert_eq!(calls(&backend), 1);
+        assert!(status.rate_limited_for().is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reads_are_retried_until_the_last_attempt() {
+        let backend = Arc::new(FailingBackend::new(503, MAX_ATTEMPTS));
+        let (layer, _) = layer_over(&backend);
+
+        let err = layer.saved_tracks().await.unwrap_err();
+        assert_eq!(request_error(&err), Some(&RequestError::Unavailable));
+        assert_eq!(calls(&backend), MAX_ATTEMPTS);
+
+        // A read that goes through on its last attempt succeeds
+        let backend = Arc::new(FailingBackend::new(500, MAX_ATTEMPTS - 1));
+        let (layer, _) = layer_over(&backend);
+        assert!(layer.saved_albums().await.is_ok());
+        assert_eq!(calls(&backend), MAX_ATTEMPTS);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn player_commands_and_writes_are_sent_once() {
+        let backend = Arc::new(FailingBackend::new(502, MAX_ATTEMPTS));
+        let (layer, _) = layer_over(&backend);
+        assert_eq!(
+            failure(layer.next_track(None).await),
+            Some(RequestError::Unavailable)
+        );
+        assert_eq!(calls(&backend), 1);
+
+        let backend = Arc::new(FailingBackend::new(500, MAX_ATTEMPTS));
+        let (layer, _) = layer_over(&backend);
+        assert_eq!(
+            failure(layer.add_track_to_playlist("playlist", "track").await),
+            Some(RequestError::Unavailable)
+        );
+        assert_eq!(calls(&backend), 1);
+    }
+
+    #[tokio::test]
+    async fn a_player_404_means_no_active_device() {
+        let backend = Arc::new(FailingBackend::new(404, 1));
+        let (layer, _) = layer_over(&backend);
+        assert_eq!(
+            failure(layer.resume(None, None).await),
+            Some(RequestError::NoActiveDevice)
+        );
+
+        // Anywhere else a 404 is passed on as it is
+        let backend = Arc::new(FailingBackend::new(404, 1));
+        let (layer, _) = layer_over(&backend);
+        let err = layer.follow_playlist("id").await.unwrap_err();
+        assert!(request_error(&err).is_none());
+    }
+
+    #[tokio::test]
+    async fn a_turned_down_token_refresh_means_the_login_expired() {
+        for status in [400, 401] {
+            let backend = Arc::new(FailingBackend {
+                url: "https://accounts.spotify.com/api/token",
+                ..FailingBackend::new(status, 1)
+            });
+            let (layer, _) = layer_over(&backend);
+            let err = layer.saved_tracks().await.unwrap_err();
+            assert_eq!(request_error(&err), Some(&RequestError::LoginExpired));
+            assert_eq!(calls(&backend), 1);
+        }
+
+        // A 400 from the Web API is a bad request, not a login problem
+        let backend = Arc::new(FailingBackend::new(400, 1));
+        let (layer, _) = layer_over(&backend);
+        let err = layer.saved_tracks().await.unwrap_err();
+        assert!(request_error(&err).is_none());
+    }
+}
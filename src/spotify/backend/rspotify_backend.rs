@@ -0,0 +1,220 @@
+use super::{invalid_id, SpotifyBackend};
+use async_trait::async_trait;
+use chrono::TimeDelta;
+use futures_util::TryStreamExt;
+use rspotify::model::{
+    AdditionalType, AlbumId, ArtistId, CurrentPlaybackContext, FullArtist, FullTrack, Market,
+    PlayHistory, PlayableId, PlaylistId, PlaylistItem, PrivateUser, RepeatState, SavedAlbum,
+    SavedTrack, SearchResult, SearchType, Show, SimplifiedAlbum, SimplifiedPlaylist,
+    SimplifiedTrack, TimeRange, Token, TrackId,
+};
+use rspotify::prelude::{BaseClient, OAuthClient};
+use rspotify::ClientError;
+use std::fmt::Debug;
+
+// The real backend, every call goes to the Spotify Web API
+//
+// It works with any of the rspotify clients that log in as a user, with or without PKCE.
+#[async_trait]
+impl<C> SpotifyBackend for C
+where
+    C: OAuthClient + Debug + Send + Sync,
+{
+    async fn current_playback(&self) -> Result<Option<CurrentPlaybackContext>, ClientError> {
+        OAuthClient::current_playback(
+            self,
+            Some(Market::FromToken),
+            Some(
+                [AdditionalType::Episode]
+                    .iter()
+                    .map(|x| x as &AdditionalType),
+            ),
+        )
+        .await
+    }
+
+    async fn start_track(
+        &self,
+        track_id: &str,
+        device_id: Option<&str>,
+    ) -> Result<(), ClientError> {
+        let track_id = TrackId::from_id(track_id).map_err(invalid_id)?;
+        let position = TimeDelta::milliseconds(0);
+        self.start_uris_playback(
+            vec![PlayableId::Track(track_id)],
+            device_id,
+            None,
+            Some(position),
+        )
+        .await
+    }
+
+    async fn resume(
+        &self,
+        device_id: Option<&str>,
+        position: Option<TimeDelta>,
+    ) -> Result<(), ClientError> {
+        self.resume_playback(device_id, position).await
+    }
+
+    async fn pause(&self, device_id: Option<&str>) -> Result<(), ClientError> {
+        self.pause_playback(device_id).await
+    }
+
+    async fn next_track(&self, device_id: Option<&str>) -> Result<(), ClientError> {
+        OAuthClient::next_track(self, device_id).await
+    }
+
+    async fn previous_track(&self, device_id: Option<&str>) -> Result<(), ClientError> {
+        OAuthClient::previous_track(self, device_id).await
+    }
+
+    async fn repeat(&self, state: RepeatState, device_id: Option<&str>) -> Result<(), ClientError> {
+        OAuthClient::repeat(self, state, device_id).await
+    }
+
+    async fn shuffle(&self, state: bool, device_id: Option<&str>) -> Result<(), ClientError> {
+        OAuthClient::shuffle(self, state, device_id).await
+    }
+
+    async fn volume(&self, volume_percent: u8, device_id: Option<&str>) -> Result<(), ClientError> {
+        OAuthClient::volume(self, volume_percent, device_id).await
+    }
+
+    async fn saved_tracks(&self) -> Result<Vec<SavedTrack>, ClientError> {
+        self.current_user_saved_tracks(None).try_collect().await
+    }
+
+    async fn save_track(&self, track_id: &str) -> Result<(), ClientError> {
+        let track_id = TrackId::from_id(track_id).map_err(invalid_id)?;
+        self.current_user_saved_tracks_add([track_id]).await
+    }
+
+    async fn recently_played(&self, limit: u32) -> Result<Vec<PlayHistory>, ClientError> {
+        let page = self.current_user_recently_played(Some(limit), None).await?;
+        Ok(page.items)
+    }
+
+    async fn saved_albums(&self) -> Result<Vec<SavedAlbum>, ClientError> {
+        self.current_user_saved_albums(None).try_collect().await
+    }
+
+    async fn followed_artists(&self, limit: u32) -> Result<Vec<FullArtist>, ClientError> {
+        let page = self
+            .current_user_followed_artists(None, Some(limit))
+            .await?;
+        Ok(page.items)
+    }
+
+    async fn saved_shows(&self) -> Result<Vec<Show>, ClientError> {
+        self.get_saved_show().try_collect().await
+    }
+
+    async fn category_playlists(
+        &self,
+        category_id: &str,
+    ) -> Result<Vec<SimplifiedPlaylist>, ClientError> {
+        BaseClient::category_playlists(self, category_id, None)
+            .try_collect()
+            .await
+    }
+
+    async fn top_tracks(
+        &self,
+        time_range: TimeRange,
+        limit: usize,
+    ) -> Result<Vec<FullTrack>, ClientError> {
+        let mut top_tracks = Vec::new();
+        let mut stream = self.current_user_top_tracks(Some(time_range));
+        while top_tracks.len() < limit {
+            match stream.try_next().await? {
+                Some(track) => top_tracks.push(track),
+                None => break,
+            }
+        }
+        Ok(top_tracks)
+    }
+
+    async fn new_releases(&self) -> Result<Vec<SimplifiedAlbum>, ClientError> {
+        BaseClient::new_releases(self, None).try_collect().await
+    }
+
+    async fn album_tracks(
+        &self,
+        album_id: &str,
+        market: Option<Market>,
+    ) -> Result<Vec<SimplifiedTrack>, ClientError> {
+        let album_id = AlbumId::from_id(album_id).map_err(invalid_id)?;
+        self.album_track(album_id, market).try_collect().await
+    }
+
+    async fn artist_top_tracks(
+        &self,
+        artist_id: &str,
+        market: Option<Market>,
+    ) -> Result<Vec<FullTrack>, ClientError> {
+        let artist_id = ArtistId::from_id(artist_id).map_err(invalid_id)?;
+        BaseClient::artist_top_tracks(self, artist_id, market).await
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        search_type: SearchType,
+        market: Option<Market>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<SearchResult, ClientError> {
+        BaseClient::search(
+            self,
+            query,
+            search_type,
+            market,
+            None,
+            Some(limit),
+            Some(offset),
+        )
+        .await
+    }
+
+    async fn user_playlists(&self) -> Result<Vec<SimplifiedPlaylist>, ClientError> {
+        self.current_user_playlists().try_collect().await
+    }
+
+    async fn playlist_items(&self, playlist_id: &str) -> Result<Vec<PlaylistItem>, ClientError> {
+        let playlist_id = PlaylistId::from_id(playlist_id).map_err(invalid_id)?;
+        BaseClient::playlist_items(self, playlist_id, None, None)
+            .try_collect()
+            .await
+    }
+
+    async fn add_track_to_playlist(
+        &self,
+        playlist_id: &str,
+        track_id: &str,
+    ) -> Result<(), ClientError> {
+        let playlist_id = PlaylistId::from_id(playlist_id).map_err(invalid_id)?;
+        let track_id = TrackId::from_id(track_id).map_err(invalid_id)?;
+        self.playlist_add_items(playlist_id, [PlayableId::Track(track_id)], Some(0))
+            .await?;
+        Ok(())
+    }
+
+    async fn follow_playlist(&self, playlist_id: &str) -> Result<(), ClientError> {
+        let playlist_id = PlaylistId::from_id(playlist_id).map_err(invalid_id)?;
+        self.playlist_follow(playlist_id, Some(true)).await
+    }
+
+    async fn unfollow_playlist(&self, playlist_id: &str) -> Result<(), ClientError> {
+        let playlist_id = PlaylistId::from_id(playlist_id).map_err(invalid_id)?;
+        self.playlist_unfollow(playlist_id).await
+    }
+
+    async fn current_user(&self) -> Result<PrivateUser, ClientError> {
+        OAuthClient::current_user(self).await
+    }
+
+    async fn token(&self) -> Result<Option<Token>, ClientError> {
+        Ok(self.get_token().lock().await.unwrap().clone())
+    }
+}
@@ -0,0 +1,64 @@
+// This section persists fetched Spotify data to disk, the app only reads it back to fill the screens at startup
+use crate::app::App;
+use crate::enums::Task;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::PathBuf;
+
+/// Version of the cache layout, bump it whenever a cached type changes so older files are ignored
+pub const CACHE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile<T> {
+    version: u32,
+    data: T,
+}
+
+/// Directory holding the cached Spotify data
+pub fn cache_dir(app: &App) -> PathBuf {
+    app.cache_dir.clone()
+}
+
+/// Name of the cache file for a task, only data that doesn't depend on the current selection is cached
+pub fn cache_name(task: &Task) -> Option<&'static str> {
+    match task {
+        Task::MadeFY => Some("made_fy_playlists"),
+        Task::LikedSongs => Some("liked_songs"),
+        Task::RecentlyPlayed => Some("recently_played"),
+        Task::UserAlbums => Some("user_albums"),
+        Task::UserArtists => Some("user_artists"),
+        Task::Podcasts => Some("podcasts"),
+        Task::NewReleases => Some("new_releases"),
+        Task::UserPlaylists => Some("playlists"),
+        Task::TopTracks => Some("top_tracks"),
+        _ => None,
+    }
+}
+
+/// Writes `data` to the cache file `name`
+pub fn save<T: Serialize>(app: &App, name: &str, data: &T) -> io::Result<()> {
+    let path = cache_dir(app);
+    fs::create_dir_all(&path)?;
+
+    let file = File::create(path.join(format!("{}.json", name)))?;
+    let cache_file = CacheFile {
+        version: CACHE_VERSION,
+        data,
+    };
+    serde_json::to_writer(BufWriter::new(file), &cache_file)?;
+    Ok(())
+}
+
+/// Reads the cache file `name`, a missing, unreadable or outdated file gives `None`
+pub fn load<T: DeserializeOwned>(app: &App, name: &str) -> Option<T> {
+    let file = File::open(cache_dir(app).join(format!("{}.json", name))).ok()?;
+    let cache_file: CacheFile<T> = serde_json::from_reader(BufReader::new(file)).ok()?;
+
+    if cache_file.version == CACHE_VERSION {
+        Some(cache_file.data)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,237 @@
+// Catches the redirect Spotify sends to the browser at the end of the login, so the user doesn't have to copy it
+use futures::future;
+use rspotify::ClientError;
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+use url::Url;
+
+use crate::spotify::backend::request::describe;
+
+// The user has this long to finish the login in the browser
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(300);
+// A connection that sends nothing for this long is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+// A redirect is a single GET request, anything longer isn't one
+const MAX_REQUEST_SIZE: usize = 16 * 1024;
+
+const LOGGED_IN_PAGE: &str = "<html><body><h2>Logged in to spoify</h2><p>You can close this tab and go back to the terminal.</p></body></html>";
+
+/// Listens on the host and port of the redirect URI
+///
+/// Browsers resolve `localhost` to `127.0.0.1` or `::1`, so it is listened on at both when the system has IPv6.
+pub async fn bind(redirect_uri: &str) -> io::Result<Vec<TcpListener>> {
+    let url =
+        Url::parse(redirect_uri).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    match url.host_str() {
+        Some("localhost") | None => {
+            let mut listeners = vec![TcpListener::bind(("127.0.0.1", port)).await?];
+            if let Ok(listener) = TcpListener::bind(("::1", port)).await {
+                listeners.push(listener);
+            }
+            Ok(listeners)
+        }
+        Some(host) => Ok(vec![TcpListener::bind((host, port)).await?]),
+    }
+}
+
+/// Waits for the browser to be redirected to `redirect_uri` and returns the authorization code
+pub async fn wait_for_code(
+    listeners: Vec<TcpListener>,
+    redirect_uri: &str,
+    state: &str,
+) -> Result<String, ClientError> {
+    let callback_path = Url::parse(redirect_uri)
+        .map(|url| url.path().to_string())
+        .unwrap_or_else(|_| "/".to_string());
+
+    // Browsers open speculative connections they may never send on, so each one is read on its own task
+    let wait = async {
+        let mut connections = JoinSet::new();
+        loop {
+            tokio::select! {
+                accepted = accept(&listeners) => {
+                    let stream = accepted.map_err(ClientError::Io)?;
+                    connections.spawn(answer(stream, callback_path.clone(), state.to_string()));
+                }
+                Some(answered) = connections.join_next() => {
+                    if let Ok(Some(code)) = answered {
+                        return code;
+                    }
+                }
+            }
+        }
+    };
+
+    tokio::time::timeout(LOGIN_TIMEOUT, wait)
+        .await
+        .unwrap_or_else(|_| {
+            Err(ClientError::Cli(
+                "The login wasn't finished in time, log in again to retry".to_string(),
+            ))
+        })
+}
+
+// Waits for a connection on any of the listeners, there is always at least one
+async fn accept(listeners: &[TcpListener]) -> io::Result<TcpStream> {
+    let accepts = listeners.iter().map(|listener| Box::pin(listener.accept()));
+    let (accepted, _, _) = future::select_all(accepts).await;
+    accepted.map(|(stream, _)| stream)
+}
+
+// Answers a single connection, None when it didn't carry the redirect
+async fn answer(
+    mut stream: TcpStream,
+    callback_path: String,
+    state: String,
+) -> Option<Result<String, ClientError>> {
+    // A broken or idle connection, or the browser asking for a favicon, isn't the redirect
+    let url = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(Some(url))) if url.path() == callback_path => url,
+        _ => {
+            let _ = respond(&mut stream, "404 Not Found", "").await;
+            return None;
+        }
+    };
+
+    let code = code_from_url(&url, &state);
+    let page = match &code {
+        Ok(_) => LOGGED_IN_PAGE.to_string(),
+        Err(e) => format!(
+            "<html><body><h2>The login failed</h2><p>{}</p></body></html>",
+            escape_html(&describe(e))
+        ),
+    };
+    let status = if code.is_ok() {
+        "200 OK"
+    } else {
+        "400 Bad Request"
+    };
+    let _ = respond(&mut stream, status, &page).await;
+    Some(code)
+}
+
+/// Reads the authorization code from the URL Spotify redirected to, after checking it answers the login spoify started
+pub fn code_from_url(url: &Url, state: &str) -> Result<String, ClientError> {
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    if let Some(error) = param("error") {
+        return Err(ClientError::Cli(format!(
+            "Spotify didn't authorize spoify: {}",
+            error
+        )));
+    }
+    // Another page could send the browser here, only the redirect of this login carries its state
+    if param("state").as_deref() != Some(state) {
+        return Err(ClientError::Cli(
+            "The login answer doesn't belong to this login, log in again to retry".to_string(),
+        ));
+    }
+    param("code")
+        .filter(|code| !code.is_empty())
+        .ok_or_else(|| ClientError::Cli("The login answer has no authorization code".to_string()))
+}
+
+// Returns the URL of a GET request, None for anything else
+async fn read_request(stream: &mut TcpStream) -> io::Result<Option<Url>> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 || request.len() > MAX_REQUEST_SIZE {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or_default().split(' ');
+    let (Some("GET"), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Ok(None);
+    };
+    Ok(Url::parse("http://localhost")
+        .and_then(|base| base.join(target))
+        .ok())
+}
+
+// The error Spotify sends back is shown on the page, so it must not be able to add markup
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    const REDIRECT: &[u8] = b"GET /callback?code=xyz&state=abc HTTP/1.1\r\nHost: localhost\r\n\r\n";
+
+    // Sends the redirect the browser would and returns the page it got back
+    async fn browse(address: SocketAddr) -> String {
+        let mut browser = TcpStream::connect(address).await.unwrap();
+        browser.write_all(REDIRECT).await.unwrap();
+        let mut response = String::new();
+        browser.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn an_idle_connection_does_not_hold_up_the_redirect() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let login = tokio::spawn(async move {
+            wait_for_code(vec![listener], "http://localhost:8888/callback", "abc").await
+        });
+
+        // The speculative connection is opened first and never sends anything
+        let _idle = TcpStream::connect(address).await.unwrap();
+        assert!(browse(address).await.starts_with("HTTP/1.1 200 OK"));
+
+        let code = tokio::time::timeout(Duration::from_secs(1), login)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(code.unwrap(), "xyz");
+    }
+
+    #[tokio::test]
+    async fn the_redirect_is_caught_on_any_listener() {
+        let ipv4 = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        // Systems without IPv6 only get the IPv4 listener
+        let Ok(ipv6) = TcpListener::bind(("::1", 0)).await else {
+            return;
+        };
+        let address = ipv6.local_addr().unwrap();
+        let login = tokio::spawn(async move {
+            wait_for_code(vec![ipv4, ipv6], "http://localhost:8888/callback", "abc").await
+        });
+
+        assert!(browse(address).await.starts_with("HTTP/1.1 200 OK"));
+        let code = tokio::time::timeout(Duration::from_secs(1), login)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(code.unwrap(), "xyz");
+    }
+}
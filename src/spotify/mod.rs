@@ -1,8 +1,14 @@
+pub mod account;
 pub mod auth;
+pub mod backend;
+pub mod cache;
 pub mod library_section;
+pub mod login_server;
 pub mod new_release_section;
 pub mod player;
 pub mod playlist_control;
 pub mod search;
+pub mod task;
 pub mod user_playlist;
 pub mod user_stats;
+pub mod util;
@@ -1,108 +1,21 @@
 extern crate rspotify;
-extern crate serde_json;
 
 use crate::app::App;
-use futures::{FutureExt, TryStreamExt};
-use rspotify::{model::SimplifiedAlbum, prelude::*, ClientCredsSpotify, ClientError, Credentials};
-use serde_json::{json, Value};
-use std::env;
-use std::fs::File;
-use std::io::BufReader;
-use std::io::Write;
-use std::path::PathBuf;
+use crate::models::Album;
+use crate::spotify::backend::get_backend;
+use rspotify::{model::SimplifiedAlbum, ClientError};
 
-/// Fetches a list of new releases from Spotify and stores them for later use
-#[tokio::main]
-pub async fn new_releases(app: &mut App) -> Result<(), ClientError> {
-    let client_id = &app.client_id;
-    let client_secret_id = &app.client_secret;
-
-    // Create authentication credentials
-    let creds = Credentials {
-        id: client_id.to_string(),
-        secret: Some(client_secret_id.to_string()),
-    };
-
-    // Create a Spotify client using client credentials flow.
-    let spotify = ClientCredsSpotify::new(creds);
-
-    // Request an access token from Spotify.
-    spotify.request_token().await.unwrap();
+/// Fetches a list of new releases from Spotify
+pub async fn new_releases(app: &mut App) -> Result<Vec<SimplifiedAlbum>, ClientError> {
+    // Get a Spotify client using an existing access token (if available).
+    let spotify = get_backend(app).await?;
 
     // Collect information about a limited number of new releases.
-    let mut new_releases = Vec::new();
-    let stream = spotify
-        .new_releases(None)
-        .try_for_each(|item| {
-            new_releases.push(item);
-            futures::future::ok(())
-        })
-        .boxed();
-
-    stream.await?;
-
-    save_new_releases_to_json(app, new_releases);
-
-    Ok(())
+    spotify.new_releases().await
 }
 
-/// Saves a vector of simplified album data to a JSON file in the Spotify cache directory
-fn save_new_releases_to_json(app: &mut App, items: Vec<SimplifiedAlbum>) {
-    let json_data = json!(items);
-
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(".."); // Move up to the root of the Git repository
-    path.push(app.file_name.clone());
-    path.push("spotify_cache");
-    std::fs::create_dir_all(&path).unwrap();
-    path.push("new_releases.json");
-
-    let mut file = File::create(&path).unwrap();
-    let _ = file.write_all(json_data.to_string().as_bytes());
-}
-
-/// Processes the new releases data stored in the cache file and populates the app's data structures
-pub fn process_new_releases(app: &mut App) {
-    app.new_release_artist.clear();
-    app.new_release_name.clear();
-
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(".."); // Move up to the root of the Git repository
-    path.push(app.file_name.clone());
-    path.push("spotify_cache");
-    path.push("new_releases.json");
-
-    let file = File::open(&path).expect("Failed to open new_releases.json");
-    let reader = BufReader::new(file);
-    let json_data: Value =
-        serde_json::from_reader(reader).expect("Failed to parse new_releases.json");
-
-    // Extract information about each new release from the JSON data
-    if let Value::Array(albums) = json_data {
-        for album in albums {
-            if let Value::Object(album_obj) = album {
-                if let Some(artists) = album_obj.get("artists") {
-                    if let Value::Array(artist_array) = artists {
-                        if let Some(first_artist) = artist_array.first() {
-                            if let Value::Object(artist_obj) = first_artist {
-                                if let Some(artist_name) = artist_obj.get("name") {
-                                    if let Value::String(name) = artist_name {
-                                        app.new_release_artist.push(name.clone());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                if let Some(id) = album_obj.get("id").and_then(Value::as_str) {
-                    app.new_release_album_links.push(id.to_string());
-                }
-                if let Some(name) = album_obj.get("name") {
-                    if let Value::String(album_name) = name {
-                        app.new_release_name.push(album_name.clone());
-                    }
-                }
-            }
-        }
-    }
+/// Populates the app's data structures with the new releases
+pub fn process_new_releases(app: &mut App, albums: &[SimplifiedAlbum]) {
+    app.new_releases
+        .set_items(albums.iter().map(Album::from).collect());
 }
@@ -4,8 +4,7 @@ pub mod play_playback;
 pub mod player;
 pub mod previous_track;
 pub mod repeat;
+pub mod set_volume;
 pub mod shuffle;
 pub mod start_playback;
 pub mod util;
-pub mod volume_decrease;
-pub mod volume_increase;
@@ -1,15 +1,14 @@
 use super::util::f64_to_duration;
 use crate::app::App;
-use crate::spotify::auth::get_spotify_client;
+use crate::spotify::backend::get_backend;
 use chrono::TimeDelta;
-use rspotify::clients::OAuthClient;
 use rspotify::ClientError;
+use std::io;
 
 // Main function to toggle the shuffle mode
-#[tokio::main]
 pub async fn play(app: &mut App) -> Result<(), ClientError> {
     // Get a Spotify client using an existing access token (if available).
-    let spotify = get_spotify_client(app).await?;
+    let spotify = get_backend(app).await?;
 
     // Get the device ID from the application state (if available)
     let device_id: Option<&str> = app.device_id_after_pause.as_deref();
@@ -21,13 +20,10 @@ pub async fn play(app: &mut App) -> Result<(), ClientError> {
     //
     // and pray to god it works
     match duration_result {
-        Ok(duration) => {
-            let _result = spotify.resume_playback(device_id, Some(duration));
-            Ok(())
-        }
-        Err(err) => {
-            eprintln!("{}", err);
-            Err(ClientError::InvalidToken)
-        }
+        Ok(duration) => spotify.resume(device_id, Some(duration)).await,
+        Err(err) => Err(ClientError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            err,
+        ))),
     }
 }
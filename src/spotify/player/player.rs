@@ -1,100 +1,60 @@
 use crate::app::App;
-use crate::spotify::auth::get_spotify_client;
+use crate::spotify::backend::get_backend;
+use crate::spotify::util::first_artist_name;
 use crate::structs::Settings;
 use chrono::DateTime;
 use rspotify::model::{
-    Actions, AdditionalType, CurrentPlaybackContext, CurrentlyPlayingType, Device, DeviceType,
-    Market, RepeatState,
+    Actions, CurrentPlaybackContext, CurrentlyPlayingType, Device, DeviceType, PlayableItem,
+    RepeatState,
 };
-use rspotify::prelude::OAuthClient;
+use rspotify::prelude::Id;
 use rspotify::ClientError;
-use serde_json::{json, Value};
-use std::fs::File;
-use std::io::{BufReader, Write};
-use std::path::PathBuf;
 
-// Main function to fetch the currently playing track information
-#[tokio::main]
-pub async fn currently_playing(app: &mut App) -> Result<(), ClientError> {
-    // Get a Spotify client using an existing access token (if available).
-    let spotify = get_spotify_client(app).await?;
+/// Device name shown while no device is playing anything
+pub const NO_ACTIVE_DEVICE: &str = "No active device";
+/// Device name shown while Spotify can't be reached
+pub const OFFLINE: &str = "Offline";
 
-    // Fetch the currently playing track information from the Spotify API
-    let currently_playing_result = spotify
-        .current_playback(
-            Some(Market::FromToken),
-            Some(
-                vec![AdditionalType::Episode]
-                    .iter()
-                    .map(|x| x as &AdditionalType),
-            ),
-        )
-        .await;
-
-    // Process and handle the API response
-    let currently_playing_tracks: CurrentPlaybackContext = match currently_playing_result {
-        Ok(page) => page.unwrap_or_else(|| CurrentPlaybackContext {
-            device: Device {
-                id: None,
-                is_active: false,
-                is_private_session: false,
-                is_restricted: false,
-                name: "Device Offline".to_string(),
-                _type: DeviceType::Computer,
-                volume_percent: Some(0),
-            },
-            repeat_state: RepeatState::Off,
-            shuffle_state: false,
-            context: None,
-            timestamp: DateTime::default(),
-            progress: None,
-            is_playing: false,
-            item: None,
-            currently_playing_type: CurrentlyPlayingType::Unknown,
-            actions: Actions::default(),
-        }),
-        Err(_err) => CurrentPlaybackContext {
-            device: Device {
-                id: None,
-                is_active: false,
-                is_private_session: false,
-                is_restricted: false,
-                name: "Device Offline".to_string(),
-                _type: DeviceType::Computer,
-                volume_percent: Some(0),
-            },
-            repeat_state: RepeatState::Off,
-            shuffle_state: false,
-            context: None,
-            timestamp: DateTime::default(),
-            progress: None,
-            is_playing: false,
-            item: None,
-            currently_playing_type: CurrentlyPlayingType::Unknown,
-            actions: Actions::default(),
+/// Playback state shown while nothing can be playing, `device_name` says why
+pub fn idle_playback(device_name: &str) -> CurrentPlaybackContext {
+    CurrentPlaybackContext {
+        device: Device {
+            id: None,
+            is_active: false,
+            is_private_session: false,
+            is_restricted: false,
+            name: device_name.to_string(),
+            _type: DeviceType::Computer,
+            volume_percent: Some(0),
         },
-    };
-
-    save_data_to_json(app, currently_playing_tracks);
-
-    Ok(())
+        repeat_state: RepeatState::Off,
+        shuffle_state: false,
+        context: None,
+        timestamp: DateTime::default(),
+        progress: None,
+        is_playing: false,
+        item: None,
+        currently_playing_type: CurrentlyPlayingType::Unknown,
+        actions: Actions::default(),
+    }
 }
 
-// Function to save the currently playing track data to a JSON file
-fn save_data_to_json(app: &mut App, items: CurrentPlaybackContext) {
-    let json_data: Value = json!(items);
-    let mut path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(".."); // Move up to the root of the Git repository
-    path.push(app.file_name.clone());
-    path.push("spotify_cache");
-    std::fs::create_dir_all(&path).unwrap();
-    path.push("currently_playing.json");
-    let mut file: File = File::create(&path).unwrap();
-    let _ = file.write_all(json_data.to_string().as_bytes());
+// Main function to fetch the currently playing track information
+pub async fn currently_playing(app: &mut App) -> Result<CurrentPlaybackContext, ClientError> {
+    // Get a Spotify client using an existing access token (if available).
+    let spotify = get_backend(app).await?;
+
+    // Spotify answers without a playback when no device is active, a failed request is reported as it is
+    let playback = spotify.current_playback().await?;
+    Ok(playback.unwrap_or_else(|| idle_playback(NO_ACTIVE_DEVICE)))
 }
 
 // Function to process the currently playing track information and update the application state
-pub fn process_currently_playing(app: &mut App, settings: &mut Settings) {
+pub fn process_currently_playing(
+    app: &mut App,
+    settings: &mut Settings,
+    playback: &CurrentPlaybackContext,
+) {
     // Clear any existing currently playing data in the app before processing new data
     app.currrent_timestamp = 0.0;
     app.ending_timestamp = 0.0;
@@ -105,92 +65,47 @@ pub fn process_currently_playing(app: &mut App, settings: &mut Settings) {
     app.current_device_volume.clear();
     app.current_device_id = Some("".to_string());
 
-    let mut repeat_state = String::new();
-
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push(".."); // Move up to the root of the Git repository
-    path.push(app.file_name.clone());
-    path.push("spotify_cache");
-    path.push("currently_playing.json");
+    app.currently_playing_media_type = match playback.currently_playing_type {
+        CurrentlyPlayingType::Track => "track",
+        CurrentlyPlayingType::Episode => "episode",
+        CurrentlyPlayingType::Advertisement => "ad",
+        CurrentlyPlayingType::Unknown => "unknown",
+    }
+    .to_string();
+    if let Some(progress) = playback.progress {
+        app.currrent_timestamp = progress.num_milliseconds() as f64;
+    }
+    app.is_playing = playback.is_playing;
+    app.is_shuffle = playback.shuffle_state;
 
-    let file = File::open(&path).expect("Failed to open currently_playing.json");
-    let reader = BufReader::new(file);
-    let json_data: Value =
-        serde_json::from_reader(reader).expect("Failed to parse currently_playing.json");
+    // Extract the device information
+    let device = &playback.device;
+    app.current_device_name = device.name.clone();
+    if let Some(device_id) = &device.id {
+        app.current_device_id = Some(device_id.clone());
+    }
+    if let Some(device_volume) = device.volume_percent {
+        app.current_device_volume = device_volume.to_string();
+        settings.volume_percent = device_volume as u8;
+    }
 
-    // Extract relevant information from the JSON data and update the application state
-    if let Value::Object(currently_playing) = json_data {
-        if let Some(currently_playing_type) = currently_playing
-            .get("currently_playing_type")
-            .and_then(Value::as_str)
-        {
-            app.currently_playing_media_type = currently_playing_type.to_string();
-        }
-        if let Some(progress_ms) = currently_playing.get("progress_ms").and_then(Value::as_i64) {
-            app.currrent_timestamp = progress_ms as f64;
-        }
-        if let Some(is_playing) = currently_playing.get("is_playing").and_then(Value::as_bool) {
-            app.is_playing = is_playing;
-        }
-        if let Some(repeat) = currently_playing
-            .get("repeat_state")
-            .and_then(Value::as_str)
-        {
-            repeat_state = repeat.to_string();
-        }
-        if let Some(device) = currently_playing.get("device").and_then(Value::as_object) {
-            if let Some(device_name) = device.get("name").and_then(Value::as_str) {
-                app.current_device_name = device_name.to_string();
-            }
-            if let Some(device_id) = device.get("id").and_then(Value::as_str) {
-                app.current_device_id = Some(device_id.to_string());
+    match &playback.item {
+        Some(PlayableItem::Track(track)) => {
+            app.ending_timestamp = track.duration.num_milliseconds() as f64;
+            app.current_playing_album = track.album.name.clone();
+            app.currently_playing_artist = first_artist_name(&track.artists);
+            app.current_playing_name = track.name.clone();
+            if let Some(id) = &track.id {
+                app.current_playing_id = id.id().to_string();
             }
-            if let Some(device_volume) = device.get("volume_percent").and_then(Value::as_u64) {
-                app.current_device_volume = device_volume.to_string();
-                settings.volume_percent = device_volume as u8;
-            }
-        }
-        if let Some(shuffle) = currently_playing
-            .get("shuffle_state")
-            .and_then(Value::as_bool)
-        {
-            app.is_shuffle = shuffle;
         }
-
-        if let Some(item) = currently_playing.get("item").and_then(Value::as_object) {
-            if let Some(duration_ms) = item.get("duration_ms").and_then(Value::as_i64) {
-                app.ending_timestamp = duration_ms as f64;
-            }
-            if app.currently_playing_media_type == "episode" {
-                if let Some(show) = item.get("show").and_then(Value::as_object) {
-                    if let Some(show_name) = show.get("name").and_then(Value::as_str) {
-                        app.current_playing_album = show_name.to_string();
-                    }
-                }
-            } else {
-                if let Some(album) = item.get("album").and_then(Value::as_object) {
-                    if let Some(album_name) = album.get("name").and_then(Value::as_str) {
-                        app.current_playing_album = album_name.to_string();
-                    }
-                }
-
-                if let Some(artist_section) = item.get("artists").and_then(Value::as_array) {
-                    if let Some(first_artist) = artist_section.first().and_then(Value::as_object) {
-                        if let Some(artist_name) = first_artist.get("name").and_then(Value::as_str)
-                        {
-                            app.currently_playing_artist = artist_name.to_string();
-                        }
-                    }
-                }
-            }
-
-            if let Some(name) = item.get("name").and_then(Value::as_str) {
-                app.current_playing_name = name.to_string();
-            }
-            if let Some(id) = item.get("id").and_then(Value::as_str) {
-                app.current_playing_id = id.to_string();
-            }
+        Some(PlayableItem::Episode(episode)) => {
+            app.ending_timestamp = episode.duration.num_milliseconds() as f64;
+            app.current_playing_album = episode.show.name.clone();
+            app.current_playing_name = episode.name.clone();
+            app.current_playing_id = episode.id.id().to_string();
         }
+        None => {}
     }
 
     // Update the playback status based on the current state
@@ -204,11 +119,9 @@ pub fn process_currently_playing(app: &mut App, settings: &mut Settings) {
     } else if !app.is_shuffle {
         app.shuffle_status = "Off".to_string();
     }
-    if repeat_state == "track" {
-        app.repeat_status = "Track".to_string();
-    } else if repeat_state == "context" {
-        app.repeat_status = "Album/Playlist".to_string();
-    } else {
-        app.repeat_status = "Off".to_string();
-    }
+    app.repeat_status = match playback.repeat_state {
+        RepeatState::Track => "Track".to_string(),
+        RepeatState::Context => "Album/Playlist".to_string(),
+        RepeatState::Off => "Off".to_string(),
+    };
 }
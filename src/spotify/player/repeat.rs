@@ -1,14 +1,12 @@
 use crate::app::App;
-use crate::spotify::auth::get_spotify_client;
-use rspotify::clients::OAuthClient;
+use crate::spotify::backend::get_backend;
 use rspotify::model::RepeatState;
 use rspotify::ClientError;
 
 // Main function to toggle the shuffle mode
-#[tokio::main]
 pub async fn cycle_repeat(app: &mut App) -> Result<(), ClientError> {
     // Get a Spotify client using an existing access token (if available).
-    let spotify = get_spotify_client(app).await?;
+    let spotify = get_backend(app).await?;
 
     // Get the device ID from the application state (if available)
     let device_id: Option<&str> = app.current_device_id.as_deref();
@@ -0,0 +1,14 @@
+use crate::app::App;
+use crate::spotify::backend::get_backend;
+use rspotify::ClientError;
+
+// Main function to set the volume of the current device to an exact value
+pub async fn set_volume(app: &mut App, volume_percent: u8) -> Result<(), ClientError> {
+    // Get a Spotify client using an existing access token (if available).
+    let spotify = get_backend(app).await?;
+
+    // Get the device ID from the application state (if available)
+    let device_id: Option<&str> = app.current_device_id.as_deref();
+
+    spotify.volume(volume_percent.min(100), device_id).await
+}
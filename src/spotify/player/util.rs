@@ -3,8 +3,8 @@ use std::time::Duration;
 
 /// Converts a floating-point timestamp to a `chrono::TimeDelta`
 pub fn f64_to_duration(timestamp: f64) -> Result<TimeDelta, chrono::OutOfRangeError> {
-    let timestamp = f64::to_bits(timestamp);
-    let duration = Duration::from_millis(timestamp);
+    // The timestamp is in milliseconds, a negative one is treated as zero
+    let duration = Duration::from_millis(timestamp.max(0.0) as u64);
 
     // Attempt to convert the `Duration` to a `chrono::TimeDelta`.
     // If conversion fails due to being out of range, return zero time delta.
@@ -1,3 +1,4 @@
+pub mod query;
 pub mod search;
 pub mod search_albums;
 pub mod search_artists;
@@ -0,0 +1,342 @@
+// Builds the queries sent to Spotify's search from the typed text and the field filters
+use serde::{Deserialize, Serialize};
+
+use crate::enums::SearchCategory;
+
+// The result types each field filter works with, Spotify finds nothing for the other types
+const FIELDS: [(&str, &[SearchCategory]); 7] = [
+    (
+        "artist",
+        &[
+            SearchCategory::Albums,
+            SearchCategory::Artists,
+            SearchCategory::Tracks,
+        ],
+    ),
+    ("album", &[SearchCategory::Albums, SearchCategory::Tracks]),
+    (
+        "year",
+        &[
+            SearchCategory::Albums,
+            SearchCategory::Artists,
+            SearchCategory::Tracks,
+        ],
+    ),
+    ("genre", &[SearchCategory::Artists, SearchCategory::Tracks]),
+    ("isrc", &[SearchCategory::Tracks]),
+    ("upc", &[SearchCategory::Albums]),
+    ("tag", &[SearchCategory::Albums]),
+];
+
+/// Field filters and result types picked on the filter form, they are remembered between runs
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchFilters {
+    pub artist: String,
+    pub album: String,
+    // A year or a range such as 1990-1999
+    pub year: String,
+    pub genre: String,
+    pub isrc: String,
+    // Only albums released in the last two weeks
+    pub new_releases: bool,
+
+    pub tracks: bool,
+    pub artists: bool,
+    pub albums: bool,
+    pub playlists: bool,
+}
+
+impl Default for SearchFilters {
+    fn default() -> Self {
+        Self {
+            artist: String::new(),
+            album: String::new(),
+            year: String::new(),
+            genre: String::new(),
+            isrc: String::new(),
+            new_releases: false,
+
+            tracks: true,
+            artists: true,
+            albums: true,
+            playlists: true,
+        }
+    }
+}
+
+impl SearchFilters {
+    /// The typed text followed by the filters in Spotify's `field:value` syntax
+    pub fn compose(&self, text: &str) -> String {
+        let mut parts = vec![text.trim().to_string()];
+        for (field, value) in [
+            ("artist", &self.artist),
+            ("album", &self.album),
+            ("year", &self.year),
+            ("genre", &self.genre),
+            ("isrc", &self.isrc),
+        ] {
+            let value = value.trim();
+            if !value.is_empty() {
+                parts.push(field_filter(field, value));
+            }
+        }
+        if self.new_releases {
+            parts.push("tag:new".to_string());
+        }
+
+        parts.retain(|part| !part.is_empty());
+        parts.join(" ")
+    }
+
+    /// Whether results of `category` are asked for
+    pub fn includes(&self, category: SearchCategory) -> bool {
+        match category {
+            SearchCategory::Tracks => self.tracks,
+            SearchCategory::Artists => self.artists,
+            SearchCategory::Albums => self.albums,
+            SearchCategory::Playlists => self.playlists,
+        }
+    }
+
+    /// The filters that are set, empty when the search isn't narrowed down
+    pub fn summary(&self) -> String {
+        let mut summary = self.compose("");
+
+        let left_out: Vec<&str> = [
+            (self.tracks, "songs"),
+            (self.artists, "artists"),
+            (self.albums, "albums"),
+            (self.playlists, "playlists"),
+        ]
+        .into_iter()
+        .filter_map(|(included, name)| (!included).then_some(name))
+        .collect();
+        if !left_out.is_empty() {
+            if !summary.is_empty() {
+                summary.push_str(", ");
+            }
+            summary.push_str("without ");
+            summary.push_str(&left_out.join(" and "));
+        }
+        summary
+    }
+}
+
+/// A search kept under a name on the saved searches screen, running it brings its filters back too
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    // The text typed into the search box, without the filters
+    pub text: String,
+    pub filters: SearchFilters,
+}
+
+impl SavedSearch {
+    /// The query the search sends
+    pub fn query(&self) -> String {
+        self.filters.compose(&self.text)
+    }
+}
+
+/// The query sent for `category`, None when the query has nothing to search that type for or uses a field it can't be filtered by
+pub fn query_for(query: &str, category: SearchCategory) -> Option<String> {
+    let mut text = Vec::new();
+    for token in tokenize(query) {
+        match field_categories(&token) {
+            Some(categories) if !categories.contains(&category) => return None,
+            Some(_) => {}
+            None => text.push(token),
+        }
+    }
+
+    match category {
+        // No field works with playlists, their names are matched on the whole text
+        SearchCategory::Playlists if !text.is_empty() => Some(format!("\"{}\"", text.join(" "))),
+        SearchCategory::Playlists => None,
+        _ if query.trim().is_empty() => None,
+        _ => Some(query.trim().to_string()),
+    }
+}
+
+/// The query without its field filters
+pub fn free_text(query: &str) -> String {
+    tokenize(query)
+        .into_iter()
+        .filter(|token| field_categories(token).is_none())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Values with spaces are quoted so Spotify reads them as one
+fn field_filter(field: &str, value: &str) -> String {
+    if value.contains(char::is_whitespace) {
+        format!("{}:\"{}\"", field, value)
+    } else {
+        format!("{}:{}", field, value)
+    }
+}
+
+// Splits the query on the spaces that aren't inside quotes
+fn tokenize(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                token.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+// The result types a `field:value` token works with, None for plain text such as `12:30`
+fn field_categories(token: &str) -> Option<&'static [SearchCategory]> {
+    let (field, value) = token.split_once(':')?;
+    if value.is_empty() {
+        return None;
+    }
+    FIELDS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(field))
+        .map(|(_, categories)| *categories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use SearchCategory::{Albums, Artists, Playlists, Tracks};
+
+    const CATEGORIES: [SearchCategory; 4] = [Tracks, Artists, Albums, Playlists];
+
+    fn queries(query: &str) -> Vec<Option<String>> {
+        CATEGORIES
+            .iter()
+            .map(|category| query_for(query, *category))
+            .collect()
+    }
+
+    #[test]
+    fn quoted_values_stay_one_token() {
+        assert_eq!(
+            tokenize("  love   artist:\"the beatles\" year:1969 "),
+            ["love", "artist:\"the beatles\"", "year:1969"]
+        );
+        // An unclosed quote runs to the end of the query
+        assert_eq!(tokenize("album:\"abbey road"), ["album:\"abbey road"]);
+        assert!(tokenize("   ").is_empty());
+    }
+
+    #[test]
+    fn the_filters_are_added_after_the_text() {
+        let filters = SearchFilters {
+            artist: "Daft Punk".to_string(),
+            year: " 2001 ".to_string(),
+            new_releases: true,
+            ..SearchFilters::default()
+        };
+        assert_eq!(
+            filters.compose(" one more time "),
+            "one more time artist:\"Daft Punk\" year:2001 tag:new"
+        );
+        assert_eq!(
+            filters.compose(""),
+            "artist:\"Daft Punk\" year:2001 tag:new"
+        );
+        assert_eq!(SearchFilters::default().compose("  "), "");
+    }
+
+    #[test]
+    fn each_field_is_only_sent_to_the_types_it_filters() {
+        let some = |query: &str| Some(query.to_string());
+
+        // No field works with playlists, so a filtered query leaves them out
+        assert_eq!(
+            queries("love artist:adele"),
+            [
+                some("love artist:adele"),
+                some("love artist:adele"),
+                some("love artist:adele"),
+                None
+            ]
+        );
+        assert_eq!(
+            queries("love album:25"),
+            [some("love album:25"), None, some("love album:25"), None]
+        );
+        assert_eq!(
+            queries("love year:2015"),
+            [
+                some("love year:2015"),
+                some("love year:2015"),
+                some("love year:2015"),
+                None
+            ]
+        );
+        assert_eq!(
+            queries("love genre:soul"),
+            [some("love genre:soul"), some("love genre:soul"), None, None]
+        );
+        assert_eq!(
+            queries("love isrc:GBBKS1500214"),
+            [some("love isrc:GBBKS1500214"), None, None, None]
+        );
+        assert_eq!(
+            queries("love upc:886445635843"),
+            [None, None, some("love upc:886445635843"), None]
+        );
+        assert_eq!(
+            queries("love tag:new"),
+            [None, None, some("love tag:new"), None]
+        );
+        // The field names are matched whatever their case
+        assert_eq!(query_for("GENRE:soul", Albums), None);
+    }
+
+    #[test]
+    fn text_only_and_filter_only_queries() {
+        let text = Some("one more time".to_string());
+        assert_eq!(
+            queries("  one more time "),
+            [
+                text.clone(),
+                text.clone(),
+                text,
+                Some("\"one more time\"".to_string())
+            ]
+        );
+
+        // Without the text there is nothing to match the playlist names on
+        assert_eq!(
+            queries("artist:\"daft punk\""),
+            [
+                Some("artist:\"daft punk\"".to_string()),
+                Some("artist:\"daft punk\"".to_string()),
+                Some("artist:\"daft punk\"".to_string()),
+                None
+            ]
+        );
+        assert_eq!(queries(" "), [None, None, None, None]);
+    }
+
+    #[test]
+    fn unknown_fields_and_empty_values_are_text() {
+        assert_eq!(free_text("at 12:30 mood: artist:adele"), "at 12:30 mood:");
+        assert_eq!(
+            query_for("at 12:30", Playlists),
+            Some("\"at 12:30\"".to_string())
+        );
+    }
+}
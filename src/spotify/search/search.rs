@@ -1,412 +1,273 @@
 /*
 fetches the search results
-stores the search results in json files
-reads the json file and store the data in required variables
+returns them as typed results
+stores them in required variables
 */
 
 extern crate rspotify;
-extern crate serde_json;
 
 use rspotify::{
-    model::{Country, Market, SearchType},
-    prelude::*,
-    ClientCredsSpotify, Credentials,
+    model::{
+        FullArtist, FullTrack, Page, SearchResult, SearchType, SimplifiedAlbum, SimplifiedPlaylist,
+    },
+    ClientError,
 };
 use serde::{Deserialize, Serialize};
-use std::env;
-use std::fs;
-use std::fs::File;
-use std::io;
-use std::io::Write;
-use std::path::{Path, PathBuf};
 
 use crate::app::App;
-
-// Main function to perform the search and store the results in JSON files
-#[tokio::main]
-pub async fn search(user_query: &str, app: &mut App) -> Result<(), std::io::Error> {
-    let client_id = &app.client_id;
-    let client_secret_id = &app.client_secret;
-
-    let creds = Credentials {
-        id: client_id.to_string(),
-        secret: Some(client_secret_id.to_string()),
-    };
-    let spotify = ClientCredsSpotify::new(creds);
-
-    // Obtaining the access token
-    spotify.request_token().await.unwrap();
-
-    // Setting up the directory to store the search results
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push("..");
-    path.push(app.file_name.clone());
-    path.push("spotify_cache");
-    std::fs::create_dir_all(&path).unwrap();
-
-    // Performing search and storing the results in JSON files
-    let album_query = user_query;
-    let result = spotify
-        .search(album_query, SearchType::Album, None, None, Some(10), None)
-        .await;
-    if let Ok(albums) = result {
-        let json_data = serde_json::to_string(&albums).unwrap();
-        let mut file = File::create(path.join("album_search_results.json")).unwrap();
-        write!(file, "{}", json_data).unwrap();
-    }
-
-    let artist_query = user_query;
-    let result = spotify
-        .search(
-            artist_query,
-            SearchType::Artist,
-            Some(Market::Country(Country::UnitedStates)),
-            None,
-            Some(10),
-            None,
-        )
-        .await;
-    if let Ok(artists) = result {
-        let json_data = serde_json::to_string(&artists).unwrap();
-        let mut file = File::create(path.join("artist_search_results.json")).unwrap();
-        write!(file, "{}", json_data).unwrap();
-    }
-
-    let formated_query = format!("\"{}\"", user_query);
-    let playlist_query: &str = formated_query.as_str();
-    let result = spotify
-        .search(
-            playlist_query,
-            SearchType::Playlist,
-            Some(Market::Country(Country::UnitedStates)),
-            None,
-            Some(10),
-            None,
-        )
-        .await;
-    if let Ok(playlists) = result {
-        let json_data = serde_json::to_string(&playlists).unwrap();
-        let mut file = File::create(path.join("playlist_search_results.json")).unwrap();
-        write!(file, "{}", json_data).unwrap();
-    }
-
-    let track_query = user_query;
-    let result = spotify
-        .search(
-            track_query,
-            SearchType::Track,
-            Some(Market::Country(Country::UnitedStates)),
-            None,
-            Some(10),
-            None,
-        )
-        .await;
-    if let Ok(tracks) = result {
-        let json_data = serde_json::to_string(&tracks).unwrap();
-        let mut file = File::create(path.join("tracks_search_results.json")).unwrap();
-        write!(file, "{}", json_data).unwrap();
-    }
-
-    Ok(())
+use crate::enums::{SearchCategory, Task};
+use crate::models::{Album, Artist, Playlist, Track};
+use crate::spotify::backend::{get_backend, SpotifyBackend};
+use crate::spotify::search::query::query_for;
+
+// Results fetched for each type at a time
+const PAGE_SIZE: u32 = 10;
+
+/// Results of a search, a type that failed to load is left empty
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub albums: Vec<SimplifiedAlbum>,
+    pub artists: Vec<FullArtist>,
+    pub playlists: Vec<SimplifiedPlaylist>,
+    pub tracks: Vec<FullTrack>,
+    #[serde(default)]
+    pub paging: SearchPaging,
 }
 
-// Function to process the search results and store them in the application state
-pub fn process_search(app: &mut App, query: &str) -> io::Result<()> {
-    app.album_names_search_results.clear();
-    app.artist_names_search_results.clear();
-    app.track_names_search_results.clear();
-    app.playlist_names_search_results.clear();
-
-    app.album_links_search_results.clear();
-    app.artist_links_search_results.clear();
-    app.track_links_search_results.clear();
-    app.playlist_links_search_results.clear();
-
-    let mut spotify_cache_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    spotify_cache_path.push("..");
-    spotify_cache_path.push(app.file_name.clone());
-    spotify_cache_path.push("spotify_cache");
-
-    if search(query, app).is_ok() {
-        (
-            app.album_names_search_results,
-            app.album_links_search_results,
-        ) = match album_storage(&spotify_cache_path) {
-            Ok(result) => result,
-            Err(err) => {
-                println!("Error reading album data: {}", err);
-                return Err(err);
-            }
-        };
-
-        (
-            app.track_names_search_results,
-            app.track_links_search_results,
-        ) = match track_storage(&spotify_cache_path) {
-            Ok(result) => result,
-            Err(err) => {
-                println!("Error reading track data: {}", err);
-                return Err(err);
-            }
-        };
-
-        (
-            app.artist_names_search_results,
-            app.artist_links_search_results,
-        ) = match artist_storage(&spotify_cache_path) {
-            Ok(result) => result,
-            Err(err) => {
-                println!("Error reading artist data: {}", err);
-                return Err(err);
-            }
-        };
-
-        (
-            app.playlist_names_search_results,
-            app.playlist_links_search_results,
-        ) = match playlist_storage(&spotify_cache_path) {
-            Ok(result) => result,
-            Err(err) => {
-                println!("Error reading playlist data: {}", err);
-                return Err(err);
-            }
-        };
-    }
-
-    Ok(())
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct TrackResponse {
-    tracks: Tracks,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct Tracks {
-    items: Vec<Track>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct Track {
-    artists: Vec<Artist>,
-    href: String,
-    id: String,
-    name: String,
-    external_urls: TrackExternalUrls,
+/// How far one type of results has been fetched
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Paging {
+    // Offset of the next page, hidden tracks count too so no page is fetched twice
+    pub next_offset: u32,
+    pub total: u32,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct TrackExternalUrls {
-    spotify: String,
-}
-
-pub fn track_storage(spotify_cache_path: &Path) -> Result<(Vec<String>, Vec<String>), io::Error> {
-    let json_file_path = spotify_cache_path.join("tracks_search_results.json");
-
-    if !json_file_path.exists() {
-        println!("tracks_search_results.json file does not exist");
-        return Ok((Vec::new(), Vec::new()));
-    }
-
-    let metadata = fs::metadata(&json_file_path)?;
-    if metadata.len() == 0 {
-        println!("tracks_search_results.json file is empty");
-        return Ok((Vec::new(), Vec::new()));
+impl Paging {
+    fn after<T>(page: &Page<T>) -> Self {
+        Self {
+            next_offset: page.offset + page.items.len() as u32,
+            total: page.total,
+        }
     }
 
-    let data = fs::read_to_string(json_file_path)?;
-
-    let track_response: TrackResponse = serde_json::from_str(&data).map_err(|e| {
-        println!("Deserialization error: {}", e);
-        e
-    })?;
-
-    let tracks = &track_response.tracks.items;
-
-    let mut track_names_search_results: Vec<String> = Vec::new();
-    let mut track_links_search_results: Vec<String> = Vec::new();
-
-    for track in tracks {
-        track_names_search_results.push(track.name.clone());
-        track_links_search_results.push(track.id.clone());
+    /// Whether Spotify has results that haven't been fetched yet
+    pub fn has_more(&self) -> bool {
+        self.next_offset < self.total
     }
-
-    Ok((track_names_search_results, track_links_search_results))
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct PlaylistResponse {
-    playlists: Playlists,
+/// Paging of every type of results
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct SearchPaging {
+    pub albums: Paging,
+    pub artists: Paging,
+    pub playlists: Paging,
+    pub tracks: Paging,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct Playlists {
-    items: Vec<Playlist>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct Playlist {
-    href: String,
-    id: String,
-    name: String,
-    external_urls: PlaylistExternalUrls,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct PlaylistExternalUrls {
-    spotify: String,
-}
-
-pub fn playlist_storage(
-    spotify_cache_path: &Path,
-) -> Result<(Vec<String>, Vec<String>), io::Error> {
-    let json_file_path = spotify_cache_path.join("playlist_search_results.json");
-
-    if !json_file_path.exists() {
-        println!("playlist_search_results.json file does not exist");
-        return Ok((Vec::new(), Vec::new()));
+impl SearchPaging {
+    pub fn get(&self, category: SearchCategory) -> Paging {
+        match category {
+            SearchCategory::Albums => self.albums,
+            SearchCategory::Artists => self.artists,
+            SearchCategory::Playlists => self.playlists,
+            SearchCategory::Tracks => self.tracks,
+        }
     }
 
-    let metadata = fs::metadata(&json_file_path)?;
-    if metadata.len() == 0 {
-        println!("playlist_search_results.json file is empty");
-        return Ok((Vec::new(), Vec::new()));
+    fn get_mut(&mut self, category: SearchCategory) -> &mut Paging {
+        match category {
+            SearchCategory::Albums => &mut self.albums,
+            SearchCategory::Artists => &mut self.artists,
+            SearchCategory::Playlists => &mut self.playlists,
+            SearchCategory::Tracks => &mut self.tracks,
+        }
     }
+}
 
-    let data = fs::read_to_string(json_file_path)?;
-
-    let playlist_response: PlaylistResponse = serde_json::from_str(&data).map_err(|e| {
-        println!("Deserialization error: {}", e);
-        e
-    })?;
-
-    let playlists = &playlist_response.playlists.items;
-
-    let mut playlist_names_search_results: Vec<String> = Vec::new();
-    let mut playlist_links_search_results: Vec<String> = Vec::new();
-
-    for playlist in playlists {
-        playlist_names_search_results.push(playlist.name.clone());
-        playlist_links_search_results.push(playlist.id.clone());
+/// Searches every type of results one after the other, for the `search` command that prints them together
+pub async fn search(user_query: &str, app: &mut App) -> Result<SearchResults, ClientError> {
+    // Get a Spotify client using an existing access token (if available).
+    let spotify = get_backend(app).await?;
+
+    let mut results = SearchResults::default();
+    // The types left out on the filter form stay empty
+    for category in SearchCategory::ALL
+        .into_iter()
+        .filter(|&category| app.search_filters.includes(category))
+    {
+        // A type that fails is left empty, the others are still shown
+        let _ = search_page(spotify.as_ref(), app, user_query, category, 0, &mut results).await;
     }
 
-    Ok((playlist_names_search_results, playlist_links_search_results))
+    Ok(results)
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct ArtistResponse {
-    artists: Artists,
-}
+/// Fetches the first page of `category`
+pub async fn search_category(
+    user_query: &str,
+    category: SearchCategory,
+    app: &App,
+) -> Result<SearchResults, ClientError> {
+    let spotify = get_backend(app).await?;
 
-#[derive(Serialize, Deserialize, Debug)]
-struct Artists {
-    items: Vec<Artist>,
-}
+    let mut results = SearchResults::default();
+    search_page(spotify.as_ref(), app, user_query, category, 0, &mut results).await?;
 
-#[derive(Serialize, Deserialize, Debug)]
-struct Artist {
-    id: String,
-    href: String,
-    external_urls: ArtistExternalUrls,
-    name: String,
+    Ok(results)
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct ArtistExternalUrls {
-    spotify: String,
+/// Fetches the page of `category` that follows the results already shown
+pub async fn search_more(
+    user_query: &str,
+    category: SearchCategory,
+    app: &App,
+) -> Result<SearchResults, ClientError> {
+    let spotify = get_backend(app).await?;
+
+    let mut results = SearchResults::default();
+    let offset = app.search_paging.get(category).next_offset;
+    search_page(
+        spotify.as_ref(),
+        app,
+        user_query,
+        category,
+        offset,
+        &mut results,
+    )
+    .await?;
+
+    Ok(results)
 }
 
-pub fn artist_storage(spotify_cache_path: &Path) -> Result<(Vec<String>, Vec<String>), io::Error> {
-    let json_file_path = spotify_cache_path.join("artist_search_results.json");
-
-    if !json_file_path.exists() {
-        println!("artist_search_results.json file does not exist");
-        return Ok((Vec::new(), Vec::new()));
-    }
-
-    let metadata = fs::metadata(&json_file_path)?;
-    if metadata.len() == 0 {
-        println!("artist_search_results.json file is empty");
-        return Ok((Vec::new(), Vec::new()));
+// Searches one type of results and stores the page and its paging in `results`
+async fn search_page(
+    spotify: &dyn SpotifyBackend,
+    app: &App,
+    user_query: &str,
+    category: SearchCategory,
+    offset: u32,
+    results: &mut SearchResults,
+) -> Result<(), ClientError> {
+    // Every type is searched in the same market, so the results match what the user can play
+    let market = Some(app.market);
+
+    // A type the filters don't work with is left empty
+    let Some(query) = query_for(user_query, category) else {
+        return Ok(());
+    };
+    let search_type = match category {
+        SearchCategory::Albums => SearchType::Album,
+        SearchCategory::Artists => SearchType::Artist,
+        SearchCategory::Playlists => SearchType::Playlist,
+        SearchCategory::Tracks => SearchType::Track,
+    };
+    let paging = results.paging.get_mut(category);
+
+    match spotify
+        .search(&query, search_type, market, PAGE_SIZE, offset)
+        .await?
+    {
+        SearchResult::Albums(albums) => {
+            *paging = Paging::after(&albums);
+            results.albums = albums.items;
+        }
+        SearchResult::Artists(artists) => {
+            *paging = Paging::after(&artists);
+            results.artists = artists.items;
+        }
+        SearchResult::Playlists(playlists) => {
+            *paging = Paging::after(&playlists);
+            results.playlists = playlists.items;
+        }
+        SearchResult::Tracks(tracks) => {
+            *paging = Paging::after(&tracks);
+            results.tracks = tracks.items;
+        }
+        _ => {}
     }
 
-    let data = fs::read_to_string(json_file_path)?;
-
-    let artist_response: ArtistResponse = serde_json::from_str(&data).map_err(|e| {
-        println!("Deserialization error: {}", e);
-        e
-    })?;
-
-    let artists = &artist_response.artists.items;
-
-    let mut artist_names_search_results: Vec<String> = Vec::new();
-    let mut artist_links_search_results: Vec<String> = Vec::new();
-
-    for artist in artists {
-        artist_names_search_results.push(artist.name.clone());
-        artist_links_search_results.push(artist.id.clone());
+    // Spotify marks a track it can't play in the market instead of leaving it out
+    if app.hide_unplayable {
+        results
+            .tracks
+            .retain(|track| track.is_playable != Some(false));
     }
-
-    Ok((artist_names_search_results, artist_links_search_results))
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct AlbumResponse {
-    albums: Albums,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct Albums {
-    items: Vec<Album>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct Album {
-    artists: Vec<Artist>,
-    href: String,
-    id: String,
-    available_markets: Vec<String>,
-    name: String,
-    external_urls: AlbumExternalUrls,
-    album_type: String,
-    release_date: String,
+    Ok(())
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct AlbumExternalUrls {
-    spotify: String,
+// Function to process the search results and store them in the application state
+pub fn process_search(app: &mut App, results: &SearchResults) {
+    app.search_paging = results.paging;
+    app.search_loading = None;
+    app.searched_albums
+        .set_items(results.albums.iter().map(Album::from).collect());
+    app.searched_tracks
+        .set_items(results.tracks.iter().map(Track::from).collect());
+    app.searched_artists
+        .set_items(results.artists.iter().map(Artist::from).collect());
+    app.searched_playlists
+        .set_items(results.playlists.iter().map(Playlist::from).collect());
 }
 
-pub fn album_storage(spotify_cache_path: &Path) -> Result<(Vec<String>, Vec<String>), io::Error> {
-    let json_file_path = spotify_cache_path.join("album_search_results.json");
-
-    if !json_file_path.exists() {
-        println!("album_search_results.json file does not exist");
-        return Ok((Vec::new(), Vec::new()));
+/// Shows the first page of `category` in its pane, nothing is selected until the pane gets the focus
+pub fn process_search_results(app: &mut App, category: SearchCategory, results: &SearchResults) {
+    *app.search_paging.get_mut(category) = results.paging.get(category);
+    match category {
+        SearchCategory::Albums => {
+            app.searched_albums
+                .set_items(results.albums.iter().map(Album::from).collect());
+            app.searched_albums.unselect();
+        }
+        SearchCategory::Artists => {
+            app.searched_artists
+                .set_items(results.artists.iter().map(Artist::from).collect());
+            app.searched_artists.unselect();
+        }
+        SearchCategory::Playlists => {
+            app.searched_playlists
+                .set_items(results.playlists.iter().map(Playlist::from).collect());
+            app.searched_playlists.unselect();
+        }
+        SearchCategory::Tracks => {
+            app.searched_tracks
+                .set_items(results.tracks.iter().map(Track::from).collect());
+            app.searched_tracks.unselect();
+        }
     }
+}
 
-    let metadata = fs::metadata(&json_file_path)?;
-    if metadata.len() == 0 {
-        println!("album_search_results.json file is empty");
-        return Ok((Vec::new(), Vec::new()));
+/// Adds the next page of `category` after the results already shown, the selection stays where it was
+pub fn process_more_search_results(
+    app: &mut App,
+    category: SearchCategory,
+    results: &SearchResults,
+) {
+    *app.search_paging.get_mut(category) = results.paging.get(category);
+    let added = match category {
+        SearchCategory::Albums => results.albums.len(),
+        SearchCategory::Artists => results.artists.len(),
+        SearchCategory::Playlists => results.playlists.len(),
+        SearchCategory::Tracks => results.tracks.len(),
+    };
+    match category {
+        SearchCategory::Albums => app
+            .searched_albums
+            .append_items(results.albums.iter().map(Album::from).collect()),
+        SearchCategory::Artists => app
+            .searched_artists
+            .append_items(results.artists.iter().map(Artist::from).collect()),
+        SearchCategory::Playlists => app
+            .searched_playlists
+            .append_items(results.playlists.iter().map(Playlist::from).collect()),
+        SearchCategory::Tracks => app
+            .searched_tracks
+            .append_items(results.tracks.iter().map(Track::from).collect()),
     }
 
-    let data = fs::read_to_string(json_file_path)?;
-
-    let album_response: AlbumResponse = serde_json::from_str(&data).map_err(|e| {
-        println!("Deserialization error: {}", e);
-        e
-    })?;
-
-    let albums = &album_response.albums.items;
-
-    let mut album_names_search_results: Vec<String> = Vec::new();
-    let mut album_links_search_results: Vec<String> = Vec::new();
-
-    for album in albums {
-        album_names_search_results.push(album.name.clone());
-        album_links_search_results.push(album.id.clone());
+    // A page the hidden tracks emptied leaves nothing to move onto, so the next one is loaded right away
+    if added == 0 && app.search_paging.get(category).has_more() {
+        app.search_loading = Some(category);
+        app.dispatch(Task::SearchMore(app.search_query.clone(), category));
     }
-
-    Ok((album_names_search_results, album_links_search_results))
 }
@@ -0,0 +1,504 @@
+// This section runs the queued Spotify requests in the background so the UI never waits on the network
+use crate::app::App;
+use crate::enums::Task;
+use crate::spotify::account::{account, Account};
+use crate::spotify::backend::request::{describe, request_error, RequestError};
+use crate::spotify::library_section::{
+    liked_songs::liked_tracks, made_fy::made_fy, made_fy_tracks::fetch_made_fy_tracks,
+    podcast::user_podcast, recently_played::recently_played, user_album_tracks::user_album_tracks,
+    user_albums::user_albums, user_artist_tracks::user_artist_tracks, user_artists::user_artists,
+};
+use crate::spotify::new_release_section::{
+    new_releases::new_releases, new_releases_tracks::new_releases_tracks,
+};
+use crate::spotify::player::{
+    next_track::next_track, pause_playback::pause, play_playback::play, player::currently_playing,
+    previous_track::previous_track, repeat::cycle_repeat, set_volume::set_volume,
+    shuffle::toogle_shuffle, start_playback::start_playback,
+};
+use crate::spotify::playlist_control::{
+    add_track_to_playlist::add_track_to_playlist, playlist_follow::follow_playlist,
+    playlist_unfollow::unfollow_playlist,
+};
+use crate::spotify::search::{
+    search::{search_category, search_more},
+    search_albums::search_selected_album_tracks,
+    search_artists::search_selected_artist_tracks,
+    search_playlists::search_selected_playlist_tracks,
+};
+use crate::spotify::user_playlist::{
+    user_playlist::get_playlists, user_playlist_track::fetch_playlists_tracks,
+};
+use crate::spotify::user_stats::top_tracks::top_tracks;
+use crate::spotify::{cache, search::search::SearchResults};
+use rspotify::model::{
+    CurrentPlaybackContext, FullArtist, FullTrack, PlayHistory, PlaylistItem, SavedAlbum,
+    SavedTrack, Show, SimplifiedAlbum, SimplifiedPlaylist, SimplifiedTrack,
+};
+use rspotify::ClientError;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::AbortHandle;
+
+/// Data fetched by a task, handed to the UI as typed Spotify objects
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TaskData {
+    None,
+    Playback(Box<CurrentPlaybackContext>),
+    SavedTracks(Vec<SavedTrack>),
+    PlayHistory(Vec<PlayHistory>),
+    SavedAlbums(Vec<SavedAlbum>),
+    Artists(Vec<FullArtist>),
+    Shows(Vec<Show>),
+    Playlists(Vec<SimplifiedPlaylist>),
+    PlaylistItems(Vec<PlaylistItem>),
+    Albums(Vec<SimplifiedAlbum>),
+    AlbumTracks(Vec<SimplifiedTrack>),
+    Tracks(Vec<FullTrack>),
+    // All time, last 6 months and last 4 weeks
+    TopTracks(Vec<FullTrack>, Vec<FullTrack>, Vec<FullTrack>),
+    Search(Box<SearchResults>),
+    Account(Box<Account>),
+}
+
+/// Why a task failed
+#[derive(Debug)]
+pub struct TaskError {
+    pub message: String,
+    // The app can't carry on without the user stepping in, e.g. the login is no longer valid
+    pub fatal: bool,
+    // What the request layer made of the failure, e.g. Spotify being unreachable
+    pub request: Option<RequestError>,
+}
+
+impl From<ClientError> for TaskError {
+    fn from(err: ClientError) -> Self {
+        Self {
+            fatal: matches!(err, ClientError::InvalidToken)
+                || request_error(&err) == Some(&RequestError::LoginExpired),
+            message: describe(&err),
+            request: request_error(&err).cloned(),
+        }
+    }
+}
+
+/// A finished task along with the app snapshot it ran against
+#[derive(Debug)]
+pub struct TaskResult {
+    pub task: Task,
+    pub app: App,
+    pub data: Result<TaskData, TaskError>,
+}
+
+/// Receives queued tasks and runs each of them on the shared runtime
+pub async fn handle_tasks(mut rx: UnboundedReceiver<(Task, App)>, tx: UnboundedSender<TaskResult>) {
+    // Player commands run one after the other, a quick pause and play can't reach Spotify swapped
+    let (player_tx, player_rx) = mpsc::unbounded_channel();
+    tokio::spawn(run_in_order(player_rx, tx.clone()));
+
+    // Search requests in flight, with the search they were made for
+    let mut searches: Vec<(u64, AbortHandle)> = Vec::new();
+
+    while let Some((task, app)) = rx.recv().await {
+        if task.is_player_command() {
+            let _ = player_tx.send((task, app));
+            continue;
+        }
+
+        // The results of an earlier search would be thrown away, so its requests are stopped,
+        // even when the same query was typed again: its pages belong after its own first page
+        if let Task::Search(..) = &task {
+            searches.retain(|(generation, handle)| {
+                let current = *generation == app.search_generation;
+                if !current {
+                    handle.abort();
+                }
+                current && !handle.is_finished()
+            });
+        }
+        let is_search = matches!(task, Task::Search(..) | Task::SearchMore(..));
+
+        let generation = app.search_generation;
+        let tx = tx.clone();
+        let handle = tokio::spawn(complete(task, app, tx));
+        if is_search {
+            searches.push((generation, handle.abort_handle()));
+        }
+    }
+}
+
+// Runs the player commands in the order they were queued, each one waits for the one before
+async fn run_in_order(mut rx: UnboundedReceiver<(Task, App)>, tx: UnboundedSender<TaskResult>) {
+    while let Some((task, app)) = rx.recv().await {
+        complete(task, app, tx.clone()).await;
+    }
+}
+
+// Runs a task and hands its result to the UI
+async fn complete(task: Task, mut app: App, tx: UnboundedSender<TaskResult>) {
+    let data = run_task(&task, &mut app).await;
+
+    // Keep a copy on disk when the cache is enabled, a failed write only loses the copy
+    if let (true, Ok(data), Some(name)) = (app.disk_cache, &data, cache::cache_name(&task)) {
+        let _ = cache::save(&app, name, data);
+    }
+
+    // The UI is gone if the receiver has been dropped, so there is nothing left to update
+    let _ = tx.send(TaskResult { task, app, data });
+}
+
+// Calls the Spotify function that matches the task
+async fn run_task(task: &Task, app: &mut App) -> Result<TaskData, TaskError> {
+    let result = match task {
+        Task::CurrentlyPlaying => currently_playing(app)
+            .await
+            .map(|playback| TaskData::Playback(Box::new(playback))),
+        Task::NextTrack => next_track(app).await.map(|_| TaskData::None),
+        Task::PreviousTrack => previous_track(app).await.map(|_| TaskData::None),
+        Task::Pause => pause(app).await.map(|_| TaskData::None),
+        Task::Play => play(app).await.map(|_| TaskData::None),
+        Task::CycleRepeat => cycle_repeat(app).await.map(|_| TaskData::None),
+        Task::ToggleShuffle => toogle_shuffle(app).await.map(|_| TaskData::None),
+        Task::StartPlayback => start_playback(app).await.map(|_| TaskData::None),
+        Task::Volume(volume_percent) => set_volume(app, *volume_percent)
+            .await
+            .map(|_| TaskData::None),
+
+        Task::MadeFY => made_fy(app).await.map(TaskData::Playlists),
+        Task::MadeFYTracks => fetch_made_fy_tracks(app).await.map(TaskData::PlaylistItems),
+        Task::LikedSongs => liked_tracks(app).await.map(TaskData::SavedTracks),
+        Task::RecentlyPlayed => recently_played(app).await.map(TaskData::PlayHistory),
+        Task::UserAlbums => user_albums(app).await.map(TaskData::SavedAlbums),
+        Task::UserAlbumTracks => user_album_tracks(app).await.map(TaskData::AlbumTracks),
+        Task::UserArtists => user_artists(app).await.map(TaskData::Artists),
+        Task::UserArtistTracks => user_artist_tracks(app).await.map(TaskData::Tracks),
+        Task::Podcasts => user_podcast(app).await.map(TaskData::Shows),
+
+        Task::NewReleases => new_releases(app).await.map(TaskData::Albums),
+        Task::NewReleaseTracks => new_releases_tracks(app).await.map(TaskData::AlbumTracks),
+
+        Task::UserPlaylists => get_playlists(app).await.map(TaskData::Playlists),
+        Task::UserPlaylistTracks => fetch_playlists_tracks(app)
+            .await
+            .map(TaskData::PlaylistItems),
+
+        Task::TopTracks => top_tracks(app)
+            .await
+            .map(|(all_time, six_months, four_weeks)| {
+                TaskData::TopTracks(all_time, six_months, four_weeks)
+            }),
+
+        Task::Search(query, category) => search_category(query, *category, app)
+            .await
+            .map(|results| TaskData::Search(Box::new(results))),
+        Task::SearchMore(query, category) => search_more(query, *category, app)
+            .await
+            .map(|results| TaskData::Search(Box::new(results))),
+        Task::SearchedAlbumTracks => search_selected_album_tracks(app)
+            .await
+            .map(TaskData::AlbumTracks),
+        Task::SearchedArtistTracks => search_selected_artist_tracks(app)
+            .await
+            .map(TaskData::Tracks),
+        Task::SearchedPlaylistTracks => search_selected_playlist_tracks(app)
+            .await
+            .map(TaskData::PlaylistItems),
+
+        Task::AddTrackToPlaylist => add_track_to_playlist(app).await.map(|_| TaskData::None),
+        Task::FollowPlaylist => follow_playlist(app).await.map(|_| TaskData::None),
+        Task::UnfollowPlaylist => unfollow_playlist(app).await.map(|_| TaskData::None),
+
+        Task::Account => account(app)
+            .await
+            .map(|account| TaskData::Account(Box::new(account))),
+    };
+
+    result.map_err(TaskError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Library, Menu, SearchCategory, View};
+    use crate::handlers::library::library_enter_event;
+    use crate::handlers::playlist_control::add_track_to_playlist::{
+        add_track_to_playlist_enter_event, add_track_to_playlist_event,
+    };
+    use crate::handlers::playlist_control::follow_playlist::follow_playlist_event;
+    use crate::handlers::playlist_control::unfollow_playlist::unfollow_playlist_event;
+    use crate::handlers::search::{go_to_search_event, live_search_event, start_search};
+    use crate::handlers::task::task_completed_event;
+    use crate::spotify::backend::fake::FakeBackend;
+    use crate::spotify::backend::SpotifyBackend;
+    use crate::spotify::search::search::{process_more_search_results, Paging, SearchResults};
+    use crate::structs::Settings;
+    use rspotify::model::PlayableItem;
+    use std::sync::Arc;
+
+    // An app on the fake backend, the tasks its handlers dispatch wait on the receiver
+    fn fake_app() -> (App, UnboundedReceiver<(Task, App)>, FakeBackend) {
+        let backend = FakeBackend::new();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = App {
+            backend: Some(Arc::new(backend.clone())),
+            task_sender: Some(tx),
+            ..App::default()
+        };
+        (app, rx, backend)
+    }
+
+    // Runs the queued tasks like the main loop would, the tasks their results dispatch are run too
+    async fn run_queued(app: &mut App, rx: &mut UnboundedReceiver<(Task, App)>) -> Vec<Task> {
+        let mut settings = Settings::default();
+        let mut ran = Vec::new();
+        while let Ok((task, mut snapshot)) = rx.try_recv() {
+            let data = run_task(&task, &mut snapshot).await;
+            ran.push(task.clone());
+            let result = TaskResult {
+                task,
+                app: snapshot,
+                data,
+            };
+            task_completed_event(app, &mut settings, result);
+        }
+        ran
+    }
+
+    fn names<'a>(names: impl Iterator<Item = &'a String>) -> Vec<&'a str> {
+        names.map(String::as_str).collect()
+    }
+
+    async fn playlist_track_names(backend: &FakeBackend, playlist_id: &str) -> Vec<String> {
+        backend
+            .playlist_items(playlist_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|item| match item.track {
+                Some(PlayableItem::Track(track)) => Some(track.name),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Opens a library section as Enter on the library list does
+    async fn open_library_section(
+        app: &mut App,
+        rx: &mut UnboundedReceiver<(Task, App)>,
+        section: Library,
+    ) {
+        app.navigate(View::Library);
+        app.library.select(usize::from(section));
+        library_enter_event(app);
+        run_queued(app, rx).await;
+    }
+
+    #[tokio::test]
+    async fn liked_songs_open_from_the_library() {
+        let (mut app, mut rx, _) = fake_app();
+
+        open_library_section(&mut app, &mut rx, Library::LikedSongs).await;
+
+        assert_eq!(
+            app.navigation.current(),
+            View::LibrarySection(Library::LikedSongs)
+        );
+        assert_eq!(
+            names(app.liked_songs.iter().map(|track| &track.name)),
+            ["First Light", "Chrome Hearts", "After Hours", "Crosswind"]
+        );
+        assert_eq!(app.liked_songs.selected_index(), Some(0));
+        // The fake marks Crosswind as not playable in the account's country
+        assert!(app.liked_songs.items()[3].unplayable);
+    }
+
+    #[tokio::test]
+    async fn a_liked_song_is_added_to_a_playlist() {
+        let (mut app, mut rx, backend) = fake_app();
+        app.dispatch(Task::UserPlaylists);
+        run_queued(&mut app, &mut rx).await;
+        open_library_section(&mut app, &mut rx, Library::LikedSongs).await;
+
+        app.liked_songs.select(3);
+        add_track_to_playlist_event(&mut app);
+        assert_eq!(app.selected_menu, Menu::AddTrackToPlaylist);
+        app.add_track_to_playlist.select(0);
+        add_track_to_playlist_enter_event(&mut app);
+        let ran = run_queued(&mut app, &mut rx).await;
+
+        assert!(matches!(ran[..], [Task::AddTrackToPlaylist]));
+        // Added tracks go to the top of the playlist
+        assert_eq!(
+            playlist_track_names(&backend, "fakeplaylist01").await,
+            ["Crosswind", "First Light", "Blue Smoke", "Paper Skies"]
+        );
+        assert_eq!(
+            app.log.last_message().as_deref(),
+            Some("Added Crosswind to the playlist")
+        );
+        assert_eq!(app.selected_menu, Menu::Library);
+    }
+
+    #[tokio::test]
+    async fn following_and_unfollowing_refetch_the_playlists() {
+        let (mut app, mut rx, _) = fake_app();
+        app.dispatch(Task::UserPlaylists);
+        run_queued(&mut app, &mut rx).await;
+        assert!(!app.user_playlists.iter().any(|p| p.name == "Daily Mix 1"));
+
+        // Follow a playlist found by the search
+        app.navigate(View::Search);
+        app.search_query = "daily".to_string();
+        start_search(&mut app);
+        run_queued(&mut app, &mut rx).await;
+        assert!(app.search_pending.is_empty());
+        app.selected_playlist_in_search_result = true;
+        app.searched_playlists.select(0);
+        follow_playlist_event(&mut app);
+        let ran = run_queued(&mut app, &mut rx).await;
+
+        assert!(matches!(
+            ran[..],
+            [Task::FollowPlaylist, Task::UserPlaylists]
+        ));
+        assert!(app.user_playlists.iter().any(|p| p.name == "Daily Mix 1"));
+
+        // And unfollow it from the playlists
+        app.navigate(View::Playlists);
+        let index = app
+            .user_playlists
+            .iter()
+            .position(|p| p.name == "Daily Mix 1")
+            .unwrap();
+        app.user_playlists.select(index);
+        unfollow_playlist_event(&mut app);
+        let ran = run_queued(&mut app, &mut rx).await;
+
+        assert!(matches!(
+            ran[..],
+            [Task::UnfollowPlaylist, Task::UserPlaylists]
+        ));
+        assert!(!app.user_playlists.iter().any(|p| p.name == "Daily Mix 1"));
+    }
+
+    #[tokio::test]
+    async fn a_task_without_a_backend_asks_for_a_login() {
+        let mut app = App::default();
+        let err = run_task(&Task::UserPlaylists, &mut app).await.unwrap_err();
+        assert!(err.message.starts_with("Not logged in"));
+        assert!(!err.fatal);
+    }
+
+    #[tokio::test]
+    async fn each_type_of_search_results_fills_its_pane() {
+        let (mut app, mut rx, _) = fake_app();
+        app.navigate(View::Search);
+        app.search_query = "paper".to_string();
+        app.search_filters.artists = false;
+
+        start_search(&mut app);
+        assert_eq!(
+            app.search_pending,
+            [
+                SearchCategory::Albums,
+                SearchCategory::Playlists,
+                SearchCategory::Tracks
+            ]
+        );
+        run_queued(&mut app, &mut rx).await;
+
+        assert!(app.search_pending.is_empty());
+        assert_eq!(
+            names(app.searched_albums.iter().map(|album| &album.name)),
+            ["Paper Skies"]
+        );
+        assert_eq!(
+            names(app.searched_tracks.iter().map(|track| &track.name)),
+            ["Paper Skies"]
+        );
+        assert!(app.searched_artists.is_empty());
+        // Nothing is highlighted until a pane gets the focus
+        assert_eq!(app.searched_tracks.selected_index(), None);
+    }
+
+    #[tokio::test]
+    async fn results_for_an_earlier_search_of_the_same_query_are_dropped() {
+        let (mut app, mut rx, _) = fake_app();
+        go_to_search_event(&mut app);
+        app.search_filters.albums = false;
+        app.search_filters.artists = false;
+        app.search_filters.playlists = false;
+
+        // paper, then papers, then back to paper before the first search returned
+        app.input = "paper".to_string();
+        live_search_event(&mut app);
+        let (first, mut snapshot) = rx.try_recv().unwrap();
+        app.input.push('s');
+        live_search_event(&mut app);
+        app.input.pop();
+        live_search_event(&mut app);
+        run_queued(&mut app, &mut rx).await;
+        app.searched_tracks.select(0);
+
+        let data = run_task(&first, &mut snapshot).await;
+        let result = TaskResult {
+            task: first,
+            app: snapshot,
+            data,
+        };
+        task_completed_event(&mut app, &mut Settings::default(), result);
+
+        // The late results didn't replace the pane the user is moving through
+        assert_eq!(app.searched_tracks.selected_index(), Some(0));
+    }
+
+    #[test]
+    fn a_page_left_empty_by_the_hidden_tracks_loads_the_next_one() {
+        let (mut app, mut rx, _) = fake_app();
+        app.hide_unplayable = true;
+        app.search_query = "paper".to_string();
+
+        let mut results = SearchResults::default();
+        results.paging.tracks = Paging {
+            next_offset: 20,
+            total: 30,
+        };
+        process_more_search_results(&mut app, SearchCategory::Tracks, &results);
+
+        assert_eq!(app.search_loading, Some(SearchCategory::Tracks));
+        let (task, _) = rx.try_recv().unwrap();
+        assert!(matches!(
+            task,
+            Task::SearchMore(query, SearchCategory::Tracks) if query == "paper"
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_page_loaded_for_an_earlier_search_is_dropped() {
+        let (mut app, mut rx, _) = fake_app();
+        app.navigate(View::Search);
+        app.search_query = "paper".to_string();
+
+        // The next page is still loading when the same query is searched again
+        app.dispatch(Task::SearchMore(
+            app.search_query.clone(),
+            SearchCategory::Tracks,
+        ));
+        let (more, mut snapshot) = rx.try_recv().unwrap();
+        start_search(&mut app);
+        run_queued(&mut app, &mut rx).await;
+
+        let data = run_task(&more, &mut snapshot).await;
+        let result = TaskResult {
+            task: more,
+            app: snapshot,
+            data,
+        };
+        task_completed_event(&mut app, &mut Settings::default(), result);
+
+        assert_eq!(
+            names(app.searched_tracks.iter().map(|track| &track.name)),
+            ["Paper Skies"]
+        );
+    }
+}
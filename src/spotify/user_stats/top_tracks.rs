@@ -1,66 +1,43 @@
 use crate::app::App;
-use crate::spotify::auth::get_spotify_client;
-use futures::future::join_all;
-use futures::stream::StreamExt; // Add this import
-use futures::FutureExt;
-use futures_util::TryStreamExt;
-use rayon::prelude::*;
+use crate::models::Track;
+use crate::spotify::backend::get_backend;
+use futures::join;
 use rspotify::model::TimeRange;
-use rspotify::prelude::OAuthClient;
 use rspotify::{model::FullTrack, ClientError};
 
-/// Fetches a user's top tracks from Spotify for different time ranges and processes the data
-#[tokio::main]
-pub async fn top_tracks(app: &mut App) -> Result<(), ClientError> {
-    // Get a Spotify client using an existing access token (if available).
-    let spotify = get_spotify_client(app).await?;
-
-    // Define the time ranges
-    let time_ranges = [
-        (TimeRange::LongTerm, &mut app.top_tracks_all_time_names),
-        (TimeRange::MediumTerm, &mut app.top_tracks_6_months_names),
-        (TimeRange::ShortTerm, &mut app.top_tracks_4_weeks_names),
-    ];
+// Limit the number of top tracks to 30
+const TOP_TRACKS_LIMIT: usize = 30;
 
-    // Collect and process the user's top tracks for each time range concurrently
-    let futures = time_ranges
-        .iter()
-        .map(|(time_range, _)| {
-            let spotify = spotify.clone();
-            async move {
-                let mut top_tracks = Vec::new();
-                let stream = spotify
-                    .current_user_top_tracks(Some(*time_range))
-                    .take(30) // Limit the number of top tracks to 20
-                    .try_for_each(|item| {
-                        top_tracks.push(item);
-                        futures::future::ok(())
-                    })
-                    .boxed();
-                stream.await?;
-                Ok(top_tracks)
-            }
-        })
-        .collect::<Vec<_>>();
+/// Fetches a user's top tracks from Spotify for the all time, 6 months and 4 weeks ranges
+pub async fn top_tracks(
+    app: &mut App,
+) -> Result<(Vec<FullTrack>, Vec<FullTrack>, Vec<FullTrack>), ClientError> {
+    // Get a Spotify client using an existing access token (if available).
+    let spotify = get_backend(app).await?;
 
-    let results: Vec<Result<Vec<FullTrack>, ClientError>> = join_all(futures).await;
+    // Collect the user's top tracks for each time range concurrently
+    let (all_time, six_months, four_weeks) = join!(
+        spotify.top_tracks(TimeRange::LongTerm, TOP_TRACKS_LIMIT),
+        spotify.top_tracks(TimeRange::MediumTerm, TOP_TRACKS_LIMIT),
+        spotify.top_tracks(TimeRange::ShortTerm, TOP_TRACKS_LIMIT),
+    );
 
-    // Process the results
-    for (i, result) in results.into_iter().enumerate() {
-        if let Ok(top_tracks) = result {
-            let names = match i {
-                0 => &mut app.top_tracks_all_time_names,
-                1 => &mut app.top_tracks_6_months_names,
-                2 => &mut app.top_tracks_4_weeks_names,
-                _ => panic!("Invalid index"),
-            };
-            let track_names: Vec<String> = top_tracks
-                .par_iter()
-                .map(|track| track.name.to_string())
-                .collect();
-            names.extend(track_names);
-        }
-    }
+    // A time range that failed to load is shown as empty
+    Ok((
+        all_time.unwrap_or_default(),
+        six_months.unwrap_or_default(),
+        four_weeks.unwrap_or_default(),
+    ))
+}
 
-    Ok(())
+/// Populates the app's data structures with the user's top tracks
+pub fn process_top_tracks(
+    app: &mut App,
+    all_time: &[FullTrack],
+    six_months: &[FullTrack],
+    four_weeks: &[FullTrack],
+) {
+    app.top_tracks_all_time = all_time.iter().map(Track::from).collect();
+    app.top_tracks_6_months = six_months.iter().map(Track::from).collect();
+    app.top_tracks_4_weeks = four_weeks.iter().map(Track::from).collect();
 }
@@ -0,0 +1,24 @@
+// Helpers for reading the fields the app shows out of the Spotify objects
+use rspotify::model::{FullTrack, PlayableItem, PlaylistItem, SimplifiedArtist};
+use std::collections::HashMap;
+
+/// Returns the open.spotify.com link of an object, or an empty string if it has none
+pub fn spotify_url(external_urls: &HashMap<String, String>) -> String {
+    external_urls.get("spotify").cloned().unwrap_or_default()
+}
+
+/// Returns the name of the first artist, or an empty string if there are no artists
+pub fn first_artist_name(artists: &[SimplifiedArtist]) -> String {
+    artists
+        .first()
+        .map(|artist| artist.name.clone())
+        .unwrap_or_default()
+}
+
+/// Returns the tracks of a playlist, skipping episodes and items Spotify no longer has
+pub fn playlist_tracks(items: &[PlaylistItem]) -> impl Iterator<Item = &FullTrack> {
+    items.iter().filter_map(|item| match &item.track {
+        Some(PlayableItem::Track(track)) => Some(track),
+        _ => None,
+    })
+}
@@ -1,4 +1,12 @@
+use async_trait::async_trait;
 use ratatui::style::Color;
+use rspotify::ClientError;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::enums::{SetupStep, Severity, View};
+use crate::spotify::auth::LoginPrompt;
 
 #[derive(Clone, Debug)]
 pub struct Key {
@@ -18,6 +26,9 @@ pub struct Key {
     pub change_keybind: char,
     pub refresh_key: char,
     pub open_config_fold_key: char,
+    pub profiles_key: char,
+    pub account_key: char,
+    pub saved_searches_key: char,
 
     pub first_keys: Vec<String>,
     pub tasks: Vec<String>,
@@ -42,6 +53,9 @@ impl Default for Key {
             player_fullscreen_key: ' ',
             change_keybind: ' ',
             refresh_key: ' ',
+            profiles_key: ' ',
+            account_key: ' ',
+            saved_searches_key: ' ',
 
             first_keys: Vec::new(),
             tasks: Vec::new(),
@@ -132,7 +146,7 @@ impl Default for Themes {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Settings {
     // Volume controls
     pub volume_increment_value: u8,
@@ -141,13 +155,352 @@ pub struct Settings {
     pub theme_name: String,
 }
 
-impl Default for Settings {
+/// Back and forward history of the views, the current view is the one on screen
+#[derive(Clone, Debug)]
+pub struct Navigation {
+    back: Vec<View>,
+    current: View,
+    forward: Vec<View>,
+}
+
+impl Default for Navigation {
     fn default() -> Self {
         Self {
-            volume_increment_value: 0,
-            volume_decreament_value: 0,
-            volume_percent: 0,
-            theme_name: String::new(),
+            back: Vec::new(),
+            current: View::Main,
+            forward: Vec::new(),
+        }
+    }
+}
+
+impl Navigation {
+    /// Views kept to go back to, the oldest ones are dropped
+    pub const LIMIT: usize = 100;
+
+    pub fn current(&self) -> View {
+        self.current
+    }
+
+    /// Opens a view, the views that were ahead of the current one are dropped
+    pub fn push(&mut self, view: View) {
+        if view != self.current {
+            self.back.push(self.current);
+            if self.back.len() > Self::LIMIT {
+                self.back.remove(0);
+            }
+            self.current = view;
+            self.forward.clear();
+        }
+    }
+
+    /// Returns to the previous view, `false` if there is none
+    pub fn back(&mut self) -> bool {
+        match self.back.pop() {
+            Some(view) => {
+                self.forward.push(self.current);
+                self.current = view;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Goes to the view that was left with `back`, `false` if there is none
+    pub fn forward(&mut self) -> bool {
+        match self.forward.pop() {
+            Some(view) => {
+                self.back.push(self.current);
+                self.current = view;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Queries searched lately, the newest last, Up and Down in the search box step through them
+#[derive(Clone, Debug, Default)]
+pub struct SearchHistory {
+    entries: Vec<String>,
+    // The entry shown in the search box, None while the user types a new query
+    position: Option<usize>,
+    // What was typed before stepping into the history, given back when stepping out of it
+    draft: String,
+}
+
+impl SearchHistory {
+    /// Queries kept, the oldest ones are dropped
+    pub const LIMIT: usize = 50;
+
+    pub fn new(entries: Vec<String>) -> Self {
+        Self {
+            entries,
+            ..Self::default()
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Adds a searched query as the newest entry, searching it again moves it to the end
+    pub fn record(&mut self, query: &str) {
+        self.position = None;
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != query);
+        self.entries.push(query.to_string());
+        if self.entries.len() > Self::LIMIT {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Leaves the history once the text in the search box changes, `typed` is the new draft
+    pub fn edited(&mut self, typed: &str) {
+        self.position = None;
+        self.draft = typed.to_string();
+    }
+
+    /// The entry before the one shown, `typed` is kept for when the user steps back out
+    pub fn older(&mut self, typed: &str) -> Option<&str> {
+        let position = match self.position {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = typed.to_string();
+                self.entries.len() - 1
+            }
+            Some(position) => position.saturating_sub(1),
+        };
+        self.position = Some(position);
+        Some(&self.entries[position])
+    }
+
+    /// The entry after the one shown, past the newest one the text typed before
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position?;
+        if position + 1 < self.entries.len() {
+            self.position = Some(position + 1);
+            Some(&self.entries[position + 1])
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+}
+
+/// A message shown for a moment over the interface
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub severity: Severity,
+    pub message: String,
+    pub created: Instant,
+}
+
+// Older notifications are dropped once there are more than this many on screen
+const NOTIFICATION_LIMIT: usize = 4;
+
+/// Queue of the notifications on screen
+///
+/// Clones share the same queue, so a background task working on an app snapshot can push to it too.
+#[derive(Clone, Debug, Default)]
+pub struct Notifications {
+    queue: Arc<Mutex<VecDeque<Notification>>>,
+}
+
+impl Notifications {
+    pub fn push(&self, severity: Severity, message: impl Into<String>) {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        queue.push_back(Notification {
+            severity,
+            message: message.into(),
+            created: Instant::now(),
+        });
+        while queue.len() > NOTIFICATION_LIMIT {
+            queue.pop_front();
+        }
+    }
+
+    /// The notifications that haven't timed out yet, oldest first
+    pub fn visible(&self) -> Vec<Notification> {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        queue.retain(|notification| {
+            notification.created.elapsed() < notification.severity.duration()
+        });
+        queue.iter().cloned().collect()
+    }
+}
+
+/// What the setup screen has been given and what it shows
+#[derive(Clone, Debug, Default)]
+pub struct Setup {
+    pub step: SetupStep,
+    pub client_id: String,
+    pub client_secret: String,
+    // Typed in when the redirect of the login can't be caught
+    pub redirected_url: String,
+    // Why the last step failed, shown until the user moves on
+    pub error: String,
+    // Set when the credentials are entered, the main loop starts the login
+    pub start_login: bool,
+    pub login: SetupLogin,
+    // Logging in again with the creds already saved, they aren't asked for or written
+    pub relogin: bool,
+    // The profile being switched to, the app keeps the current one until the login is done
+    pub profile: Option<String>,
+}
+
+/// The login running in the background, as the setup screen sees it
+///
+/// Clones share the same state, so the login task can show its URL and read what the user pastes.
+#[derive(Clone, Debug, Default)]
+pub struct SetupLogin {
+    state: Arc<Mutex<SetupLoginState>>,
+}
+
+#[derive(Debug, Default)]
+struct SetupLoginState {
+    auth_url: String,
+    paste_reason: Option<String>,
+    pasted_url: Option<String>,
+}
+
+// How often the login checks whether a redirected URL has been pasted
+const PASTE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+impl SetupLogin {
+    /// The URL to log in at, empty until the login has started
+    pub fn auth_url(&self) -> String {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .auth_url
+            .clone()
+    }
+
+    /// Why the redirected URL has to be pasted, None while spoify catches it itself
+    pub fn paste_reason(&self) -> Option<String> {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .paste_reason
+            .clone()
+    }
+
+    /// Hands the URL the user pasted to the login
+    pub fn paste(&self, url: String) {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pasted_url = Some(url);
+    }
+}
+
+#[async_trait]
+impl LoginPrompt for SetupLogin {
+    fn show_login(&self, auth_url: &str, _browser_opened: bool, paste_reason: Option<String>) {
+        // The screen always shows the URL, the browser may have opened on another display
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.auth_url = auth_url.to_string();
+        state.paste_reason = paste_reason;
+        state.pasted_url = None;
+    }
+
+    async fn redirected_url(&self) -> Result<String, ClientError> {
+        loop {
+            let pasted_url = self
+                .state
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .pasted_url
+                .take();
+            if let Some(url) = pasted_url {
+                return Ok(url);
+            }
+            tokio::time::sleep(PASTE_POLL_INTERVAL).await;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_oldest_views_are_dropped_past_the_limit() {
+        let mut navigation = Navigation::default();
+        for _ in 0..Navigation::LIMIT {
+            navigation.push(View::Search);
+            navigation.push(View::NewReleases);
+        }
+
+        let mut steps = 0;
+        while navigation.back() {
+            steps += 1;
+        }
+        assert_eq!(steps, Navigation::LIMIT);
+        // The first views were dropped, so going all the way back doesn't reach the main view
+        assert_ne!(navigation.current(), View::Main);
+    }
+
+    fn history(queries: &[&str]) -> SearchHistory {
+        let mut history = SearchHistory::default();
+        for query in queries {
+            history.record(query);
+        }
+        history
+    }
+
+    #[test]
+    fn a_query_searched_again_moves_to_the_end() {
+        let history = history(&["paper", "glacier ", "  ", "paper"]);
+        assert_eq!(history.entries(), ["glacier", "paper"]);
+    }
+
+    #[test]
+    fn the_oldest_queries_are_dropped_past_the_limit() {
+        let queries: Vec<String> = (0..=SearchHistory::LIMIT)
+            .map(|query| format!("query {query}"))
+            .collect();
+        let mut history = SearchHistory::default();
+        for query in &queries {
+            history.record(query);
+        }
+        assert_eq!(history.entries().len(), SearchHistory::LIMIT);
+        assert_eq!(history.entries()[0], "query 1");
+    }
+
+    #[test]
+    fn stepping_through_the_history_gives_back_the_draft() {
+        let mut history = history(&["paper", "glacier"]);
+        assert_eq!(history.newer(), None);
+
+        assert_eq!(history.older("outr"), Some("glacier"));
+        assert_eq!(history.older("glacier"), Some("paper"));
+        // The oldest entry stays put
+        assert_eq!(history.older("paper"), Some("paper"));
+        assert_eq!(history.newer(), Some("glacier"));
+        assert_eq!(history.newer(), Some("outr"));
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn typing_leaves_the_history() {
+        let mut history = history(&["paper", "glacier"]);
+        history.older("");
+        history.older("");
+        history.edited("papers");
+
+        // Up starts again from the newest entry, Down past it gives back the edited text
+        assert_eq!(history.older("papers"), Some("glacier"));
+        assert_eq!(history.newer(), Some("papers"));
+    }
+
+    #[test]
+    fn an_empty_history_has_nothing_to_step_through() {
+        let mut history = SearchHistory::default();
+        assert_eq!(history.older("paper"), None);
+        assert_eq!(history.newer(), None);
+    }
+}
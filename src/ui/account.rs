@@ -0,0 +1,169 @@
+use chrono::{Local, Utc};
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{block::Title, Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use rspotify::prelude::Id;
+
+use crate::{
+    app::App,
+    enums::AuthFlow,
+    spotify::auth::{requested_scopes, token_cache_path},
+    structs::{Key, Themes},
+};
+
+/// Renders the account screen, the logged in user and the state of the token
+pub fn render_account(f: &mut Frame, app: &mut App, key: &mut Key, theme: &mut Themes) {
+    let account_label = format!(
+        "Account (press {} to close, Enter to log in again, Ctrl+O to log out)",
+        key.account_key
+    );
+    let highlight = Style::new().fg(theme.main_highlight_color);
+    let warning = Style::new().fg(Color::Red);
+
+    let mut lines = Vec::new();
+    if app.login_expired {
+        lines.push(Line::styled(
+            "Spotify no longer accepts the login, press Enter to log in again",
+            warning,
+        ));
+        lines.push(Line::from(""));
+    }
+
+    lines.push(field("Profile", app.profile.clone(), highlight));
+    lines.push(field(
+        "Login",
+        match app.auth_flow {
+            AuthFlow::Pkce => "PKCE",
+            AuthFlow::ClientSecret => "Client Secret",
+        },
+        highlight,
+    ));
+
+    match &app.account {
+        None => lines.push(Line::from("Loading the account...")),
+        Some(account) => {
+            let user = &account.user;
+            lines.push(field(
+                "User",
+                format!(
+                    "{} ({})",
+                    user.display_name.as_deref().unwrap_or("no display name"),
+                    user.id.id()
+                ),
+                highlight,
+            ));
+            lines.push(field(
+                "Email",
+                user.email.as_deref().unwrap_or("not shared"),
+                highlight,
+            ));
+            lines.push(field(
+                "Plan",
+                match user.product {
+                    Some(product) => format!("{:?}", product),
+                    None => "unknown".to_string(),
+                },
+                highlight,
+            ));
+            lines.push(Line::from(""));
+
+            match &account.token {
+                None => lines.push(Line::styled("No token, log in again", warning)),
+                Some(token) => {
+                    lines.push(match token.expires_at {
+                        Some(expires_at) if expires_at > Utc::now() => field(
+                            "Token",
+                            format!(
+                                "valid until {} ({} minutes left)",
+                                expires_at.with_timezone(&Local).format("%H:%M"),
+                                (expires_at - Utc::now()).num_minutes()
+                            ),
+                            highlight,
+                        ),
+                        Some(expires_at) => field(
+                            "Token",
+                            format!(
+                                "expired at {}",
+                                expires_at.with_timezone(&Local).format("%H:%M")
+                            ),
+                            warning,
+                        ),
+                        None => field("Token", "no expiry", highlight),
+                    });
+                    // Without a refresh token the login has to be repeated once the token expires
+                    lines.push(field(
+                        "Renewal",
+                        if token.refresh_token.is_some() {
+                            "automatic"
+                        } else {
+                            "not possible, log in again once it expires"
+                        },
+                        highlight,
+                    ));
+
+                    let mut granted: Vec<&String> = token.scopes.iter().collect();
+                    granted.sort();
+                    let mut missing: Vec<String> = requested_scopes()
+                        .into_iter()
+                        .filter(|scope| !token.scopes.contains(scope))
+                        .collect();
+                    missing.sort();
+
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(format!("Granted scopes ({}):", granted.len())));
+                    lines.push(Line::styled(
+                        granted
+                            .iter()
+                            .map(|scope| scope.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        highlight,
+                    ));
+                    if !missing.is_empty() {
+                        lines.push(Line::from(""));
+                        lines.push(Line::styled(
+                            format!(
+                                "Missing scopes, log in again to grant them: {}",
+                                missing.join(", ")
+                            ),
+                            warning,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(field(
+        "Token file",
+        token_cache_path(app).display().to_string(),
+        highlight,
+    ));
+
+    let account_block = Block::default()
+        .borders(Borders::ALL)
+        .title(Title::from(account_label))
+        .border_style(Style::new().fg(theme.main_border_color))
+        .style(Style::default().bg(theme.main_background_color));
+
+    let account_text = Paragraph::new(lines)
+        .block(account_block)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, f.size());
+    f.render_widget(account_text, f.size());
+}
+
+// A label and its value on one line
+fn field(label: &str, value: impl Into<String>, style: Style) -> Line<'static> {
+    Line::from(vec![
+        Span::raw(format!("{:<12}", label)),
+        Span::styled(value.into(), style),
+    ])
+}
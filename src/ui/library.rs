@@ -6,7 +6,11 @@ use ratatui::{
 };
 
 use crate::ui::util::{album_table_ui, artist_table_ui, podcast_table_ui, track_table_ui};
-use crate::{app::App, structs::Themes};
+use crate::{
+    app::App,
+    enums::{Library, View},
+    structs::Themes,
+};
 
 use super::{
     search::util::{searched_track_table_for_album_ui, searched_track_table_for_artist_ui},
@@ -27,66 +31,6 @@ pub fn render_library(
         .border_style(Style::new().fg(theme.library_border_color))
         .style(Style::default().bg(theme.library_background_color));
 
-    let liked_song_block = Block::default()
-        .borders(Borders::ALL)
-        .title(Title::from("Liked Songs"))
-        .border_style(if app.liked_songs_selected {
-            Style::default().fg(theme.main_border_color)
-        } else {
-            Style::default().fg(theme.main_inactive_border_color)
-        })
-        .style(Style::default().bg(theme.main_background_color));
-
-    let recently_played_block = Block::default()
-        .borders(Borders::ALL)
-        .title(Title::from("Recently Played"))
-        .border_style(if app.recently_played_selected {
-            Style::default().fg(theme.main_border_color)
-        } else {
-            Style::default().fg(theme.main_inactive_border_color)
-        })
-        .style(Style::default().bg(theme.main_background_color));
-
-    let user_album_block = Block::default()
-        .borders(Borders::ALL)
-        .title(Title::from("Albums"))
-        .border_style(if app.user_album_selected {
-            Style::default().fg(theme.main_border_color)
-        } else {
-            Style::default().fg(theme.main_inactive_border_color)
-        })
-        .style(Style::default().bg(theme.main_background_color));
-
-    let podcast_block = Block::default()
-        .borders(Borders::ALL)
-        .title(Title::from("Podcasts"))
-        .border_style(if app.podcast_selected {
-            Style::default().fg(theme.main_border_color)
-        } else {
-            Style::default().fg(theme.main_inactive_border_color)
-        })
-        .style(Style::default().bg(theme.main_background_color));
-
-    let user_artist_block = Block::default()
-        .borders(Borders::ALL)
-        .title(Title::from("Artists"))
-        .border_style(if app.user_artist_selected {
-            Style::default().fg(theme.main_border_color)
-        } else {
-            Style::default().fg(theme.main_inactive_border_color)
-        })
-        .style(Style::default().bg(theme.main_background_color));
-
-    let made_fy_block = Block::default()
-        .borders(Borders::ALL)
-        .title(Title::from("Made For You"))
-        .border_style(if app.made_fy_selected {
-            Style::default().fg(theme.main_border_color)
-        } else {
-            Style::default().fg(theme.main_inactive_border_color)
-        })
-        .style(Style::default().bg(theme.main_background_color));
-
     let library_items = vec![
         String::from("Made For You"),
         String::from("Recently Played"),
@@ -100,230 +44,170 @@ pub fn render_library(
         .block(library_block)
         .highlight_style(Style::default().fg(theme.library_highlight_color));
 
-    f.render_stateful_widget(library_list, content_sub_chunk[0], &mut app.library_state);
-
-    // Render content for the selected library section based on app state.
-    if app.made_fy_display {
-        f.render_widget(Clear, content_chunk[1]);
-
-        let made_fy_playlist_table = made_fy_table_ui(
-            app.made_fy_playlist_names.clone(),
-            app.made_fy_playlist_track_total.clone(),
-            made_fy_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(
-            made_fy_playlist_table,
-            content_chunk[1],
-            &mut app.made_fy_state,
-        );
-    }
-
-    if app.made_fy_track_display {
-        let made_fy_track_label = app.made_fy_playlist_names[app.made_fy_index].to_string();
-
-        let made_fy_track_block = Block::default()
-            .borders(Borders::ALL)
-            .title(Title::from(made_fy_track_label))
-            .border_style(if app.made_fy_track_selected {
-                Style::default().fg(theme.main_border_color)
-            } else {
-                Style::default().fg(theme.main_inactive_border_color)
-            })
-            .style(Style::default().bg(theme.main_background_color));
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        let made_fy_track_table = track_table_ui(
-            app.made_fy_track_names.clone(),
-            app.made_fy_artist_names.clone(),
-            app.made_fy_album_names.clone(),
-            app.made_fy_track_duration.clone(),
-            made_fy_track_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(
-            made_fy_track_table,
-            content_chunk[1],
-            &mut app.made_fy_track_state,
-        );
-    }
-
-    if app.liked_song_display {
-        f.render_widget(Clear, content_chunk[1]);
-
-        let liked_songs_table = track_table_ui(
-            app.liked_song_names.clone(),
-            app.liked_song_artist_names.clone(),
-            app.liked_song_album_names.clone(),
-            app.liked_song_duration.clone(),
-            liked_song_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(
-            liked_songs_table,
-            content_chunk[1],
-            &mut app.liked_songs_state,
-        );
-    }
-
-    if app.recently_played_display {
-        f.render_widget(Clear, content_chunk[1]);
-
-        let recently_played_table = track_table_ui(
-            app.recently_played_names.clone(),
-            app.recently_played_artist_names.clone(),
-            app.recently_played_album_names.clone(),
-            app.recently_played_duration.clone(),
-            recently_played_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(
-            recently_played_table,
-            content_chunk[1],
-            &mut app.recently_played_state,
-        );
-    }
-
-    if app.podcast_display {
-        f.render_widget(Clear, content_chunk[1]);
-
-        let podcast_table = podcast_table_ui(
-            app.podcast_names.clone(),
-            app.podcast_publisher.clone(),
-            podcast_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(podcast_table, content_chunk[1], &mut app.podcast_state);
-    }
+    app.library
+        .render_list(f, library_list, content_sub_chunk[0]);
 
-    if app.user_artist_display {
-        f.render_widget(Clear, content_chunk[1]);
-
-        let artist_table = artist_table_ui(
-            app.user_artist_names.clone(),
-            user_artist_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(artist_table, content_chunk[1], &mut app.user_artist_state);
-    }
-
-    if app.user_album_display {
-        f.render_widget(Clear, content_chunk[1]);
-
-        let user_album_table = album_table_ui(
-            app.user_album_names.clone(),
-            app.user_album_artist_names.clone(),
-            app.user_album_tracks.clone(),
-            user_album_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(
-            user_album_table,
-            content_chunk[1],
-            &mut app.user_album_state,
-        );
-    }
-    if app.user_album_track_display {
-        let user_album_track_label = app.user_album_names[app.user_album_index].to_string();
-
-        let user_album_track_block = Block::default()
+    // The content next to the list is highlighted while it has the focus
+    let content_focused = !app.can_navigate_menu;
+    let content_block = |title: String| {
+        Block::default()
             .borders(Borders::ALL)
-            .title(Title::from(user_album_track_label))
-            .border_style(if app.user_album_track_selected {
+            .title(Title::from(title))
+            .border_style(if content_focused {
                 Style::default().fg(theme.main_border_color)
             } else {
                 Style::default().fg(theme.main_inactive_border_color)
             })
-            .style(Style::default().bg(theme.main_background_color));
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        let user_album_track_table = searched_track_table_for_album_ui(
-            app.user_album_track_names.clone(),
-            app.user_album_track_artist.clone(),
-            app.user_album_track_duration.clone(),
-            user_album_track_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(
-            user_album_track_table,
-            content_chunk[1],
-            &mut app.user_album_track_state,
-        );
-    }
-    if app.user_artist_track_display {
-        let user_artist_track_label = app.user_artist_names[app.user_artist_index].to_string();
-
-        let user_artist_track_block = Block::default()
-            .borders(Borders::ALL)
-            .title(Title::from(user_artist_track_label))
-            .border_style(if app.user_artist_track_selected {
-                Style::default().fg(theme.main_border_color)
-            } else {
-                Style::default().fg(theme.main_inactive_border_color)
-            })
-            .style(Style::default().bg(theme.main_background_color));
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        let user_artist_track_table = searched_track_table_for_artist_ui(
-            app.user_artist_track_names.clone(),
-            app.user_artist_track_album.clone(),
-            app.user_artist_track_duration.clone(),
-            user_artist_track_block,
-            theme.main_highlight_color,
-            theme.main_background_color,
-            theme.main_inactive_border_color,
-        );
-
-        f.render_widget(Clear, content_chunk[1]);
-
-        f.render_stateful_widget(
-            user_artist_track_table,
-            content_chunk[1],
-            &mut app.user_artist_track_state,
-        );
+            .style(Style::default().bg(theme.main_background_color))
+    };
+
+    // Render the library section or the tracks of the item opened from it
+    match app.navigation.current() {
+        View::LibrarySection(Library::MadeFY) => {
+            let made_fy_playlist_table = made_fy_table_ui(
+                app.made_fy_playlists.items(),
+                content_block(String::from("Made For You")),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.made_fy_playlists
+                .render_table(f, made_fy_playlist_table, content_chunk[1]);
+        }
+        View::LibraryTracks(Library::MadeFY) => {
+            let made_fy_track_label = app
+                .made_fy_playlists
+                .selected()
+                .map(|playlist| playlist.name.clone())
+                .unwrap_or_default();
+
+            let made_fy_track_table = track_table_ui(
+                app.made_fy_tracks.items(),
+                content_block(made_fy_track_label),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.made_fy_tracks
+                .render_table(f, made_fy_track_table, content_chunk[1]);
+        }
+        View::LibrarySection(Library::LikedSongs) => {
+            let liked_songs_table = track_table_ui(
+                app.liked_songs.items(),
+                content_block(String::from("Liked Songs")),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.liked_songs
+                .render_table(f, liked_songs_table, content_chunk[1]);
+        }
+        View::LibrarySection(Library::RecentlyPlayed) => {
+            let recently_played_table = track_table_ui(
+                app.recently_played.items(),
+                content_block(String::from("Recently Played")),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.recently_played
+                .render_table(f, recently_played_table, content_chunk[1]);
+        }
+        View::LibrarySection(Library::Podcasts) => {
+            let podcast_table = podcast_table_ui(
+                app.podcasts.items(),
+                content_block(String::from("Podcasts")),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.podcasts
+                .render_table(f, podcast_table, content_chunk[1]);
+        }
+        View::LibrarySection(Library::Artists) => {
+            let artist_table = artist_table_ui(
+                app.user_artists.items(),
+                content_block(String::from("Artists")),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.user_artists
+                .render_table(f, artist_table, content_chunk[1]);
+        }
+        View::LibraryTracks(Library::Artists) => {
+            let user_artist_track_label = app
+                .user_artists
+                .selected()
+                .map(|artist| artist.name.clone())
+                .unwrap_or_default();
+
+            let user_artist_track_table = searched_track_table_for_artist_ui(
+                app.user_artist_tracks.items(),
+                content_block(user_artist_track_label),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.user_artist_tracks
+                .render_table(f, user_artist_track_table, content_chunk[1]);
+        }
+        View::LibrarySection(Library::Albums) => {
+            let user_album_table = album_table_ui(
+                app.user_albums.items(),
+                content_block(String::from("Albums")),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.user_albums
+                .render_table(f, user_album_table, content_chunk[1]);
+        }
+        View::LibraryTracks(Library::Albums) => {
+            let user_album_track_label = app
+                .user_albums
+                .selected()
+                .map(|album| album.name.clone())
+                .unwrap_or_default();
+
+            let user_album_track_table = searched_track_table_for_album_ui(
+                app.user_album_tracks.items(),
+                content_block(user_album_track_label),
+                theme.main_highlight_color,
+                theme.main_background_color,
+                theme.main_inactive_border_color,
+            );
+
+            f.render_widget(Clear, content_chunk[1]);
+
+            app.user_album_tracks
+                .render_table(f, user_album_track_table, content_chunk[1]);
+        }
+        _ => {}
     }
 }
 
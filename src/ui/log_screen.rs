@@ -0,0 +1,61 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        block::{Position, Title},
+        Block, Borders, Clear, List, ListItem,
+    },
+    Frame,
+};
+
+use crate::{
+    app::App,
+    structs::{Key, Themes},
+    ui::util::severity_color,
+};
+
+/// Renders the log screen, the entries are refreshed from the log on every frame
+pub fn render_log(f: &mut Frame, app: &mut App, key: &mut Key, theme: &mut Themes) {
+    app.refresh_log_entries();
+
+    let filter = app
+        .log_filter
+        .map_or("all entries", |severity| severity.label());
+    let log_label = format!(
+        "Log: {} (press {} to exit, Tab to filter, Enter to copy the entry, Ctrl+Y to copy the list)",
+        filter, key.error_key
+    );
+    let file_label = app
+        .log
+        .file()
+        .map(|file| format!("Saved to {}", file.display()))
+        .unwrap_or_default();
+
+    let log_block = Block::default()
+        .borders(Borders::ALL)
+        .title(Title::from(log_label))
+        .title(Title::from(file_label).position(Position::Bottom))
+        .border_style(Style::new().fg(theme.error_border_color))
+        .style(Style::default().bg(theme.error_background_color));
+
+    let entries: Vec<ListItem> = app
+        .log_entries
+        .iter()
+        .map(|entry| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", entry.time.format("%H:%M:%S"))),
+                Span::styled(
+                    format!("{:<8}", entry.severity.label()),
+                    Style::default().fg(severity_color(entry.severity)),
+                ),
+                Span::raw(entry.message.clone()),
+            ]))
+        })
+        .collect();
+    let log_list = List::new(entries)
+        .block(log_block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    f.render_widget(Clear, f.size());
+    app.log_entries.render_list(f, log_list, f.size());
+}
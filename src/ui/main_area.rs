@@ -75,18 +75,18 @@ pub fn render_main_area(
         .title_style(Style::default().underlined().add_modifier(Modifier::BOLD))
         .style(Style::default().bg(theme.main_background_color));
 
-    let top_tracks_all_time_names_clone = app.top_tracks_all_time_names.clone();
-    let top_track_all_time_names = convert_to_list(&top_tracks_all_time_names_clone);
+    let top_track_all_time_names =
+        convert_to_list(app.top_tracks_all_time.iter().map(|track| &track.name));
     let top_track_all_time_list =
         List::new(top_track_all_time_names).block(top_track_all_time_block.clone());
 
-    let top_tracks_6_months_names_clone = app.top_tracks_6_months_names.clone();
-    let top_track_6_months_names = convert_to_list(&top_tracks_6_months_names_clone);
+    let top_track_6_months_names =
+        convert_to_list(app.top_tracks_6_months.iter().map(|track| &track.name));
     let top_track_6_months_list =
         List::new(top_track_6_months_names).block(top_track_6_months_block.clone());
 
-    let top_tracks_4_weeks_names_clone = app.top_tracks_4_weeks_names.clone();
-    let top_track_4_weeks_names = convert_to_list(&top_tracks_4_weeks_names_clone);
+    let top_track_4_weeks_names =
+        convert_to_list(app.top_tracks_4_weeks.iter().map(|track| &track.name));
     let top_track_4_weeks_list =
         List::new(top_track_4_weeks_names).block(top_track_4_weeks_block.clone());
 
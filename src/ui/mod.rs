@@ -1,13 +1,20 @@
+pub mod account;
 pub mod blank_screen;
 pub mod error_screen;
 pub mod fullscreen_player;
 pub mod help;
 pub mod library;
+pub mod log_screen;
 pub mod main_area;
 pub mod new_release;
+pub mod notifications;
 pub mod player;
 pub mod playlist_control;
+pub mod profiles;
+pub mod saved_searches;
 pub mod search;
+pub mod selectable;
+pub mod setup;
 pub mod tui;
 pub mod ui;
 pub mod user_playlist;
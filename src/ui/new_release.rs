@@ -5,7 +5,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::{app::App, structs::Themes};
+use crate::{app::App, enums::View, structs::Themes};
 
 use super::util::{convert_to_list, new_release_table_ui};
 
@@ -25,7 +25,7 @@ pub fn render_default_new_releases(
                 .fg(theme.new_release_inactive_border_color),
         );
 
-    let new_releases_name = convert_to_list(&app.new_release_name);
+    let new_releases_name = convert_to_list(app.new_releases.iter().map(|album| &album.name));
     let new_releases_list = List::new(new_releases_name)
         .block(new_release_block.clone())
         .highlight_style(Style::default().fg(theme.new_release_highlight_color));
@@ -41,7 +41,7 @@ pub fn render_new_releases(
     app: &mut App,
     theme: &mut Themes,
 ) {
-    let current_new_release_name = (&app.current_new_release_album).to_string();
+    let current_new_release_name = app.current_new_release.to_string();
 
     let new_release_block = Block::default()
         .borders(Borders::ALL)
@@ -56,44 +56,36 @@ pub fn render_new_releases(
     let current_new_release_block = Block::default()
         .borders(Borders::ALL)
         .title(Title::from(current_new_release_name))
-        .border_style(if app.new_release_album_selected {
+        .border_style(if !app.can_navigate_menu {
             Style::default().fg(theme.new_release_border_color)
         } else {
             Style::default().fg(theme.new_release_inactive_border_color)
         })
         .style(Style::default().bg(theme.new_release_background_color));
 
-    let new_releases_name = convert_to_list(&app.new_release_name);
+    let new_releases_name = convert_to_list(app.new_releases.iter().map(|album| &album.name));
     let new_releases_list = List::new(new_releases_name)
         .block(new_release_block.clone())
         .highlight_style(Style::default().fg(theme.new_release_highlight_color));
 
     f.render_widget(Clear, content_sub_chunk[1]);
-    f.render_stateful_widget(
-        new_releases_list,
-        content_sub_chunk[1],
-        &mut app.new_release_state,
-    );
+    app.new_releases
+        .render_list(f, new_releases_list, content_sub_chunk[1]);
 
-    // Conditionally render details for the selected new release album.
-    if app.new_release_display {
+    // Render the tracks once an album has been opened
+    if app.navigation.current() == View::NewReleaseTracks {
         f.render_widget(Clear, content_chunk[1]);
 
         let new_release_tracks_table = new_release_table_ui(
-            app.new_release_track_names.clone(),
-            app.new_release_artist_names.clone(),
-            app.new_release_durations_ms.clone(),
+            app.new_release_tracks.items(),
             current_new_release_block,
-            theme.new_release_highlight_color.clone(),
-            theme.new_release_background_color.clone(),
-            theme.new_release_inactive_border_color.clone(),
+            theme.new_release_highlight_color,
+            theme.new_release_background_color,
+            theme.new_release_inactive_border_color,
         );
         f.render_widget(Clear, content_chunk[1]);
 
-        f.render_stateful_widget(
-            new_release_tracks_table,
-            content_chunk[1],
-            &mut app.new_release_album_state,
-        );
+        app.new_release_tracks
+            .render_table(f, new_release_tracks_table, content_chunk[1]);
     }
 }
@@ -0,0 +1,41 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    widgets::{block::Title, Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{app::App, ui::util::severity_color};
+
+// Longest a notification gets before its text is cut off, in lines
+const MAX_LINES: u16 = 3;
+
+/// Renders the notifications stacked in the bottom right corner of `area`, the newest at the bottom
+pub fn render_notifications(f: &mut Frame, area: Rect, app: &App) {
+    let width = (area.width * 2 / 5).max(30).min(area.width);
+    let text_width = width.saturating_sub(2).max(1);
+    let mut bottom = area.y + area.height;
+
+    for notification in app.notifications.visible().iter().rev() {
+        let text_length = notification.message.chars().count() as u16;
+        let lines = text_length.div_ceil(text_width).clamp(1, MAX_LINES);
+        let height = lines + 2;
+        if bottom < area.y + height {
+            break;
+        }
+        bottom -= height;
+
+        let toast_area = Rect::new(area.x + area.width - width, bottom, width, height);
+        let color = severity_color(notification.severity);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(Title::from(notification.severity.label()))
+            .border_style(Style::new().fg(color));
+        let toast = Paragraph::new(notification.message.clone())
+            .block(block)
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(Clear, toast_area);
+        f.render_widget(toast, toast_area);
+    }
+}
@@ -8,20 +8,13 @@ use ratatui::{
 
 use crate::{app::App, structs::Themes};
 
-use super::util::format_duration;
+use super::util::{format_duration, player_title};
 
 /// Renders the player UI section, including playback info, progress bar, and current media details
 pub fn render_player(f: &mut Frame, player_layout: &[Rect], app: &mut App, theme: &mut Themes) {
     let player_info_block = Block::default()
         .borders(Borders::TOP | Borders::RIGHT | Borders::LEFT)
-        .title(format!(
-            "{} ({} | Shuffle: {} | Repeat: {} | Volume: {}%)",
-            app.playback_status,
-            app.current_device_name,
-            app.shuffle_status,
-            app.repeat_status,
-            app.current_device_volume
-        ))
+        .title(player_title(app))
         .style(
             Style::default()
                 .bg(theme.player_background_color)
@@ -29,16 +29,14 @@ pub fn render_add_track_to_playlist_screen(
         .border_style(Style::new().fg(theme.playlist_border_color))
         .style(Style::default().bg(theme.playlist_background_color));
 
-    let add_to_playlist_names = convert_to_list(&app.user_playlist_names);
+    let add_to_playlist_names =
+        convert_to_list(app.user_playlists.iter().map(|playlist| &playlist.name));
     let add_to_playlist_list = List::new(add_to_playlist_names)
         .block(add_playlist_block.clone())
         .highlight_style(Style::default().fg(theme.playlist_highlight_color));
 
     f.render_widget(Clear, f.size());
 
-    f.render_stateful_widget(
-        add_to_playlist_list,
-        f.size(),
-        &mut app.add_track_to_playlist_state,
-    );
+    app.add_track_to_playlist
+        .render_list(f, add_to_playlist_list, f.size());
 }
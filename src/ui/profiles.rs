@@ -0,0 +1,42 @@
+use ratatui::{
+    style::Style,
+    widgets::{block::Title, Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+use crate::{
+    app::App,
+    structs::{Key, Themes},
+};
+
+/// Renders the profile screen, the profile in use is marked
+pub fn render_profiles(f: &mut Frame, app: &mut App, key: &mut Key, theme: &mut Themes) {
+    let profiles_label = format!(
+        "Profiles (press {} to cancel, Enter to switch)",
+        key.profiles_key
+    );
+
+    let profiles_block = Block::default()
+        .borders(Borders::ALL)
+        .title(Title::from(profiles_label))
+        .border_style(Style::new().fg(theme.playlist_border_color))
+        .style(Style::default().bg(theme.playlist_background_color));
+
+    let profile_names: Vec<ListItem> = app
+        .profiles
+        .iter()
+        .map(|name| {
+            if *name == app.profile {
+                ListItem::new(format!("{} (in use)", name))
+            } else {
+                ListItem::new(name.clone())
+            }
+        })
+        .collect();
+    let profiles_list = List::new(profile_names)
+        .block(profiles_block)
+        .highlight_style(Style::default().fg(theme.playlist_highlight_color));
+
+    f.render_widget(Clear, f.size());
+    app.profiles.render_list(f, profiles_list, f.size());
+}
@@ -0,0 +1,53 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::Line,
+    widgets::{block::Title, Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::{app::App, structs::Themes};
+
+/// Renders the saved searches screen, the name the last search is saved under is typed above the list
+pub fn render_saved_searches(f: &mut Frame, app: &mut App, theme: &mut Themes) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(f.size());
+
+    let name_line = match &app.last_search {
+        Some(search) => Line::from(format!(
+            "Save the last search ({}) as: {}\u{2588}",
+            search.query(),
+            app.saved_search_name
+        )),
+        None => Line::from("Search for something to save the search here"),
+    };
+    let name_field = Paragraph::new(name_line).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(theme.search_inactive_border_color))
+            .style(Style::default().bg(theme.search_background_color)),
+    );
+
+    let searches_block = Block::default()
+        .borders(Borders::ALL)
+        .title(Title::from(
+            "Saved searches (Enter to run the selected one or save under the typed name, Delete to remove, Esc to close)",
+        ))
+        .border_style(Style::new().fg(theme.search_border_color))
+        .style(Style::default().bg(theme.search_background_color));
+
+    let searches: Vec<ListItem> = app
+        .saved_searches
+        .iter()
+        .map(|search| ListItem::new(format!("{} - {}", search.name, search.query())))
+        .collect();
+    let searches_list = List::new(searches)
+        .block(searches_block)
+        .highlight_style(Style::default().fg(theme.search_highlight_color));
+
+    f.render_widget(Clear, f.size());
+    f.render_widget(name_field, chunks[0]);
+    app.saved_searches.render_list(f, searches_list, chunks[1]);
+}
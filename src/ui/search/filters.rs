@@ -0,0 +1,83 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::Line,
+    widgets::{block::Title, Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{app::App, enums::FilterField, spotify::search::query::SearchFilters, structs::Themes};
+
+/// Renders the search filter form, the query it makes is shown below the fields
+pub fn render_search_filters(f: &mut Frame, app: &mut App, theme: &mut Themes) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(5)])
+        .split(f.size());
+
+    let filters_block = Block::default()
+        .borders(Borders::ALL)
+        .title(Title::from(
+            "Search filters (Enter to search, Esc to go back, Space to switch, Delete to clear)",
+        ))
+        .border_style(Style::new().fg(theme.search_border_color))
+        .style(Style::default().bg(theme.search_background_color));
+
+    let selected = app.search_filter_fields.selected().copied();
+    let fields: Vec<ListItem> = app
+        .search_filter_fields
+        .iter()
+        .map(|&field| {
+            ListItem::new(field_line(
+                &app.search_filters,
+                field,
+                selected == Some(field),
+            ))
+        })
+        .collect();
+    let fields_list = List::new(fields)
+        .block(filters_block)
+        .highlight_style(Style::default().fg(theme.search_highlight_color));
+
+    let query = app.search_filters.compose(&app.input);
+    let preview = Paragraph::new(vec![
+        Line::from(format!("Searches for: {}", query)),
+        Line::from(
+            "Artist and year narrow down songs, artists and albums, album only songs and albums, genre only songs and artists, ISRC only songs and new releases only albums",
+        ),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(theme.search_inactive_border_color))
+            .style(Style::default().bg(theme.search_background_color)),
+    )
+    .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, f.size());
+    app.search_filter_fields
+        .render_list(f, fields_list, chunks[0]);
+    f.render_widget(preview, chunks[1]);
+}
+
+// A text field shows its value, the block stands in for the cursor of the selected one
+fn field_line(filters: &SearchFilters, field: FilterField, selected: bool) -> String {
+    let (text, switch): (&str, Option<bool>) = match field {
+        FilterField::Artist => (&filters.artist, None),
+        FilterField::Album => (&filters.album, None),
+        FilterField::Year => (&filters.year, None),
+        FilterField::Genre => (&filters.genre, None),
+        FilterField::Isrc => (&filters.isrc, None),
+        FilterField::NewReleases => ("", Some(filters.new_releases)),
+        FilterField::Tracks => ("", Some(filters.tracks)),
+        FilterField::Artists => ("", Some(filters.artists)),
+        FilterField::Albums => ("", Some(filters.albums)),
+        FilterField::Playlists => ("", Some(filters.playlists)),
+    };
+
+    match switch {
+        Some(on) => format!("[{}] {}", if on { "x" } else { " " }, field.label()),
+        None if selected => format!("{:<19}{}\u{2588}", field.label(), text),
+        None => format!("{:<19}{}", field.label(), text),
+    }
+}
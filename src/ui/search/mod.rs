@@ -1,3 +1,4 @@
+pub mod filters;
 pub mod search;
 pub mod search_album;
 pub mod search_artist;
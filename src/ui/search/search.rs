@@ -1,13 +1,13 @@
 use ratatui::{
     layout::Rect,
-    style::Style,
-    widgets::{block::Title, Block, Borders, Clear, List, Paragraph},
+    style::{Color, Style},
+    widgets::{block::Title, Block, Borders, Clear, List, ListItem, Paragraph},
     Frame,
 };
 
 use crate::{
     app::App,
-    enums::{InputMode, SearchMenu},
+    enums::{InputMode, SearchCategory, View},
     structs::Themes,
     ui::util::convert_to_list,
 };
@@ -36,7 +36,12 @@ pub fn render_search(
     // Create styled blocks for each search category (albums, artists, etc.) with dynamic borders
     let album_block = Block::default()
         .borders(Borders::ALL)
-        .title(Title::from("Albums"))
+        .title(Title::from(results_title(
+            "Albums",
+            app.searched_albums.items().len(),
+            SearchCategory::Albums,
+            app,
+        )))
         .border_style(if app.selected_album_in_search_result {
             Style::default().fg(theme.search_border_color)
         } else {
@@ -45,7 +50,12 @@ pub fn render_search(
         .style(Style::default().bg(theme.search_background_color));
     let artist_block = Block::default()
         .borders(Borders::ALL)
-        .title(Title::from("Artists"))
+        .title(Title::from(results_title(
+            "Artists",
+            app.searched_artists.items().len(),
+            SearchCategory::Artists,
+            app,
+        )))
         .border_style(if app.selected_artist_in_search_result {
             Style::default().fg(theme.search_border_color)
         } else {
@@ -54,7 +64,12 @@ pub fn render_search(
         .style(Style::default().bg(theme.search_background_color));
     let song_block = Block::default()
         .borders(Borders::ALL)
-        .title(Title::from("Songs"))
+        .title(Title::from(results_title(
+            "Songs",
+            app.searched_tracks.items().len(),
+            SearchCategory::Tracks,
+            app,
+        )))
         .border_style(if app.selected_track_in_search_result {
             Style::default().fg(theme.search_border_color)
         } else {
@@ -63,7 +78,12 @@ pub fn render_search(
         .style(Style::default().bg(theme.search_background_color));
     let playlist_block = Block::default()
         .borders(Borders::ALL)
-        .title(Title::from("Playlists"))
+        .title(Title::from(results_title(
+            "Playlists",
+            app.searched_playlists.items().len(),
+            SearchCategory::Playlists,
+            app,
+        )))
         .border_style(if app.selected_playlist_in_search_result {
             Style::default().fg(theme.search_border_color)
         } else {
@@ -78,7 +98,11 @@ pub fn render_search(
             InputMode::Editing => Style::default().fg(theme.search_border_color),
             InputMode::SearchResults => Style::default(),
         })
-        .block(Block::default().borders(Borders::ALL).title("Search"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(search_box_title(app)),
+        )
         .style(
             Style::default()
                 .bg(theme.search_background_color)
@@ -87,80 +111,78 @@ pub fn render_search(
 
     f.render_widget(search_block, header_chunk[0]);
 
-    // Render search input or search results depending on the input mode
-    match app.input_mode {
-        InputMode::Normal => {}
-        InputMode::Editing => {
-            // Render the search input with a cursor at the current position
-            f.render_widget(search_input, header_chunk[0]);
-            f.set_cursor(
-                header_chunk[0].x + app.cursor_position as u16 + 1,
-                header_chunk[0].y + 1,
-            );
-        }
-        InputMode::SearchResults if app.search_results_rendered => {
-            f.render_widget(Clear, content_chunk[1]);
-            f.render_widget(Clear, main_chunk_upper[0]);
-
-            let album_names_list = convert_to_list(&app.album_names_search_results);
-            let track_names_list = convert_to_list(&app.track_names_search_results);
-            let artist_names_list = convert_to_list(&app.artist_names_search_results);
-            let playlist_names_list = convert_to_list(&app.playlist_names_search_results);
-
-            let album_list = List::new(album_names_list)
-                .block(album_block.clone())
-                .highlight_style(Style::default().fg(theme.search_highlight_color));
-
-            let song_list = List::new(track_names_list)
-                .block(song_block.clone())
-                .highlight_style(Style::default().fg(theme.search_highlight_color));
-
-            let playlist_list = List::new(playlist_names_list)
-                .block(playlist_block.clone())
-                .highlight_style(Style::default().fg(theme.search_highlight_color));
-
-            let artist_list = List::new(artist_names_list)
-                .block(artist_block.clone())
-                .highlight_style(Style::default().fg(theme.search_highlight_color));
-
-            f.render_stateful_widget(
-                song_list,
-                main_chunk_upper[0],
-                &mut app.track_state_in_search_result,
-            );
-            f.render_stateful_widget(
-                artist_list,
-                main_chunk_upper[1],
-                &mut app.artist_state_in_search_result,
-            );
-            f.render_stateful_widget(
-                album_list,
-                main_chunk_lower[0],
-                &mut app.album_state_in_search_result,
-            );
-            f.render_stateful_widget(
-                playlist_list,
-                main_chunk_lower[1],
-                &mut app.playlist_state_in_search_result,
-            );
-        }
-        _ => {}
+    // Render the search input with a cursor at the current position while it is typed into
+    if app.input_mode == InputMode::Editing {
+        f.render_widget(search_input, header_chunk[0]);
+        f.set_cursor(
+            header_chunk[0].x + app.cursor_position as u16 + 1,
+            header_chunk[0].y + 1,
+        );
+    }
+
+    // The results are shown once searched for, search-as-you-type fills the panes while the query is typed
+    if app.input_mode != InputMode::Normal && app.search_results_shown() {
+        f.render_widget(Clear, content_chunk[1]);
+        f.render_widget(Clear, main_chunk_upper[0]);
+
+        let album_names_list = convert_to_list(app.searched_albums.iter().map(|album| &album.name));
+        // Tracks Spotify can't play in the market stay listed but are greyed out
+        let track_names_list: Vec<ListItem> = app
+            .searched_tracks
+            .iter()
+            .map(|track| {
+                if track.unplayable {
+                    ListItem::new(format!("{} (unavailable)", track.name))
+                        .style(Style::new().fg(Color::DarkGray))
+                } else {
+                    ListItem::new(track.name.clone())
+                }
+            })
+            .collect();
+        let artist_names_list =
+            convert_to_list(app.searched_artists.iter().map(|artist| &artist.name));
+        let playlist_names_list =
+            convert_to_list(app.searched_playlists.iter().map(|playlist| &playlist.name));
+
+        let album_list = List::new(album_names_list)
+            .block(album_block.clone())
+            .highlight_style(Style::default().fg(theme.search_highlight_color));
+
+        let song_list = List::new(track_names_list)
+            .block(song_block.clone())
+            .highlight_style(Style::default().fg(theme.search_highlight_color));
+
+        let playlist_list = List::new(playlist_names_list)
+            .block(playlist_block.clone())
+            .highlight_style(Style::default().fg(theme.search_highlight_color));
+
+        let artist_list = List::new(artist_names_list)
+            .block(artist_block.clone())
+            .highlight_style(Style::default().fg(theme.search_highlight_color));
+
+        app.searched_tracks
+            .render_list(f, song_list, main_chunk_upper[0]);
+        app.searched_artists
+            .render_list(f, artist_list, main_chunk_upper[1]);
+        app.searched_albums
+            .render_list(f, album_list, main_chunk_lower[0]);
+        app.searched_playlists
+            .render_list(f, playlist_list, main_chunk_lower[1]);
     }
-    match app.search_menu {
-        SearchMenu::Default => {}
-        SearchMenu::SearchedTrack => {}
-        SearchMenu::SearchedAlbum => {
+    match app.navigation.current() {
+        View::SearchedAlbum => {
             f.render_widget(Clear, content_chunk[1]);
             render_searched_album(f, content_chunk, app, theme);
         }
-        SearchMenu::SearchedArtist => {
+        View::SearchedArtist => {
             f.render_widget(Clear, content_chunk[1]);
             render_searched_artist(f, content_chunk, app, theme);
         }
-        SearchMenu::SearchedPlaylist => {
+        View::SearchedPlaylist => {
             f.render_widget(Clear, content_chunk[1]);
             render_searched_playlist(f, content_chunk, app, theme);
         }
+        _ => {}
     }
 }
 
@@ -177,3 +199,27 @@ pub fn render_default_search(f: &mut Frame, header_chunk: &[Rect], theme: &mut T
 
     f.render_widget(search_block, header_chunk[0]);
 }
+
+// The title of a pane of results, it tells when Spotify has more than the ones listed
+fn results_title(name: &str, listed: usize, category: SearchCategory, app: &App) -> String {
+    let paging = app.search_paging.get(category);
+    if app.search_pending.contains(&category) {
+        format!("{} (searching...)", name)
+    } else if app.search_loading == Some(category) {
+        format!("{} (loading more...)", name)
+    } else if paging.has_more() {
+        format!("{} ({} of {})", name, listed, paging.total)
+    } else {
+        name.to_string()
+    }
+}
+
+// The search box tells which filters are added to the typed text
+fn search_box_title(app: &App) -> String {
+    let summary = app.search_filters.summary();
+    if summary.is_empty() {
+        "Search (Tab for filters)".to_string()
+    } else {
+        format!("Search ({}, Tab to change)", summary)
+    }
+}
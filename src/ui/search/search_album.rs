@@ -16,22 +16,20 @@ pub fn render_searched_album(
     theme: &mut Themes,
 ) {
     f.render_widget(Clear, content_chunk[1]);
-    let current_album = &app.album_names_search_results[app.album_index];
+    let current_album = app
+        .searched_albums
+        .selected()
+        .map(|album| album.name.clone())
+        .unwrap_or_default();
 
     let album_block = Block::default()
         .borders(Borders::ALL)
-        .title(Title::from(current_album.to_string()))
-        .border_style(if app.searched_album_selected {
-            Style::default().fg(theme.main_border_color)
-        } else {
-            Style::default().fg(theme.main_inactive_border_color)
-        })
+        .title(Title::from(current_album))
+        .border_style(Style::default().fg(theme.main_border_color))
         .style(Style::default().bg(theme.main_background_color));
 
     let track_table = searched_track_table_for_album_ui(
-        app.selected_album_tracks_names.clone(),
-        app.selected_album_tracks_artists.clone(),
-        app.selected_album_tracks_duration.clone(),
+        app.selected_album_tracks.items(),
         album_block,
         theme.main_highlight_color,
         theme.main_background_color,
@@ -40,5 +38,6 @@ pub fn render_searched_album(
 
     f.render_widget(Clear, content_chunk[1]);
 
-    f.render_stateful_widget(track_table, content_chunk[1], &mut app.searched_album_state);
+    app.selected_album_tracks
+        .render_table(f, track_table, content_chunk[1]);
 }
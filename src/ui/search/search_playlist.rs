@@ -14,23 +14,20 @@ pub fn render_searched_playlist(
     theme: &mut Themes,
 ) {
     f.render_widget(Clear, content_chunk[1]);
-    let current_playlist = &app.playlist_names_search_results[app.playlist_index];
+    let current_playlist = app
+        .searched_playlists
+        .selected()
+        .map(|playlist| playlist.name.clone())
+        .unwrap_or_default();
 
     let playlist_block = Block::default()
         .borders(Borders::ALL)
-        .title(Title::from(current_playlist.to_string()))
-        .border_style(if app.searched_playlist_selected {
-            Style::default().fg(theme.main_border_color)
-        } else {
-            Style::default().fg(theme.main_inactive_border_color)
-        })
+        .title(Title::from(current_playlist))
+        .border_style(Style::default().fg(theme.main_border_color))
         .style(Style::default().bg(theme.main_background_color));
 
     let track_table = track_table_ui(
-        app.selected_playlist_tracks_names.clone(),
-        app.selected_playlist_tracks_artists.clone(),
-        app.selected_playlist_tracks_albums.clone(),
-        app.selected_playlist_tracks_duration.clone(),
+        app.selected_playlist_tracks.items(),
         playlist_block,
         theme.main_highlight_color,
         theme.main_background_color,
@@ -39,9 +36,6 @@ pub fn render_searched_playlist(
 
     f.render_widget(Clear, content_chunk[1]);
 
-    f.render_stateful_widget(
-        track_table,
-        content_chunk[1],
-        &mut app.searched_playlist_state,
-    );
+    app.selected_playlist_tracks
+        .render_table(f, track_table, content_chunk[1]);
 }
@@ -4,37 +4,30 @@ use ratatui::{
     widgets::{Block, Cell, Row, Table},
 };
 
+use crate::models::Track;
 use crate::ui::util::format_duration;
 
-pub fn searched_track_table_for_album_ui(
-    names: Vec<String>,
-    artist_names: Vec<String>,
-    duration: Vec<i64>,
-    block: Block,
+pub fn searched_track_table_for_album_ui<'a>(
+    tracks: &[Track],
+    block: Block<'a>,
     highlight_color: Color,
     background_color: Color,
     inactive_border_color: Color,
-) -> Table {
-    let tracks: Vec<(usize, String, String, String)> = names
-        .iter()
-        .enumerate()
-        .zip(artist_names.iter())
-        .zip(duration.iter().map(|d| format_duration(*d)))
-        .map(|(((index, name), artist), duration)| {
-            (index + 1, name.clone(), artist.clone(), duration)
-        })
-        .collect();
-
+) -> Table<'a> {
     let table = Table::new(
         tracks
             .iter()
-            .map(|(index, name, artist, duration)| {
-                Row::new(vec![
-                    Cell::from(format!("{}", index)),
-                    Cell::from(name.clone()),
-                    Cell::from(artist.clone()),
-                    Cell::from(duration.clone()),
-                ])
+            .enumerate()
+            .map(|(index, track)| {
+                unplayable_row(
+                    track,
+                    Row::new(vec![
+                        Cell::from(format!("{}", index + 1)),
+                        Cell::from(track.name.clone()),
+                        Cell::from(track.artist_names()),
+                        Cell::from(format_duration(track.duration_ms)),
+                    ]),
+                )
             })
             .collect::<Vec<_>>(),
         [
@@ -64,35 +57,27 @@ pub fn searched_track_table_for_album_ui(
     table
 }
 
-pub fn searched_track_table_for_artist_ui(
-    names: Vec<String>,
-    album_names: Vec<String>,
-    duration: Vec<i64>,
-    block: Block,
+pub fn searched_track_table_for_artist_ui<'a>(
+    tracks: &[Track],
+    block: Block<'a>,
     highlight_color: Color,
     background_color: Color,
     inactive_border_color: Color,
-) -> Table {
-    let tracks: Vec<(usize, String, String, String)> = names
-        .iter()
-        .enumerate()
-        .zip(album_names.iter())
-        .zip(duration.iter().map(|d| format_duration(*d)))
-        .map(|(((index, name), album), duration)| {
-            (index + 1, name.clone(), album.clone(), duration)
-        })
-        .collect();
-
+) -> Table<'a> {
     let table = Table::new(
         tracks
             .iter()
-            .map(|(index, name, album, duration)| {
-                Row::new(vec![
-                    Cell::from(format!("{}", index)),
-                    Cell::from(name.clone()),
-                    Cell::from(album.clone()),
-                    Cell::from(duration.clone()),
-                ])
+            .enumerate()
+            .map(|(index, track)| {
+                unplayable_row(
+                    track,
+                    Row::new(vec![
+                        Cell::from(format!("{}", index + 1)),
+                        Cell::from(track.name.clone()),
+                        Cell::from(track.album_name().to_string()),
+                        Cell::from(format_duration(track.duration_ms)),
+                    ]),
+                )
             })
             .collect::<Vec<_>>(),
         [
@@ -121,3 +106,12 @@ pub fn searched_track_table_for_artist_ui(
 
     table
 }
+
+// Greys out a track Spotify can't play in the market
+fn unplayable_row<'a>(track: &Track, row: Row<'a>) -> Row<'a> {
+    if track.unplayable {
+        row.style(Style::new().fg(Color::DarkGray))
+    } else {
+        row
+    }
+}
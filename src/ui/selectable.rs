@@ -0,0 +1,126 @@
+use ratatui::{
+    layout::Rect,
+    widgets::{List, ListState, Table, TableState},
+    Frame,
+};
+
+use crate::enums::Movement;
+
+/// Items the user can move a selection through, shown as a table or a list.
+/// It keeps the selection and the scroll offset in range, an empty collection has no selection.
+#[derive(Clone, Debug)]
+pub struct Selectable<T> {
+    items: Vec<T>,
+    selected: Option<usize>,
+    offset: usize,
+    // Rows visible the last time the items were rendered, used for paging
+    page_size: usize,
+}
+
+impl<T> Default for Selectable<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            selected: None,
+            offset: 0,
+            page_size: 1,
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Selectable<T> {
+    fn from(items: Vec<T>) -> Self {
+        let mut selectable = Self::default();
+        selectable.set_items(items);
+        selectable
+    }
+}
+
+impl<T> Selectable<T> {
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Replaces the items, the selection stays where it was if the new items are long enough
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.selected = match self.selected {
+            _ if self.items.is_empty() => None,
+            Some(index) => Some(index.min(self.items.len() - 1)),
+            None => Some(0),
+        };
+        self.offset = self.offset.min(self.selected.unwrap_or(0));
+    }
+
+    /// Adds items after the existing ones, the selection and scroll position stay where they are
+    pub fn append_items(&mut self, items: Vec<T>) {
+        self.items.extend(items);
+    }
+
+    pub fn selected(&self) -> Option<&T> {
+        self.selected.and_then(|index| self.items.get(index))
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Selects the item at `index`, or the last item if there are fewer
+    pub fn select(&mut self, index: usize) {
+        if !self.items.is_empty() {
+            self.selected = Some(index.min(self.items.len() - 1));
+        }
+    }
+
+    pub fn unselect(&mut self) {
+        self.selected = None;
+    }
+
+    /// Moves the selection, moving past either end of the items wraps around
+    pub fn apply(&mut self, movement: Movement) {
+        let Some(last) = self.items.len().checked_sub(1) else {
+            return;
+        };
+        let current = self.selected.unwrap_or(0);
+        let next = match movement {
+            Movement::Next if self.selected.is_none() => 0,
+            Movement::Next if current == last => 0,
+            Movement::Next => current + 1,
+            Movement::Previous if current == 0 => last,
+            Movement::Previous => current - 1,
+            Movement::PageDown => (current + self.page_size).min(last),
+            Movement::PageUp => current.saturating_sub(self.page_size),
+            Movement::First => 0,
+            Movement::Last => last,
+        };
+        self.selected = Some(next);
+    }
+
+    /// Renders the items as a table inside a bordered block with a header row
+    pub fn render_table(&mut self, f: &mut Frame, table: Table, area: Rect) {
+        let mut state = TableState::default()
+            .with_selected(self.selected)
+            .with_offset(self.offset);
+        f.render_stateful_widget(table, area, &mut state);
+        self.offset = state.offset();
+        self.page_size = usize::from(area.height.saturating_sub(3)).max(1);
+    }
+
+    /// Renders the items as a list inside a bordered block
+    pub fn render_list(&mut self, f: &mut Frame, list: List, area: Rect) {
+        let mut state = ListState::default()
+            .with_selected(self.selected)
+            .with_offset(self.offset);
+        f.render_stateful_widget(list, area, &mut state);
+        self.offset = state.offset();
+        self.page_size = usize::from(area.height.saturating_sub(2)).max(1);
+    }
+}
@@ -0,0 +1,149 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{block::Title, Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{app::App, enums::SetupStep, spotify::auth::REDIRECT_URI, structs::Themes};
+
+const DASHBOARD_URL: &str = "https://developer.spotify.com/dashboard";
+
+/// Renders the setup screen, shown on the first run until spoify is logged in to Spotify
+pub fn render_setup(f: &mut Frame, app: &mut App, theme: &mut Themes) {
+    let setup = &app.setup;
+    let highlight = Style::new().fg(theme.main_highlight_color);
+
+    let (step_label, mut lines) = match setup.step {
+        SetupStep::Instructions => (
+            "Step 1 of 4: create a Spotify app",
+            vec![
+                Line::from("spoify talks to Spotify through an app of your own on the Spotify dashboard."),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("1. Go to "),
+                    Span::styled(DASHBOARD_URL, highlight),
+                    Span::raw(" and log in"),
+                ]),
+                Line::from("2. Click 'Create app' and give it any name and description"),
+                Line::from(vec![
+                    Span::raw("3. Add "),
+                    Span::styled(REDIRECT_URI, highlight),
+                    Span::raw(" to the Redirect URIs and save"),
+                ]),
+                Line::from("4. Open the app's settings, the Client ID is shown there and the Client Secret under 'View client secret'"),
+                Line::from(""),
+                Line::from("Press Enter to continue, Esc to quit"),
+            ],
+        ),
+        SetupStep::ClientId => (
+            "Step 2 of 4: Client ID",
+            vec![
+                Line::from("Paste the Client ID of your app:"),
+                Line::from(""),
+                input_line(&setup.client_id, highlight),
+                Line::from(""),
+                Line::from("Press Enter to continue, Esc to go back"),
+            ],
+        ),
+        SetupStep::ClientSecret => (
+            "Step 3 of 4: Client Secret",
+            vec![
+                Line::from("Paste the Client Secret of your app, or leave it empty to log in with PKCE, which only needs the Client ID:"),
+                Line::from(""),
+                input_line(&"*".repeat(setup.client_secret.chars().count()), highlight),
+                Line::from(""),
+                Line::from("Press Enter to log in, Esc to go back"),
+            ],
+        ),
+        SetupStep::LoggingIn => {
+            let auth_url = setup.login.auth_url();
+            let checking = auth_url.is_empty();
+            let mut lines = if checking {
+                vec![Line::from(match &setup.profile {
+                    Some(profile) => format!("Logging in to the '{}' profile...", profile),
+                    None => "Checking the credentials with Spotify...".to_string(),
+                })]
+            } else {
+                vec![
+                    Line::from("Log in to Spotify in the browser and accept the permissions spoify asks for."),
+                    Line::from("If the browser didn't open, visit:"),
+                    Line::from(""),
+                    Line::styled(auth_url, highlight),
+                    Line::from(""),
+                ]
+            };
+            match setup.login.paste_reason() {
+                Some(reason) => lines.extend([
+                    Line::from(reason),
+                    Line::from("Once logged in, paste the URL the browser was sent to:"),
+                    Line::from(""),
+                    input_line(&setup.redirected_url, highlight),
+                    Line::from(""),
+                    Line::from("Press Enter to finish the login, Esc to go back"),
+                ]),
+                None if checking => {}
+                None => lines.extend([
+                    Line::from("Waiting for the login to finish in the browser..."),
+                    Line::from("If the browser shows INVALID_CLIENT, press Esc and check the Client ID"),
+                ]),
+            }
+            let step_label = if setup.profile.is_some() {
+                "log in to the profile"
+            } else {
+                "Step 4 of 4: log in"
+            };
+            (step_label, lines)
+        }
+        SetupStep::Failed if setup.relogin => (
+            "The login failed",
+            vec![Line::from(if app.backend.is_some() {
+                "Press Enter to try again, Esc to go back"
+            } else {
+                "Press Enter to try again, Esc to quit"
+            })],
+        ),
+        SetupStep::Failed => (
+            "The login failed",
+            vec![
+                Line::from("Press Enter to check the credentials and try again, Esc to quit"),
+            ],
+        ),
+        SetupStep::LoggedOut => (
+            "Logged out",
+            vec![
+                Line::from("The saved login was removed, the credentials are kept."),
+                Line::from(""),
+                Line::from("Press Enter to log in again, Esc to quit"),
+            ],
+        ),
+    };
+
+    if !setup.error.is_empty() {
+        lines.insert(0, Line::from(""));
+        lines.insert(
+            0,
+            Line::styled(setup.error.clone(), Style::new().fg(Color::Red)),
+        );
+    }
+
+    let setup_block = Block::default()
+        .borders(Borders::ALL)
+        .title(Title::from(format!("Set up spoify - {}", step_label)))
+        .border_style(Style::new().fg(theme.main_border_color))
+        .style(Style::default().bg(theme.main_background_color));
+
+    let setup_text = Paragraph::new(lines)
+        .block(setup_block)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, f.size());
+    f.render_widget(setup_text, f.size());
+}
+
+// A text field, the block at the end stands in for the cursor
+fn input_line(value: &str, style: Style) -> Line<'static> {
+    Line::styled(format!("> {}\u{2588}", value), style)
+}
@@ -1,6 +1,7 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
 use crossterm::{execute, terminal::*};
 use ratatui::prelude::*;
-use std::io::{self, stdout, Stdout};
+use std::io::{self, stdout, Stdout, Write};
 
 /// A type alias for the terminal type used in this application
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
@@ -18,3 +19,10 @@ pub fn restore() -> io::Result<()> {
     disable_raw_mode()?;
     Ok(())
 }
+
+/// Puts the text on the system clipboard through the terminal (OSC 52), this also works over SSH
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()
+}
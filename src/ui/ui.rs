@@ -4,15 +4,22 @@ use crate::structs::{Key, Themes};
 
 use ratatui::prelude::*;
 
+use super::account::render_account;
 use super::error_screen::render_error;
 use super::fullscreen_player::render_player_in_fullscreen;
 use super::help::{render_default_help, render_help};
 use super::library::{render_default_library, render_library};
+use super::log_screen::render_log;
 use super::main_area::render_main_area;
 use super::new_release::{render_default_new_releases, render_new_releases};
+use super::notifications::render_notifications;
 use super::player::render_player;
 use super::playlist_control::add_to_playlist::render_add_track_to_playlist_screen;
+use super::profiles::render_profiles;
+use super::saved_searches::render_saved_searches;
+use super::search::filters::render_search_filters;
 use super::search::search::{render_default_search, render_search};
+use super::setup::render_setup;
 use super::user_playlist::{render_default_user_playlist, render_user_playlist};
 
 /// Renders the main frame of the application's user interface
@@ -156,5 +163,26 @@ pub fn render_frame(
         Menu::AddTrackToPlaylist => {
             render_add_track_to_playlist_screen(f, app, key, theme);
         }
+        Menu::Log => {
+            render_log(f, app, key, theme);
+        }
+        Menu::Profiles => {
+            render_profiles(f, app, key, theme);
+        }
+        Menu::Setup => {
+            render_setup(f, app, theme);
+        }
+        Menu::Account => {
+            render_account(f, app, key, theme);
+        }
+        Menu::SearchFilters => {
+            render_search_filters(f, app, theme);
+        }
+        Menu::SavedSearches => {
+            render_saved_searches(f, app, theme);
+        }
     }
+
+    // Notifications are drawn last so they stay on top of every screen
+    render_notifications(f, chunks[1], app);
 }
@@ -5,7 +5,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::{app::App, structs::Themes};
+use crate::{app::App, enums::View, structs::Themes};
 
 use super::util::{convert_to_list, track_table_ui};
 
@@ -16,7 +16,7 @@ pub fn render_user_playlist(
     app: &mut App,
     theme: &mut Themes,
 ) {
-    let current_playlist_name = (&app.current_user_playlist).to_string();
+    let current_playlist_name = app.current_user_playlist.to_string();
 
     let playlist_block_user = Block::default()
         .borders(Borders::ALL)
@@ -31,7 +31,7 @@ pub fn render_user_playlist(
     let user_playlist_block = Block::default()
         .borders(Borders::ALL)
         .title(Title::from(current_playlist_name))
-        .border_style(if app.user_playlist_tracks_selected {
+        .border_style(if !app.can_navigate_menu {
             Style::default().fg(theme.playlist_border_color)
         } else {
             Style::default().fg(theme.playlist_inactive_border_color)
@@ -39,41 +39,33 @@ pub fn render_user_playlist(
         .style(Style::default().bg(theme.playlist_background_color));
 
     // Convert app data (user playlist names) to a List widget
-    let user_playlist_names = convert_to_list(&app.user_playlist_names);
+    let user_playlist_names =
+        convert_to_list(app.user_playlists.iter().map(|playlist| &playlist.name));
     let user_playlist_list = List::new(user_playlist_names)
         .block(playlist_block_user.clone())
         .highlight_style(Style::default().fg(theme.playlist_highlight_color));
 
     f.render_widget(Clear, content_chunk[2]);
 
-    f.render_stateful_widget(
-        user_playlist_list,
-        content_chunk[2],
-        &mut app.user_playlist_state,
-    );
+    app.user_playlists
+        .render_list(f, user_playlist_list, content_chunk[2]);
 
-    // Conditionally render the user playlist track table
-    if app.user_playlist_display {
+    // Render the track table once a playlist has been opened
+    if app.navigation.current() == View::PlaylistTracks {
         f.render_widget(Clear, content_chunk[1]);
 
         let user_playlist_tracks_table = track_table_ui(
-            app.user_playlist_track_names.clone(),
-            app.user_playlist_artist_names.clone(),
-            app.user_playlist_album_names.clone(),
-            app.user_playlist_track_duration.clone(),
+            app.user_playlist_tracks.items(),
             user_playlist_block,
-            theme.playlist_highlight_color.clone(),
-            theme.playlist_background_color.clone(),
-            theme.playlist_inactive_border_color.clone(),
+            theme.playlist_highlight_color,
+            theme.playlist_background_color,
+            theme.playlist_inactive_border_color,
         );
 
         f.render_widget(Clear, content_chunk[1]);
 
-        f.render_stateful_widget(
-            user_playlist_tracks_table,
-            content_chunk[1],
-            &mut app.user_playlist_tracks_state,
-        );
+        app.user_playlist_tracks
+            .render_table(f, user_playlist_tracks_table, content_chunk[1]);
     }
 }
 
@@ -94,7 +86,8 @@ pub fn render_default_user_playlist(
         );
 
     // Convert app data (user playlist names) to a List widget
-    let user_playlist_names = convert_to_list(&app.user_playlist_names);
+    let user_playlist_names =
+        convert_to_list(app.user_playlists.iter().map(|playlist| &playlist.name));
     let user_playlist_list = List::new(user_playlist_names).block(playlist_block_user.clone());
 
     f.render_widget(user_playlist_list, content_chunk[2]);
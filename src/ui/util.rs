@@ -1,21 +1,58 @@
 use ratatui::{
     layout::Constraint,
     style::{Color, Style, Stylize},
+    text::{Line, Span},
     widgets::{Block, Cell, Row, Table},
 };
 
 use ratatui::widgets::ListItem;
 
-// Helper function to convert a list of strings to a vector of ListItems
-pub fn convert_to_list<'a>(names: &'a [String]) -> Vec<ListItem<'a>> {
+use crate::app::App;
+use crate::enums::Severity;
+use crate::models::{Album, Artist, Playlist, Show, Track};
+
+/// Color of the notifications and log entries of a severity
+pub fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Info => Color::Cyan,
+        Severity::Success => Color::Green,
+        Severity::Warning => Color::Yellow,
+        Severity::Error => Color::Red,
+    }
+}
+
+// Helper function to convert a list of names to a vector of ListItems
+pub fn convert_to_list<'a>(names: impl IntoIterator<Item = &'a String>) -> Vec<ListItem<'static>> {
     let mut search_results = Vec::new();
 
     for name in names {
-        search_results.push(ListItem::new(format!("{}", name)));
+        search_results.push(ListItem::new(name.to_string()));
     }
     search_results
 }
 
+/// Title of the player block, with a warning while Spotify is rate limiting the requests
+pub fn player_title(app: &App) -> Line<'static> {
+    let mut title = vec![Span::raw(format!(
+        "{} ({} | Shuffle: {} | Repeat: {} | Volume: {}%)",
+        app.playback_status,
+        app.current_device_name,
+        app.shuffle_status,
+        app.repeat_status,
+        app.current_device_volume
+    ))];
+    if let Some(wait) = app.requests.rate_limited_for() {
+        title.push(Span::styled(
+            format!(
+                " Rate limited by Spotify, retrying in {}s",
+                wait.as_secs() + 1
+            ),
+            Style::new().fg(severity_color(Severity::Warning)),
+        ));
+    }
+    Line::from(title)
+}
+
 /// Formats a duration in milliseconds to minutes and seconds with leading zeros
 pub fn format_duration(duration: i64) -> String {
     let minutes = duration / 60000;
@@ -26,47 +63,27 @@ pub fn format_duration(duration: i64) -> String {
 /*
  Creates a table UI for displaying track information.
 
- This function takes various data about tracks (names, artists, albums, durations),
- a styling block, highlight color, and background color, and returns a Table widget
- configured to display the track information.
+ This function takes the tracks, a styling block, highlight color, and background color,
+ and returns a Table widget configured to display the track information.
 */
-pub fn track_table_ui(
-    names: Vec<String>,
-    artist_names: Vec<String>,
-    album_names: Vec<String>,
-    duration: Vec<i64>,
-    block: Block,
+pub fn track_table_ui<'a>(
+    tracks: &[Track],
+    block: Block<'a>,
     highlight_color: Color,
     background_color: Color,
     inactive_border_color: Color,
-) -> Table {
-    let tracks: Vec<(usize, String, String, String, String)> = names
-        .iter()
-        .enumerate()
-        .zip(artist_names.iter())
-        .zip(album_names.iter())
-        .zip(duration.iter().map(|d| format_duration(*d)))
-        .map(|((((index, name), artist), album), duration)| {
-            (
-                index + 1,
-                name.clone(),
-                artist.clone(),
-                album.clone(),
-                duration,
-            )
-        })
-        .collect();
-
+) -> Table<'a> {
     let table = Table::new(
         tracks
             .iter()
-            .map(|(index, name, artist, albums, duration)| {
+            .enumerate()
+            .map(|(index, track)| {
                 Row::new(vec![
-                    Cell::from(format!("{}", index)),
-                    Cell::from(name.clone()),
-                    Cell::from(artist.clone()),
-                    Cell::from(albums.clone()),
-                    Cell::from(duration.clone()),
+                    Cell::from(format!("{}", index + 1)),
+                    Cell::from(track.name.clone()),
+                    Cell::from(track.artist_names()),
+                    Cell::from(track.album_name().to_string()),
+                    Cell::from(format_duration(track.duration_ms)),
                 ])
             })
             .collect::<Vec<_>>(),
@@ -99,34 +116,23 @@ pub fn track_table_ui(
     table
 }
 
-pub fn album_table_ui(
-    names: Vec<String>,
-    artist_names: Vec<String>,
-    tracks: Vec<usize>,
-    block: Block,
+pub fn album_table_ui<'a>(
+    albums: &[Album],
+    block: Block<'a>,
     highlight_color: Color,
     background_color: Color,
     inactive_border_color: Color,
-) -> Table {
-    let albums: Vec<(usize, String, String, usize)> = names
-        .iter()
-        .enumerate()
-        .zip(artist_names.iter())
-        .zip(tracks.iter())
-        .map(|(((index, name), artist), track)| {
-            (index + 1, name.clone(), artist.clone(), track.clone())
-        })
-        .collect();
-
+) -> Table<'a> {
     let table = Table::new(
         albums
             .iter()
-            .map(|(index, name, artist, track)| {
+            .enumerate()
+            .map(|(index, album)| {
                 Row::new(vec![
-                    Cell::from(format!("{}", index)),
-                    Cell::from(name.clone()),
-                    Cell::from(artist.clone()),
-                    Cell::from(format!("{}", track)),
+                    Cell::from(format!("{}", index + 1)),
+                    Cell::from(album.name.clone()),
+                    Cell::from(album.artist_names()),
+                    Cell::from(format!("{}", album.total_tracks)),
                 ])
             })
             .collect::<Vec<_>>(),
@@ -157,29 +163,22 @@ pub fn album_table_ui(
     table
 }
 
-pub fn podcast_table_ui(
-    names: Vec<String>,
-    publisher: Vec<String>,
-    block: Block,
+pub fn podcast_table_ui<'a>(
+    podcasts: &[Show],
+    block: Block<'a>,
     highlight_color: Color,
     background_color: Color,
     inactive_border_color: Color,
-) -> Table {
-    let podcasts: Vec<(usize, String, String)> = names
-        .iter()
-        .enumerate()
-        .zip(publisher.iter())
-        .map(|((index, name), publisher)| (index + 1, name.clone(), publisher.clone()))
-        .collect();
-
+) -> Table<'a> {
     let table = Table::new(
         podcasts
             .iter()
-            .map(|(index, name, publisher)| {
+            .enumerate()
+            .map(|(index, podcast)| {
                 Row::new(vec![
-                    Cell::from(format!("{}", index)),
-                    Cell::from(name.clone()),
-                    Cell::from(publisher.clone()),
+                    Cell::from(format!("{}", index + 1)),
+                    Cell::from(podcast.name.clone()),
+                    Cell::from(podcast.publisher.clone()),
                 ])
             })
             .collect::<Vec<_>>(),
@@ -208,26 +207,21 @@ pub fn podcast_table_ui(
     table
 }
 
-pub fn artist_table_ui(
-    names: Vec<String>,
-    block: Block,
+pub fn artist_table_ui<'a>(
+    artists: &[Artist],
+    block: Block<'a>,
     highlight_color: Color,
     background_color: Color,
     inactive_border_color: Color,
-) -> Table {
-    let podcasts: Vec<(usize, String)> = names
-        .iter()
-        .enumerate()
-        .map(|(index, name)| (index + 1, name.clone()))
-        .collect();
-
+) -> Table<'a> {
     let table = Table::new(
-        podcasts
+        artists
             .iter()
-            .map(|(index, name)| {
+            .enumerate()
+            .map(|(index, artist)| {
                 Row::new(vec![
-                    Cell::from(format!("{}", index)),
-                    Cell::from(name.clone()),
+                    Cell::from(format!("{}", index + 1)),
+                    Cell::from(artist.name.clone()),
                 ])
             })
             .collect::<Vec<_>>(),
@@ -291,34 +285,23 @@ pub fn help_table_ui(
     table
 }
 
-pub fn new_release_table_ui(
-    names: Vec<String>,
-    artist_names: Vec<String>,
-    duration: Vec<i64>,
-    block: Block,
+pub fn new_release_table_ui<'a>(
+    tracks: &[Track],
+    block: Block<'a>,
     highlight_color: Color,
     background_color: Color,
     inactive_border_color: Color,
-) -> Table {
-    let tracks: Vec<(usize, String, String, String)> = names
-        .iter()
-        .enumerate()
-        .zip(artist_names.iter())
-        .zip(duration.iter().map(|d| format_duration(*d)))
-        .map(|(((index, name), artist), duration)| {
-            (index + 1, name.clone(), artist.clone(), duration)
-        })
-        .collect();
-
+) -> Table<'a> {
     let table = Table::new(
         tracks
             .iter()
-            .map(|(index, name, artist, duration)| {
+            .enumerate()
+            .map(|(index, track)| {
                 Row::new(vec![
-                    Cell::from(format!("{}", index)),
-                    Cell::from(name.clone()),
-                    Cell::from(artist.clone()),
-                    Cell::from(duration.clone()),
+                    Cell::from(format!("{}", index + 1)),
+                    Cell::from(track.name.clone()),
+                    Cell::from(track.artist_names()),
+                    Cell::from(format_duration(track.duration_ms)),
                 ])
             })
             .collect::<Vec<_>>(),
@@ -349,29 +332,22 @@ pub fn new_release_table_ui(
     table
 }
 
-pub fn made_fy_table_ui(
-    names: Vec<String>,
-    track_total: Vec<i64>,
-    block: Block,
+pub fn made_fy_table_ui<'a>(
+    playlists: &[Playlist],
+    block: Block<'a>,
     highlight_color: Color,
     background_color: Color,
     inactive_border_color: Color,
-) -> Table {
-    let tracks: Vec<(usize, String, i64)> = names
-        .iter()
-        .enumerate()
-        .zip(track_total.iter())
-        .map(|((index, name), track_total)| (index + 1, name.clone(), track_total.clone()))
-        .collect();
-
+) -> Table<'a> {
     let table = Table::new(
-        tracks
+        playlists
             .iter()
-            .map(|(index, name, track_total)| {
+            .enumerate()
+            .map(|(index, playlist)| {
                 Row::new(vec![
-                    Cell::from(format!("{}", index)),
-                    Cell::from(name.clone()),
-                    Cell::from(format!("{}", track_total)),
+                    Cell::from(format!("{}", index + 1)),
+                    Cell::from(playlist.name.clone()),
+                    Cell::from(format!("{}", playlist.total_tracks)),
                 ])
             })
             .collect::<Vec<_>>(),